@@ -0,0 +1,89 @@
+use crate::constants::{CARRIAGE_RETURN, NEW_LINE};
+use crate::parser::types::Document;
+
+mod tests;
+
+pub fn is_line_terminator(c: char) -> bool {
+    c == NEW_LINE || c == CARRIAGE_RETURN
+}
+
+pub fn is_valid_name(value: &str) -> bool {
+    let mut chars = value.chars();
+
+    let first_char = match chars.next() {
+        Some(c) => c,
+        None => return false,
+    };
+
+    if !first_char.is_alphabetic() && first_char != '_' {
+        return false;
+    }
+
+    for c in chars {
+        if !c.is_alphabetic() && !c.is_ascii_digit() && c != '_' {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Names starting with `__` are reserved for the introspection system.
+/// https://spec.graphql.org/October2021/#sec-Names.Reserved-Names
+pub fn is_reserved_name(value: &str) -> bool {
+    value.starts_with("__")
+}
+
+/// Returns the name of every operation in the document, in source order.
+/// Anonymous operations are returned as `None`.
+pub fn extract_operation_names(document: &Document) -> Vec<Option<&str>> {
+    document
+        .operations()
+        .map(|operation| operation.name.as_ref().map(|name| name.value.as_str()))
+        .collect()
+}
+
+/// Returns the name of every fragment in the document, in source order.
+pub fn extract_fragment_names(document: &Document) -> Vec<&str> {
+    document
+        .fragments()
+        .map(|fragment| fragment.name.value.as_str())
+        .collect()
+}
+
+/// The number of single character insertions, deletions and substitutions it
+/// takes to turn one string into the other.
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<char>>();
+    let mut previous_row = (0..=b.len()).collect::<Vec<usize>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + usize::from(a_char != *b_char);
+            let insertion = row[j] + 1;
+            let deletion = previous_row[j + 1] + 1;
+            row.push(substitution.min(insertion).min(deletion));
+        }
+        previous_row = row;
+    }
+
+    previous_row[b.len()]
+}
+
+/// The candidate closest to the name, to suggest in place of a name that
+/// doesn't exist. Candidates that differ in more than about two fifths of the
+/// name aren't close enough to suggest.
+pub fn closest_name<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let threshold = name.chars().count() * 2 / 5 + 1;
+
+    candidates
+        .into_iter()
+        .map(|candidate| (levenshtein_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
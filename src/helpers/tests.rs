@@ -0,0 +1,76 @@
+#![cfg(test)]
+
+use super::*;
+use crate::parser::types::ParseOptions;
+use crate::parser::{parse_strict, parse_with_options};
+
+#[test]
+fn it_detects_reserved_names() {
+    assert!(is_reserved_name("__typename"));
+    assert!(!is_reserved_name("_private"));
+    assert!(is_valid_name("__typename"));
+}
+
+#[test]
+fn it_extracts_operation_names() {
+    let source = r#"
+        query First { a }
+        mutation Second { b }
+        fragment Fields on User { id }
+        query { c }
+    "#;
+
+    // the unnamed operation is invalid alongside the others, but the
+    // recovered document still holds all three
+    let options = ParseOptions {
+        recover_errors: true,
+    };
+    let (document, _) = parse_with_options(source, options);
+    let document = document.unwrap();
+
+    assert_eq!(
+        extract_operation_names(&document),
+        vec![Some("First"), Some("Second"), None]
+    );
+}
+
+#[test]
+fn it_extracts_fragment_names() {
+    let source = r#"
+        fragment UserFields on User { id }
+        query { user { ...UserFields } }
+        fragment PostFields on Post { title }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    assert_eq!(
+        extract_fragment_names(&document),
+        vec!["UserFields", "PostFields"]
+    );
+}
+
+#[test]
+fn it_extracts_nothing_from_schema_documents() {
+    let document = parse_strict(String::from("scalar Date")).unwrap();
+
+    assert!(extract_operation_names(&document).is_empty());
+    assert!(extract_fragment_names(&document).is_empty());
+}
+
+#[test]
+fn it_measures_the_levenshtein_distance() {
+    assert_eq!(levenshtein_distance("name", "name"), 0);
+    assert_eq!(levenshtein_distance("", "name"), 4);
+    assert_eq!(levenshtein_distance("nmae", "name"), 2);
+    assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+}
+
+#[test]
+fn it_finds_the_closest_name() {
+    let fields = ["id", "name", "friends"];
+
+    assert_eq!(closest_name("nam", fields), Some("name"));
+    assert_eq!(closest_name("freinds", fields), Some("friends"));
+    assert_eq!(closest_name("foobar", fields), None);
+}
@@ -1,6 +1,8 @@
 use crate::constants::{BOM, CARRIAGE_RETURN, NEW_LINE, SPACE, TAB};
 use crate::helpers::is_line_terminator;
-use crate::lexer::types::{char_to_punctuator, LexicalToken, LexicalTokenType, Punctuator};
+use crate::lexer::types::{
+    char_to_punctuator, LexerOptions, LexicalToken, LexicalTokenType, Punctuator,
+};
 use crate::lsp::types::{Diagnostic, DiagnosticSeverity, Position, Range};
 
 pub mod types;
@@ -8,66 +10,178 @@ pub mod types;
 mod tests;
 
 pub fn lex(source: String) -> Result<Vec<LexicalToken>, Diagnostic> {
-    let mut lexer = Lexer::new(source);
-    lexer.lex()
+    LexerIter::new(Lexer::new(source, LexerOptions::default())).collect()
+}
+
+pub fn lex_with_options(
+    source: &str,
+    options: LexerOptions,
+) -> Result<Vec<LexicalToken>, Diagnostic> {
+    LexerIter::new(Lexer::new(source.to_string(), options)).collect()
+}
+
+/// Lazily lexes the source one token at a time instead of collecting every
+/// token up front.
+pub fn lex_iter(source: &str) -> LexerIter {
+    lex_iter_with_options(source, LexerOptions::default())
+}
+
+pub fn lex_iter_with_options(source: &str, options: LexerOptions) -> LexerIter {
+    LexerIter::new(Lexer::new(source.to_string(), options))
+}
+
+/// Iterator over the tokens of a source. Yields every token up to and
+/// including `EOF`, or up to and including the first error.
+pub struct LexerIter {
+    inner: Lexer,
+    done: bool,
+}
+
+impl LexerIter {
+    fn new(inner: Lexer) -> LexerIter {
+        LexerIter { inner, done: false }
+    }
+
+    /// Non-fatal diagnostics reported for the tokens read so far.
+    pub fn warnings(&self) -> &[Diagnostic] {
+        &self.inner.warnings
+    }
+}
+
+impl Iterator for LexerIter {
+    type Item = Result<LexicalToken, Diagnostic>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let token = self.inner.next_token();
+
+        match &token {
+            Ok(token) if token.token_type != LexicalTokenType::EOF => {}
+            _ => self.done = true,
+        }
+
+        Some(token)
+    }
 }
 
 struct Lexer {
     source: String,
+    options: LexerOptions,
+    warnings: Vec<Diagnostic>,
     ptr: usize,
+    byte_offset: usize,
     character: usize,
     line: usize,
 }
 
 impl Lexer {
-    pub fn new(source: String) -> Lexer {
+    pub fn new(source: String, options: LexerOptions) -> Lexer {
         Lexer {
             source,
+            options,
+            warnings: Vec::new(),
             ptr: 0,
+            byte_offset: 0,
             character: 0,
             line: 0,
         }
     }
 
-    pub fn lex(&mut self) -> Result<Vec<LexicalToken>, Diagnostic> {
-        let mut tokens: Vec<LexicalToken> = Vec::new();
-
+    /// Reads the next token from the source, skipping over any ignored
+    /// tokens. Returns an `EOF` token once the source is exhausted.
+    pub fn next_token(&mut self) -> Result<LexicalToken, Diagnostic> {
         while let Some(c) = self.peek() {
             match c {
                 // Ignored tokens
                 // https://spec.graphql.org/October2021/#sec-Language.Source-Text.Ignored-Tokens
-                SPACE | TAB | BOM | ',' => {
+                SPACE | TAB | ',' => {
+                    self.next();
+                }
+
+                // https://spec.graphql.org/October2021/#sec-Unicode
+                BOM => {
+                    if self.ptr > 0 {
+                        return Err(Diagnostic::new(
+                            DiagnosticSeverity::Error,
+                            String::from(
+                                "Byte-order mark is only allowed at the start of the document",
+                            ),
+                            Range::new(
+                                Position::new(self.line, self.character, self.byte_offset),
+                                Position::new(
+                                    self.line,
+                                    self.character + 1,
+                                    self.byte_offset + BOM.len_utf8(),
+                                ),
+                            ),
+                        ));
+                    }
+
+                    self.next();
+                }
+
+                NEW_LINE => {
+                    self.line += 1;
                     self.next();
+                    self.character = 0;
                 }
 
-                NEW_LINE | CARRIAGE_RETURN => {
+                CARRIAGE_RETURN => {
                     self.line += 1;
                     self.next();
+
+                    // `\r\n` is a single line terminator
+                    if self.peek() == Some(NEW_LINE) {
+                        self.next();
+                    }
+
                     self.character = 0;
                 }
 
                 // Comments
                 // https://spec.graphql.org/October2021/#sec-Comments
-                '#' => self.ignore_while(|c| !is_line_terminator(c)),
+                '#' => {
+                    if !self.options.preserve_comments {
+                        self.ignore_while(|c| !is_line_terminator(c));
+                        continue;
+                    }
+
+                    let start_position = Position::new(self.line, self.character, self.byte_offset);
+
+                    self.next();
+                    let value = self.consume_while(|c| !is_line_terminator(c));
+
+                    return Ok(LexicalToken::new(
+                        LexicalTokenType::Comment(value),
+                        Range::new(
+                            start_position,
+                            Position::new(self.line, self.character, self.byte_offset),
+                        ),
+                    ));
+                }
 
                 // Punctuators
                 // https://spec.graphql.org/October2021/#sec-Punctuators
                 '!' | '$' | '&' | '(' | ')' | ':' | '=' | '@' | '[' | ']' | '{' | '}' | '|' => {
                     let punctuator = char_to_punctuator(c);
                     let character = self.character;
+                    let byte_offset = self.byte_offset;
 
                     self.next();
 
-                    tokens.push(LexicalToken::new(
+                    return Ok(LexicalToken::new(
                         LexicalTokenType::Punctuator(punctuator),
                         Range::new(
-                            Position::new(self.line, character),
-                            Position::new(self.line, self.character),
+                            Position::new(self.line, character, byte_offset),
+                            Position::new(self.line, self.character, self.byte_offset),
                         ),
                     ));
                 }
                 '.' => {
-                    let start_position = Position::new(self.line, self.character);
+                    let start_position = Position::new(self.line, self.character, self.byte_offset);
 
                     self.next();
                     self.expect_peek('.')?;
@@ -75,46 +189,50 @@ impl Lexer {
                     self.expect_peek('.')?;
                     self.next();
 
-                    tokens.push(LexicalToken::new(
+                    return Ok(LexicalToken::new(
                         LexicalTokenType::Punctuator(Punctuator::Ellipsis),
-                        Range::new(start_position, Position::new(self.line, self.character)),
+                        Range::new(
+                            start_position,
+                            Position::new(self.line, self.character, self.byte_offset),
+                        ),
                     ));
                 }
                 '"' => {
-                    // if self.peek_at(1) == Some('"') && self.peek_at(2) == Some('"') {
-                    //     tokens.push(self.tokenize_block_string()?);
-                    // } else {
-                    tokens.push(self.tokenize_string()?);
-                    // }
+                    if self.peek_at(1) == Some('"') && self.peek_at(2) == Some('"') {
+                        return self.tokenize_block_string();
+                    } else {
+                        return self.tokenize_string();
+                    }
                 }
 
                 '-' => {
-                    tokens.push(self.tokenize_number()?);
+                    return self.tokenize_number();
                 }
 
                 _ => {
                     let character = self.character;
                     let line = self.line;
+                    let byte_offset = self.byte_offset;
 
                     if c.is_ascii_digit() {
-                        tokens.push(self.tokenize_number()?);
+                        return self.tokenize_number();
                     } else if c.is_ascii_alphabetic() || c == '_' {
                         let value = self.consume_while(|c| c.is_ascii_alphanumeric() || c == '_');
 
-                        tokens.push(LexicalToken::new(
+                        return Ok(LexicalToken::new(
                             LexicalTokenType::Name(value.clone()),
                             Range::new(
-                                Position::new(line, character),
-                                Position::new(self.line, self.character),
+                                Position::new(line, character, byte_offset),
+                                Position::new(self.line, self.character, self.byte_offset),
                             ),
                         ));
                     } else {
                         return Err(Diagnostic::new(
                             DiagnosticSeverity::Error,
-                            String::from(format!("Unexpected character: {}", c)),
+                            format!("Unexpected character: {}", c),
                             Range::new(
-                                Position::new(line, character),
-                                Position::new(self.line, self.character),
+                                Position::new(line, character, byte_offset),
+                                Position::new(self.line, self.character, self.byte_offset),
                             ),
                         ));
                     }
@@ -122,19 +240,17 @@ impl Lexer {
             }
         }
 
-        tokens.push(LexicalToken::new(
+        Ok(LexicalToken::new(
             LexicalTokenType::EOF,
             Range::new(
-                Position::new(self.line, self.character),
-                Position::new(self.line, self.character),
+                Position::new(self.line, self.character, self.byte_offset),
+                Position::new(self.line, self.character, self.byte_offset),
             ),
-        ));
-
-        Ok(tokens)
+        ))
     }
 
     fn tokenize_string(&mut self) -> Result<LexicalToken, Diagnostic> {
-        let start_position = Position::new(self.line, self.character);
+        let start_position = Position::new(self.line, self.character, self.byte_offset);
         self.expect_next('"')?;
 
         let mut result = String::new();
@@ -144,7 +260,10 @@ impl Lexer {
                 self.next();
                 return Ok(LexicalToken::new(
                     LexicalTokenType::StringValue(result),
-                    Range::new(start_position, Position::new(self.line, self.character)),
+                    Range::new(
+                        start_position,
+                        Position::new(self.line, self.character, self.byte_offset),
+                    ),
                 ));
             }
 
@@ -158,15 +277,43 @@ impl Lexer {
                     Some('t') => result.push('\t'),
                     Some('\\') => result.push('\\'),
                     Some('"') => result.push('"'),
-                    // TODO maybe
-                    // Some('u')
+                    Some(c @ ('/' | 'b' | 'f')) => {
+                        if self.options.strict_escapes {
+                            self.warnings.push(Diagnostic::new(
+                                DiagnosticSeverity::Warning,
+                                format!("Non-strict character escape sequence: \\{}", c),
+                                Range::new(
+                                    Position::new(
+                                        self.line,
+                                        self.character - 1,
+                                        self.byte_offset - 1,
+                                    ),
+                                    Position::new(
+                                        self.line,
+                                        self.character + 1,
+                                        self.byte_offset + 1,
+                                    ),
+                                ),
+                            ));
+                        }
+
+                        result.push(match c {
+                            'b' => '\u{0008}',
+                            'f' => '\u{000C}',
+                            _ => '/',
+                        });
+                    }
+                    Some('u') => {
+                        result.push(self.tokenize_unicode_escape()?);
+                        continue;
+                    }
                     _ => {
                         return Err(Diagnostic::new(
                             DiagnosticSeverity::Error,
                             String::from("Invalid character escape sequence."),
                             Range::new(
-                                Position::new(self.line, self.character),
-                                Position::new(self.line, self.character + 1),
+                                Position::new(self.line, self.character, self.byte_offset),
+                                Position::new(self.line, self.character + 1, self.byte_offset + 1),
                             ),
                         ));
                     }
@@ -180,6 +327,18 @@ impl Lexer {
                 break;
             }
 
+            // control characters may only appear escaped
+            if c.is_ascii_control() && c != TAB {
+                return Err(Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    format!("Invalid character U+{:04X} in string literal", c as u32),
+                    Range::new(
+                        Position::new(self.line, self.character, self.byte_offset),
+                        Position::new(self.line, self.character + 1, self.byte_offset + 1),
+                    ),
+                ));
+            }
+
             result.push(c);
             self.next();
         }
@@ -188,17 +347,120 @@ impl Lexer {
             DiagnosticSeverity::Error,
             String::from("Unterminated string."),
             Range::new(
-                Position::new(self.line, self.character),
-                Position::new(self.line, self.character + 1),
+                Position::new(self.line, self.character, self.byte_offset),
+                Position::new(self.line, self.character + 1, self.byte_offset + 1),
             ),
         ))
     }
 
+    /// Reads the `uXXXX` part of a `\uXXXX` escape sequence, the leading `\`
+    /// must already have been consumed.
+    ///
+    /// https://spec.graphql.org/October2021/#EscapedUnicode
+    fn tokenize_unicode_escape(&mut self) -> Result<char, Diagnostic> {
+        let start_position = Position::new(self.line, self.character - 1, self.byte_offset - 1);
+        self.expect_next('u')?;
+
+        let mut hex = String::new();
+
+        for _ in 0..4 {
+            match self.peek() {
+                Some(c) if c.is_ascii_hexdigit() => {
+                    hex.push(c);
+                    self.next();
+                }
+                _ => {
+                    return Err(Diagnostic::new(
+                        DiagnosticSeverity::Error,
+                        String::from("Invalid Unicode escape sequence, expected hex digit."),
+                        Range::new(
+                            Position::new(self.line, self.character, self.byte_offset),
+                            Position::new(self.line, self.character + 1, self.byte_offset + 1),
+                        ),
+                    ));
+                }
+            }
+        }
+
+        // surrogate code points are not valid unicode scalar values, so
+        // `char::from_u32` rejects them along with anything out of range
+        let code_point = u32::from_str_radix(&hex, 16).unwrap_or_default();
+
+        match char::from_u32(code_point) {
+            Some(c) => Ok(c),
+            None => Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                format!("Invalid Unicode escape sequence: \\u{}.", hex),
+                Range::new(
+                    start_position,
+                    Position::new(self.line, self.character, self.byte_offset),
+                ),
+            )),
+        }
+    }
+
+    /// https://spec.graphql.org/October2021/#BlockString
     fn tokenize_block_string(&mut self) -> Result<LexicalToken, Diagnostic> {
-        unimplemented!()
+        let start_position = Position::new(self.line, self.character, self.byte_offset);
+        self.expect_next('"')?;
+        self.expect_next('"')?;
+        self.expect_next('"')?;
+
+        let mut raw = String::new();
+
+        while let Some(c) = self.peek() {
+            if c == '"' && self.peek_at(1) == Some('"') && self.peek_at(2) == Some('"') {
+                self.next();
+                self.next();
+                self.next();
+
+                return Ok(LexicalToken::new(
+                    LexicalTokenType::BlockStringValue(dedent_block_string(&raw)),
+                    Range::new(
+                        start_position,
+                        Position::new(self.line, self.character, self.byte_offset),
+                    ),
+                ));
+            }
+
+            // `\"""` is the only escape sequence in a block string
+            if c == '\\'
+                && self.peek_at(1) == Some('"')
+                && self.peek_at(2) == Some('"')
+                && self.peek_at(3) == Some('"')
+            {
+                raw.push_str("\"\"\"");
+                self.next();
+                self.next();
+                self.next();
+                self.next();
+                continue;
+            }
+
+            raw.push(c);
+            self.next();
+
+            // a `\r\n` pair only counts as a single line terminator
+            if c == NEW_LINE || (c == CARRIAGE_RETURN && self.peek() != Some(NEW_LINE)) {
+                self.line += 1;
+                self.character = 0;
+            }
+        }
+
+        Err(Diagnostic::new(
+            DiagnosticSeverity::Error,
+            String::from("Unterminated string."),
+            Range::new(
+                Position::new(self.line, self.character, self.byte_offset),
+                Position::new(self.line, self.character + 1, self.byte_offset + 1),
+            ),
+        ))
     }
 
     fn tokenize_number(&mut self) -> Result<LexicalToken, Diagnostic> {
+        // snapshot before the sign so the range covers the whole literal
+        let start_position = Position::new(self.line, self.character, self.byte_offset);
+
         let sign = if let Some('-') = self.peek() {
             self.next();
             "-"
@@ -213,15 +475,29 @@ impl Lexer {
                 DiagnosticSeverity::Error,
                 String::from("Invalid number, expected digit"),
                 Range::new(
-                    Position::new(self.line, self.character),
-                    Position::new(self.line, self.character + 1),
+                    Position::new(self.line, self.character, self.byte_offset),
+                    Position::new(self.line, self.character + 1, self.byte_offset + 1),
                 ),
             ));
         }
 
-        let next = self.peek();
+        // https://spec.graphql.org/October2021/#IntegerPart
+        if number_value.len() > 1 && number_value.starts_with('0') {
+            return Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                String::from("Invalid number literal: leading zeros are not allowed"),
+                Range::new(
+                    start_position,
+                    Position::new(self.line, self.character, self.byte_offset),
+                ),
+            ));
+        }
+
+        let mut float_value = format!("{}{}", sign, number_value);
+        let mut is_float = false;
 
-        if let Some('.') = next {
+        // https://spec.graphql.org/October2021/#FractionalPart
+        if let Some('.') = self.peek() {
             self.next();
             let decimal_value = self.consume_while(|c| c.is_ascii_digit());
 
@@ -230,62 +506,82 @@ impl Lexer {
                     DiagnosticSeverity::Error,
                     String::from("Invalid number, expected digit"),
                     Range::new(
-                        Position::new(self.line, self.character),
-                        Position::new(self.line, self.character + 1),
+                        Position::new(self.line, self.character, self.byte_offset),
+                        Position::new(self.line, self.character + 1, self.byte_offset + 1),
                     ),
                 ));
             }
 
-            let parsed_float = format!("{}{}.{}", sign, number_value, decimal_value).parse::<f32>();
+            float_value.push('.');
+            float_value.push_str(&decimal_value);
+            is_float = true;
+        }
 
-            match parsed_float {
-                Ok(value) => {
-                    return Ok(LexicalToken::new(
-                        LexicalTokenType::FloatValue(value),
-                        Range::new(
-                            Position::new(self.line, self.character),
-                            Position::new(
-                                self.line,
-                                self.character + number_value.len() + decimal_value.len(),
-                            ),
-                        ),
-                    ));
-                }
-                Err(_) => {
-                    return Err(Diagnostic::new(
-                        DiagnosticSeverity::Error,
-                        String::from("Invalid number"),
-                        Range::new(
-                            Position::new(self.line, self.character),
-                            Position::new(self.line, self.character + 1),
-                        ),
-                    ));
-                }
+        // https://spec.graphql.org/October2021/#ExponentPart
+        if let Some('e' | 'E') = self.peek() {
+            self.next();
+            float_value.push('e');
+
+            if let Some(c @ ('+' | '-')) = self.peek() {
+                self.next();
+                float_value.push(c);
             }
-        }
 
-        let parsed_int = format!("{}{}", sign, number_value).parse::<i32>();
+            let exponent_value = self.consume_while(|c| c.is_ascii_digit());
 
-        match parsed_int {
-            Ok(value) => {
-                return Ok(LexicalToken::new(
-                    LexicalTokenType::IntValue(value),
+            if exponent_value.is_empty() {
+                return Err(Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    String::from("Invalid number, expected digit"),
                     Range::new(
-                        Position::new(self.line, self.character),
-                        Position::new(self.line, self.character + number_value.len()),
+                        Position::new(self.line, self.character, self.byte_offset),
+                        Position::new(self.line, self.character + 1, self.byte_offset + 1),
                     ),
                 ));
             }
-            Err(_) => {
-                return Err(Diagnostic::new(
+
+            float_value.push_str(&exponent_value);
+            is_float = true;
+        }
+
+        if is_float {
+            return match float_value.parse::<f64>() {
+                Ok(value) => Ok(LexicalToken::new(
+                    LexicalTokenType::FloatValue(value),
+                    Range::new(
+                        start_position,
+                        Position::new(self.line, self.character, self.byte_offset),
+                    ),
+                )),
+                Err(_) => Err(Diagnostic::new(
                     DiagnosticSeverity::Error,
                     String::from("Invalid number"),
                     Range::new(
-                        Position::new(self.line, self.character),
-                        Position::new(self.line, self.character + 1),
+                        start_position,
+                        Position::new(self.line, self.character, self.byte_offset),
                     ),
-                ));
-            }
+                )),
+            };
+        }
+
+        let parsed_int = format!("{}{}", sign, number_value).parse::<i64>();
+
+        match parsed_int {
+            Ok(value) => Ok(LexicalToken::new(
+                LexicalTokenType::IntValue(value),
+                Range::new(
+                    start_position,
+                    Position::new(self.line, self.character, self.byte_offset),
+                ),
+            )),
+            Err(_) => Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                String::from("Invalid number"),
+                Range::new(
+                    start_position,
+                    Position::new(self.line, self.character, self.byte_offset),
+                ),
+            )),
         }
     }
 
@@ -293,6 +589,10 @@ impl Lexer {
         self.source.chars().nth(self.ptr)
     }
 
+    fn peek_at(&self, n: usize) -> Option<char> {
+        self.source.chars().nth(self.ptr + n)
+    }
+
     fn expect_next(&mut self, expected: char) -> Result<char, Diagnostic> {
         let next = self.next();
 
@@ -300,18 +600,18 @@ impl Lexer {
             Some(c) if c == expected => Ok(c),
             Some(c) => Err(Diagnostic::new(
                 DiagnosticSeverity::Error,
-                String::from(format!("Expected \"{}\", found \"{}\"", expected, c)),
+                format!("Expected \"{}\", found \"{}\"", expected, c),
                 Range::new(
-                    Position::new(self.line, self.character),
-                    Position::new(self.line, self.character + 1),
+                    Position::new(self.line, self.character, self.byte_offset),
+                    Position::new(self.line, self.character + 1, self.byte_offset + 1),
                 ),
             )),
             None => Err(Diagnostic::new(
                 DiagnosticSeverity::Error,
-                String::from(format!("Expected \"{}\", found EOF", expected)),
+                format!("Expected \"{}\", found EOF", expected),
                 Range::new(
-                    Position::new(self.line, self.character),
-                    Position::new(self.line, self.character + 1),
+                    Position::new(self.line, self.character, self.byte_offset),
+                    Position::new(self.line, self.character + 1, self.byte_offset + 1),
                 ),
             )),
         }
@@ -324,18 +624,18 @@ impl Lexer {
             Some(c) if c == expected => Ok(c),
             Some(c) => Err(Diagnostic::new(
                 DiagnosticSeverity::Error,
-                String::from(format!("Expected \"{}\", found \"{}\"", expected, c)),
+                format!("Expected \"{}\", found \"{}\"", expected, c),
                 Range::new(
-                    Position::new(self.line, self.character),
-                    Position::new(self.line, self.character + 1),
+                    Position::new(self.line, self.character, self.byte_offset),
+                    Position::new(self.line, self.character + 1, self.byte_offset + 1),
                 ),
             )),
             None => Err(Diagnostic::new(
                 DiagnosticSeverity::Error,
-                String::from(format!("Expected \"{}\", found EOF", expected)),
+                format!("Expected \"{}\", found EOF", expected),
                 Range::new(
-                    Position::new(self.line, self.character),
-                    Position::new(self.line, self.character + 1),
+                    Position::new(self.line, self.character, self.byte_offset),
+                    Position::new(self.line, self.character + 1, self.byte_offset + 1),
                 ),
             )),
         }
@@ -344,7 +644,8 @@ impl Lexer {
     fn next(&mut self) -> Option<char> {
         let next_char = self.peek();
         self.ptr += 1;
-        self.character += 1;
+        self.character += next_char.map_or(1, |c| c.len_utf16());
+        self.byte_offset += next_char.map_or(1, |c| c.len_utf8());
         next_char
     }
 
@@ -387,3 +688,46 @@ impl Lexer {
         }
     }
 }
+
+/// Removes the common indentation and the leading and trailing blank lines
+/// from the raw contents of a block string.
+///
+/// https://spec.graphql.org/October2021/#BlockStringValue()
+fn dedent_block_string(raw: &str) -> String {
+    let normalized = raw.replace("\r\n", "\n").replace(CARRIAGE_RETURN, "\n");
+    let lines = normalized.split(NEW_LINE).collect::<Vec<&str>>();
+
+    let is_whitespace = |c: &char| *c == SPACE || *c == TAB;
+
+    let common_indent = lines
+        .iter()
+        .skip(1)
+        .filter(|line| !line.chars().all(|c| is_whitespace(&c)))
+        .map(|line| line.chars().take_while(is_whitespace).count())
+        .min();
+
+    let mut lines = lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| match common_indent {
+            Some(indent) if index > 0 => line.chars().skip(indent).collect(),
+            _ => line.to_string(),
+        })
+        .collect::<Vec<String>>();
+
+    while lines
+        .first()
+        .is_some_and(|line| line.chars().all(|c| is_whitespace(&c)))
+    {
+        lines.remove(0);
+    }
+
+    while lines
+        .last()
+        .is_some_and(|line| line.chars().all(|c| is_whitespace(&c)))
+    {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
@@ -108,10 +108,7 @@ fn it_tokenizes_valid_float_values() {
 
 #[test]
 fn it_does_not_tokenize_invalid_number_values() {
-    let invalid_int_values = vec![
-        // "01", // TODO
-        "-", ".0", ".0",
-    ];
+    let invalid_int_values = vec!["01", "-", ".0", ".0"];
 
     for value in invalid_int_values {
         let source = String::from(value);
@@ -126,3 +123,383 @@ fn it_returns_error_on_invalid_character() {
     let result = lex(source);
     assert!(result.is_err());
 }
+
+#[test]
+fn it_tokenizes_block_strings() {
+    let source = String::from("\"\"\"Hello, \"World\"!\"\"\"");
+    let tokens = lex(source).unwrap();
+    let token = tokens.first().unwrap();
+    assert_eq!(
+        token.token_type,
+        LexicalTokenType::BlockStringValue(String::from("Hello, \"World\"!"))
+    );
+}
+
+#[test]
+fn it_does_not_interpret_escapes_in_block_strings() {
+    let source = String::from(r#""""C:\path\n""""#);
+    let tokens = lex(source).unwrap();
+    let token = tokens.first().unwrap();
+    assert_eq!(
+        token.token_type,
+        LexicalTokenType::BlockStringValue(String::from(r"C:\path\n"))
+    );
+}
+
+#[test]
+fn it_dedents_block_strings_and_strips_blank_lines() {
+    let source = String::from(
+        "\"\"\"\n\n    Hello,\n      World!\n\n    Yours,\n      GraphQL.\n  \n\"\"\"",
+    );
+    let tokens = lex(source).unwrap();
+    let token = tokens.first().unwrap();
+    assert_eq!(
+        token.token_type,
+        LexicalTokenType::BlockStringValue(String::from("Hello,\n  World!\n\nYours,\n  GraphQL."))
+    );
+}
+
+#[test]
+fn it_does_not_terminate_block_strings_on_escaped_triple_quotes() {
+    let source = String::from(r#""""contains \""" inside""" next"#);
+    let tokens = lex(source).unwrap();
+
+    assert_eq!(
+        tokens[0].token_type,
+        LexicalTokenType::BlockStringValue(String::from("contains \"\"\" inside"))
+    );
+    assert_eq!(
+        tokens[1].token_type,
+        LexicalTokenType::Name(String::from("next"))
+    );
+}
+
+#[test]
+fn it_tracks_lines_across_block_strings() {
+    let source = String::from("\"\"\"\n  one\n  two\n\"\"\" next");
+    let tokens = lex(source).unwrap();
+
+    assert_eq!(
+        tokens[1].token_type,
+        LexicalTokenType::Name(String::from("next"))
+    );
+    assert_eq!(tokens[1].position.start, Position::new(3, 4, 20));
+}
+
+#[test]
+fn it_errs_if_block_string_is_unterminated() {
+    let source = String::from("\"\"\"Hello, World!\"\"");
+    let result = lex(source);
+    assert!(result.is_err());
+}
+
+#[test]
+fn it_tokenizes_unicode_escape_sequences() {
+    let cases = vec![
+        ("\"\\u0041\"", "A"),
+        ("\"\\u00e9\"", "é"),
+        ("\"caf\\u00E9!\"", "café!"),
+    ];
+
+    for (source, expected) in cases {
+        let tokens = lex(String::from(source)).unwrap();
+        let token = tokens.first().unwrap();
+        assert_eq!(
+            token.token_type,
+            LexicalTokenType::StringValue(String::from(expected))
+        );
+    }
+}
+
+#[test]
+fn it_errs_for_surrogate_unicode_escape_sequences() {
+    let source = String::from("\"\\uD800\"");
+    let result = lex(source);
+    assert!(result.is_err());
+}
+
+#[test]
+fn it_errs_for_incomplete_unicode_escape_sequences() {
+    let invalid_escapes = vec!["\"\\u004\"", "\"\\u\"", "\"\\u00G1\""];
+
+    for source in invalid_escapes {
+        let result = lex(String::from(source));
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn it_tokenizes_float_values_with_exponents() {
+    let valid_float_values = vec![
+        ("1e5", 1e5),
+        ("1.0e5", 1.0e5),
+        ("2.5E-3", 2.5E-3),
+        ("1.0E+10", 1.0E+10),
+        ("-1e-2", -1e-2),
+    ];
+
+    for (value, expected) in valid_float_values {
+        let source = String::from(value);
+        let tokens = lex(source).unwrap();
+        let token = tokens.first().unwrap();
+        assert_eq!(token.token_type, LexicalTokenType::FloatValue(expected));
+    }
+}
+
+#[test]
+fn it_does_not_tokenize_exponents_without_digits() {
+    let invalid_float_values = vec!["1e", "1.0E", "1e+", "2.5e-"];
+
+    for value in invalid_float_values {
+        let source = String::from(value);
+        let result = lex(source);
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn it_preserves_float_precision() {
+    let source = String::from("123.000001");
+    let tokens = lex(source).unwrap();
+    let token = tokens.first().unwrap();
+
+    match token.token_type {
+        LexicalTokenType::FloatValue(value) => assert_eq!(format!("{:.6}", value), "123.000001"),
+        _ => panic!("Expected FloatValue"),
+    }
+}
+
+#[test]
+fn it_does_not_tokenize_numbers_with_leading_zeros() {
+    let invalid_values = vec!["01", "007", "-01", "00.5"];
+
+    for value in invalid_values {
+        let source = String::from(value);
+        let result = lex(source);
+        assert!(result.is_err());
+    }
+
+    let valid_values = vec!["0", "10", "-0", "0.5"];
+
+    for value in valid_values {
+        let source = String::from(value);
+        let result = lex(source);
+        assert!(result.is_ok());
+    }
+}
+
+#[test]
+fn it_discards_comments_by_default() {
+    let source = String::from("# hello\nname");
+    let tokens = lex(source).unwrap();
+    assert_eq!(
+        tokens[0].token_type,
+        LexicalTokenType::Name(String::from("name"))
+    );
+}
+
+#[test]
+fn it_preserves_comments_when_enabled() {
+    let options = LexerOptions {
+        preserve_comments: true,
+        ..LexerOptions::default()
+    };
+    let tokens = lex_with_options("# hello\nname", options).unwrap();
+
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(
+        tokens[0].token_type,
+        LexicalTokenType::Comment(String::from(" hello"))
+    );
+    assert_eq!(tokens[0].position.start, Position::new(0, 0, 0));
+    assert_eq!(tokens[0].position.end, Position::new(0, 7, 7));
+    assert_eq!(
+        tokens[1].token_type,
+        LexicalTokenType::Name(String::from("name"))
+    );
+}
+
+#[test]
+fn it_tracks_byte_offsets_after_multi_byte_characters() {
+    let source = String::from("\"é\" name");
+    let tokens = lex(source).unwrap();
+
+    assert_eq!(tokens[0].position.end.character, 3);
+    assert_eq!(tokens[0].position.end.offset, 4);
+
+    assert_eq!(
+        tokens[1].token_type,
+        LexicalTokenType::Name(String::from("name"))
+    );
+    assert_eq!(tokens[1].position.start.character, 4);
+    assert_eq!(tokens[1].position.start.offset, 5);
+}
+
+#[test]
+fn it_counts_characters_in_utf16_code_units() {
+    let source = String::from("\"😀\" name");
+    let tokens = lex(source).unwrap();
+
+    // the emoji is a surrogate pair in UTF-16, and four bytes in UTF-8
+    assert_eq!(tokens[0].position.end.character, 4);
+    assert_eq!(tokens[0].position.end.offset, 6);
+    assert_eq!(tokens[1].position.start.character, 5);
+    assert_eq!(tokens[1].position.start.offset, 7);
+}
+
+#[test]
+fn it_lazily_tokenizes_with_lex_iter() {
+    let mut tokens = lex_iter("query { name }");
+
+    assert_eq!(
+        tokens.next().unwrap().unwrap().token_type,
+        LexicalTokenType::Name(String::from("query"))
+    );
+
+    let rest = tokens.collect::<Result<Vec<LexicalToken>, Diagnostic>>();
+    assert_eq!(rest.unwrap().len(), 4);
+}
+
+#[test]
+fn it_stops_lex_iter_after_an_error() {
+    let tokens = lex_iter("name ? other").collect::<Vec<Result<LexicalToken, Diagnostic>>>();
+
+    assert_eq!(tokens.len(), 2);
+    assert!(tokens[0].is_ok());
+    assert!(tokens[1].is_err());
+}
+
+#[test]
+fn it_produces_the_same_tokens_with_lex_and_lex_iter() {
+    let source = "query Test($id: ID = 1) { user(id: $id) { ...on User { name } } }";
+
+    let eager = lex(String::from(source)).unwrap();
+    let lazy = lex_iter(source)
+        .collect::<Result<Vec<LexicalToken>, Diagnostic>>()
+        .unwrap();
+
+    assert_eq!(eager, lazy);
+}
+
+#[test]
+fn it_tokenizes_json_style_escape_sequences() {
+    let cases = vec![
+        ("\"a\\/b\"", "a/b"),
+        ("\"a\\bb\"", "a\u{0008}b"),
+        ("\"a\\fb\"", "a\u{000C}b"),
+    ];
+
+    for (source, expected) in cases {
+        let mut tokens = lex_iter(source);
+        let token = tokens.next().unwrap().unwrap();
+
+        assert_eq!(
+            token.token_type,
+            LexicalTokenType::StringValue(String::from(expected))
+        );
+        assert!(tokens.warnings().is_empty());
+    }
+}
+
+#[test]
+fn it_warns_for_json_style_escape_sequences_in_strict_mode() {
+    let options = LexerOptions {
+        strict_escapes: true,
+        ..LexerOptions::default()
+    };
+    let mut tokens = lex_iter_with_options("\"a\\/b\" \"a\\nb\"", options);
+
+    let result = tokens
+        .by_ref()
+        .collect::<Result<Vec<LexicalToken>, Diagnostic>>();
+
+    assert!(result.is_ok());
+    assert_eq!(tokens.warnings().len(), 1);
+    assert!(matches!(
+        tokens.warnings()[0].severity,
+        DiagnosticSeverity::Warning
+    ));
+    assert_eq!(tokens.warnings()[0].range.start.character, 2);
+}
+
+#[test]
+fn it_errs_for_control_characters_in_strings() {
+    let invalid_strings = vec![
+        "\"hello\x01world\"",
+        "\"hello\0world\"",
+        "\"hello\x7fworld\"",
+    ];
+
+    for source in invalid_strings {
+        let diagnostic = lex(String::from(source)).unwrap_err();
+        assert!(diagnostic.message.starts_with("Invalid character U+"));
+        assert_eq!(diagnostic.range.start.character, 6);
+    }
+}
+
+#[test]
+fn it_allows_escaped_control_characters_and_tabs_in_strings() {
+    let valid_strings = vec![
+        ("\"hello\\nworld\"", "hello\nworld"),
+        ("\"hello\\u0001world\"", "hello\u{0001}world"),
+        ("\"hello\tworld\"", "hello\tworld"),
+    ];
+
+    for (source, expected) in valid_strings {
+        let tokens = lex(String::from(source)).unwrap();
+        assert_eq!(
+            tokens[0].token_type,
+            LexicalTokenType::StringValue(String::from(expected))
+        );
+    }
+}
+
+#[test]
+fn it_tracks_number_positions() {
+    let tokens = lex(String::from("-42 foo")).unwrap();
+
+    assert_eq!(tokens[0].token_type, LexicalTokenType::IntValue(-42));
+    assert_eq!(tokens[0].position.start.character, 0);
+    assert_eq!(tokens[0].position.end.character, 3);
+
+    assert_eq!(tokens[1].position.start.character, 4);
+    assert_eq!(tokens[1].position.end.character, 7);
+
+    let tokens = lex(String::from("a 1.5e3")).unwrap();
+
+    assert_eq!(tokens[1].position.start.character, 2);
+    assert_eq!(tokens[1].position.end.character, 7);
+}
+
+#[test]
+fn it_treats_crlf_as_a_single_line_terminator() {
+    let sources = vec!["one\ntwo\nthree", "one\r\ntwo\r\nthree", "one\rtwo\rthree"];
+
+    for source in sources {
+        let tokens = lex(String::from(source)).unwrap();
+
+        assert_eq!(
+            tokens[2].token_type,
+            LexicalTokenType::Name(String::from("three"))
+        );
+        assert_eq!(tokens[2].position.start.line, 2);
+        assert_eq!(tokens[2].position.start.character, 0);
+    }
+}
+
+#[test]
+fn it_ignores_a_leading_byte_order_mark() {
+    let source = String::from("\u{FEFF}name");
+    let tokens = lex(source).unwrap();
+    assert_eq!(
+        tokens[0].token_type,
+        LexicalTokenType::Name(String::from("name"))
+    );
+}
+
+#[test]
+fn it_errs_for_a_byte_order_mark_after_the_start() {
+    let source = String::from("name \u{FEFF}other");
+    let result = lex(source);
+    assert!(result.is_err());
+}
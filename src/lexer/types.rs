@@ -57,8 +57,21 @@ impl LexicalToken {
 pub enum LexicalTokenType {
     Punctuator(Punctuator),
     Name(String),
-    IntValue(i32),
-    FloatValue(f32),
+    IntValue(i64),
+    FloatValue(f64),
     StringValue(String),
+    BlockStringValue(String),
+    /// The text of a comment, following the `#` up to the end of the line.
+    /// Only produced when `LexerOptions::preserve_comments` is set.
+    Comment(String),
     EOF,
 }
+
+#[derive(Debug, Clone, Default)]
+pub struct LexerOptions {
+    /// Emit `#` comments as `Comment` tokens instead of discarding them.
+    pub preserve_comments: bool,
+    /// Report the JSON-style `\/`, `\b` and `\f` escapes with a warning,
+    /// since not every GraphQL implementation accepts them.
+    pub strict_escapes: bool,
+}
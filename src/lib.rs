@@ -0,0 +1,24 @@
+/// Traces with the `log` crate when the `log` feature is enabled, and
+/// compiles to nothing otherwise. The `gql_lsp` binary logs to stderr, and
+/// anything embedding the library has to install its own logger. Never print
+/// instead: stdout is the transport when running as a language server.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "log")]
+        log::trace!($($arg)*);
+    };
+}
+
+pub mod constants;
+pub mod errors;
+pub mod helpers;
+pub mod lexer;
+pub mod lsp;
+pub mod parser;
+pub mod print;
+pub mod schema;
+pub mod server;
+pub mod validation;
+pub mod visitor;
+
+pub use server::serve;
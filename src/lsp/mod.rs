@@ -1 +1,4 @@
+pub mod transport;
 pub mod types;
+
+mod tests;
@@ -0,0 +1,130 @@
+#![cfg(test)]
+
+use std::io::{BufReader, ErrorKind};
+
+use serde_json::json;
+
+use super::transport::{read_lsp_message, write_lsp_message};
+use super::types::*;
+
+#[test]
+fn it_orders_positions_by_line_then_character() {
+    let start = Position::new(0, 5, 5);
+    let same_line = Position::new(0, 9, 9);
+    let next_line = Position::new(1, 0, 12);
+
+    assert!(start < same_line);
+    assert!(same_line < next_line);
+    assert!(next_line > start);
+    assert_eq!(start.cmp(&start.clone()), std::cmp::Ordering::Equal);
+}
+
+#[test]
+fn it_orders_ranges_by_start_then_end() {
+    let mut ranges = [
+        Range::new(Position::new(2, 0, 20), Position::new(2, 4, 24)),
+        Range::new(Position::new(0, 0, 0), Position::new(0, 8, 8)),
+        Range::new(Position::new(0, 0, 0), Position::new(0, 4, 4)),
+    ];
+
+    ranges.sort();
+
+    assert_eq!(ranges[0].end.character, 4);
+    assert_eq!(ranges[1].end.character, 8);
+    assert_eq!(ranges[2].start.line, 2);
+}
+
+#[test]
+fn it_checks_if_a_range_contains_a_position() {
+    let range = Range::new(Position::new(1, 2, 10), Position::new(1, 6, 14));
+
+    assert!(range.contains(&Position::new(1, 2, 10)));
+    assert!(range.contains(&Position::new(1, 5, 13)));
+    assert!(!range.contains(&Position::new(1, 6, 14)));
+    assert!(!range.contains(&Position::new(0, 4, 4)));
+}
+
+#[test]
+fn it_contains_the_start_but_not_the_end_of_a_range() {
+    let start = Position::new(0, 2, 2);
+    let end = Position::new(0, 8, 8);
+    let range = Range::new(start.clone(), end.clone());
+
+    assert!(range.contains(&start));
+    assert!(!range.contains(&end));
+    assert!(!Range::ZERO.contains(&Position::ZERO));
+}
+
+#[test]
+fn it_checks_if_ranges_overlap() {
+    let range = Range::new(Position::new(0, 2, 2), Position::new(0, 8, 8));
+    let inside = Range::new(Position::new(0, 4, 4), Position::new(0, 6, 6));
+    let crossing = Range::new(Position::new(0, 6, 6), Position::new(1, 0, 12));
+    let touching = Range::new(Position::new(0, 8, 8), Position::new(0, 10, 10));
+
+    assert!(range.overlaps(&inside));
+    assert!(inside.overlaps(&range));
+    assert!(range.overlaps(&crossing));
+    assert!(!range.overlaps(&touching));
+    assert!(!touching.overlaps(&range));
+}
+
+#[test]
+fn it_reads_framed_messages() {
+    let input: &[u8] = b"Content-Length: 17\r\n\r\n{\"jsonrpc\":\"2.0\"}\
+Content-Length: 8\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{\"id\":1}";
+    let mut reader = BufReader::new(input);
+
+    assert_eq!(
+        read_lsp_message(&mut reader).unwrap(),
+        json!({ "jsonrpc": "2.0" })
+    );
+    assert_eq!(read_lsp_message(&mut reader).unwrap(), json!({ "id": 1 }));
+    assert_eq!(
+        read_lsp_message(&mut reader).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+}
+
+#[test]
+fn it_errs_for_malformed_messages() {
+    let missing_length: &[u8] = b"Content-Type: application/json\r\n\r\n{}";
+    let error = read_lsp_message(&mut BufReader::new(missing_length)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    let invalid_json: &[u8] = b"Content-Length: 5\r\n\r\n{oops";
+    let error = read_lsp_message(&mut BufReader::new(invalid_json)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    let truncated: &[u8] = b"Content-Length: 10\r\n\r\n{}";
+    let error = read_lsp_message(&mut BufReader::new(truncated)).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn it_writes_framed_messages() {
+    let mut output = Vec::new();
+    write_lsp_message(&mut output, &json!({ "id": 1 })).unwrap();
+
+    assert_eq!(output, b"Content-Length: 8\r\n\r\n{\"id\":1}");
+}
+
+#[test]
+fn it_renders_a_diagnostic_with_a_caret_under_the_range() {
+    let diagnostic = Diagnostic::new(
+        DiagnosticSeverity::Error,
+        String::from("Expected Name"),
+        Range::new(Position::new(1, 2, 10), Position::new(1, 5, 13)),
+    )
+    .with_uri("file:///query.graphql");
+
+    assert_eq!(
+        diagnostic.render("query {\n  1234\n}"),
+        concat!(
+            "file:///query.graphql:2:3\n",
+            "Error: \"Expected Name\"\n",
+            "  1234\n",
+            "  ^^^\n",
+        )
+    );
+}
@@ -0,0 +1,58 @@
+use std::io::{self, BufRead, Write};
+
+const CONTENT_LENGTH: &str = "Content-Length";
+
+/// Reads one message from the client. Messages are a JSON body preceded by
+/// headers, of which only `Content-Length` is required.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#baseProtocol
+pub fn read_lsp_message<R: BufRead>(reader: &mut R) -> Result<serde_json::Value, io::Error> {
+    let mut content_length: Option<usize> = None;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Connection closed before the message headers ended",
+            ));
+        }
+
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+
+        // other headers, like `Content-Type`, are ignored
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case(CONTENT_LENGTH) {
+                let length = value.trim().parse::<usize>().map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("Invalid Content-Length header: {}", value.trim()),
+                    )
+                })?;
+                content_length = Some(length);
+            }
+        }
+    }
+
+    let content_length = content_length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "Missing Content-Length header")
+    })?;
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    serde_json::from_slice(&body).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+/// Writes one message to the client, framed with a `Content-Length` header.
+pub fn write_lsp_message<W: Write>(
+    writer: &mut W,
+    msg: &serde_json::Value,
+) -> Result<(), io::Error> {
+    let body = serde_json::to_string(msg)?;
+
+    write!(writer, "{}: {}\r\n\r\n{}", CONTENT_LENGTH, body.len(), body)?;
+    writer.flush()
+}
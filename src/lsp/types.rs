@@ -1,4 +1,7 @@
+use std::cmp::Ordering;
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DiagnosticSeverity {
     Error,
     Warning,
@@ -6,33 +9,92 @@ pub enum DiagnosticSeverity {
     Hint,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub line: usize,
+    /// UTF-16 code units from the start of the line, which is how LSP counts
+    /// characters unless another position encoding is negotiated.
+    /// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#position
     pub character: usize,
+    /// Byte index into the source string
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub offset: usize,
 }
 
 impl Position {
-    pub fn new(line: usize, character: usize) -> Position {
-        Position { line, character }
+    pub const ZERO: Position = Position {
+        line: 0,
+        character: 0,
+        offset: 0,
+    };
+
+    pub fn new(line: usize, character: usize, offset: usize) -> Position {
+        Position {
+            line,
+            character,
+            offset,
+        }
+    }
+}
+
+// Positions are ordered by where they appear in the document. The offset only
+// breaks ties so the ordering stays consistent with `Eq`.
+impl Ord for Position {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.line, self.character, self.offset).cmp(&(other.line, other.character, other.offset))
+    }
+}
+
+impl PartialOrd for Position {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Range {
     pub start: Position,
     pub end: Position,
 }
 
+impl Ord for Range {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then_with(|| self.end.cmp(&other.end))
+    }
+}
+
+impl PartialOrd for Range {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Range {
-    pub fn new(start: Position, end: Position) -> Range {
+    pub const ZERO: Range = Range::new(Position::ZERO, Position::ZERO);
+
+    pub const fn new(start: Position, end: Position) -> Range {
         Range { start, end }
     }
+
+    /// Whether the position falls inside the range. The end is exclusive.
+    pub fn contains(&self, position: &Position) -> bool {
+        position >= &self.start && position < &self.end
+    }
+
+    /// Whether the two ranges share at least one position.
+    pub fn overlaps(&self, other: &Range) -> bool {
+        self.start < other.end && other.start < self.end
+    }
 }
 
 /// Represents a diagnostic, such as a compiler error or warning.
 /// Diagnostic objects are only valid in the scope of a resource
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Diagnostic {
     /// The range at which the message applies
     pub range: Range,
@@ -45,6 +107,9 @@ pub struct Diagnostic {
 
     // The diagnostic's message.
     pub message: String,
+
+    /// The URI of the document the diagnostic belongs to, if known.
+    pub source_uri: Option<String>,
     // The diagnostic's code, which might appear in the user interface.
     // code: Option<i32>,
 
@@ -69,25 +134,47 @@ impl Diagnostic {
             severity,
             message,
             range,
+            source_uri: None,
         }
     }
 
-    pub fn print(&self, source: &str) {
-        println!("{:?}: {:?}", self.severity, self.message);
+    pub fn with_uri(mut self, uri: impl Into<String>) -> Self {
+        self.source_uri = Some(uri.into());
+        self
+    }
+
+    /// Renders the diagnostic for a terminal, with the offending line of the
+    /// source and a caret under the range. Nothing is printed, since stdout
+    /// is the transport when running as a language server.
+    pub fn render(&self, source: &str) -> String {
+        let mut output = String::new();
+
+        if let Some(uri) = &self.source_uri {
+            output.push_str(&format!(
+                "{}:{}:{}\n",
+                uri,
+                self.range.start.line + 1,
+                self.range.start.character + 1
+            ));
+        }
+
+        output.push_str(&format!("{:?}: {:?}\n", self.severity, self.message));
 
         let lines = source.lines().collect::<Vec<&str>>();
         let error_line = lines.get(self.range.start.line);
 
         if let Some(error_line) = error_line {
-            println!("{}", error_line);
-            let mut caret = String::new();
+            output.push_str(error_line);
+            output.push('\n');
             for _ in 0..self.range.start.character {
-                caret.push(' ');
+                output.push(' ');
             }
             for _ in self.range.start.character..self.range.end.character {
-                caret.push('^');
+                output.push('^');
             }
-            println!("{}", caret);
+            output.push('\n');
         }
+
+        output
     }
 }
@@ -1,21 +1,44 @@
-use std::fs;
+use std::io;
+use std::process;
 
-use parser::parse;
+fn main() {
+    #[cfg(feature = "log")]
+    logger::init();
 
-use print::pretty_print::print;
+    let stdin = io::stdin();
+    let stdout = io::stdout();
 
-mod constants;
-mod errors;
-mod helpers;
-mod lexer;
-mod lsp;
-mod parser;
-mod print;
-mod visitor;
+    if let Err(error) = gql_lsp::serve(stdin.lock(), stdout.lock()) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
 
-fn main() {
-    let file = fs::read_to_string("test_document.graphql").expect("Unable to read file");
-    let document = parse(file).unwrap();
-    let pretty = print(&document);
-    println!("{}", pretty);
+/// Writes every log record to stderr, since stdout is the transport. Only
+/// built with the `log` feature, so tracing costs nothing otherwise.
+#[cfg(feature = "log")]
+mod logger {
+    use log::{LevelFilter, Log, Metadata, Record};
+
+    struct StderrLogger;
+
+    impl Log for StderrLogger {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            eprintln!("[{}] {}", record.level(), record.args());
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: StderrLogger = StderrLogger;
+
+    pub fn init() {
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(LevelFilter::Trace);
+        }
+    }
 }
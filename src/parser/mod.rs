@@ -1,6 +1,6 @@
-use crate::helpers::is_valid_name;
-use crate::lexer::lex;
+use crate::helpers::{is_reserved_name, is_valid_name};
 use crate::lexer::types::{LexicalToken, LexicalTokenType, Punctuator};
+use crate::lexer::{lex, lex_iter, LexerIter};
 use crate::lsp::types::{Diagnostic, DiagnosticSeverity, Position, Range};
 use crate::parser::types::{
     Argument, BooleanValue, Definition, Directive, Document, EnumValue, Field, FieldDefinition,
@@ -12,29 +12,195 @@ use crate::parser::types::{
 };
 
 use self::types::{
-    EnumTypeDefinition, EnumValueDefinition, InputObjectTypeDefinition, InterfaceTypeDefinition,
-    UnionTypeDefinition,
+    DirectiveDefinition, DirectiveLocation, EnumTypeDefinition, EnumTypeExtension,
+    EnumValueDefinition, InputObjectTypeDefinition, InputObjectTypeExtension,
+    InterfaceTypeDefinition, InterfaceTypeExtension, Node, ObjectTypeExtension, ParseOptions,
+    ParseResult, ScalarTypeExtension, SchemaExtension, UnionTypeDefinition, UnionTypeExtension,
 };
 
 pub mod types;
 
 mod tests;
 
-pub fn parse(source: String) -> Result<Document, Diagnostic> {
+/// Parses the source into a document. Non-fatal diagnostics, such as warnings,
+/// are returned alongside the document; any error fails the parse.
+pub fn parse(source: &str) -> Result<ParseResult, Vec<Diagnostic>> {
+    let mut lexer = lex_iter(source);
+    let tokens = lexer
+        .by_ref()
+        .collect::<Result<Vec<LexicalToken>, Diagnostic>>()
+        .map_err(|diagnostic| vec![diagnostic])?;
+    let mut diagnostics = lexer.warnings().to_vec();
+
+    let mut parser = Parser::new(tokens);
+    let document = parser.parse().map_err(|diagnostic| vec![diagnostic])?;
+    diagnostics.append(&mut parser.diagnostics);
+
+    Ok(ParseResult {
+        document,
+        diagnostics,
+    })
+}
+
+/// Parses the source into a document, failing on the first error and
+/// discarding any warnings.
+pub fn parse_strict(source: String) -> Result<Document, Diagnostic> {
     let tokens = lex(source)?;
     let mut parser = Parser::new(tokens);
     parser.parse()
 }
 
-#[derive(Debug, Clone)]
-struct Parser {
-    tokens: Vec<LexicalToken>,
-    ptr: usize,
+/// Parses the source, returning every diagnostic found along the way. With
+/// `recover_errors` set the parser carries on past broken definitions and the
+/// document holds whatever could be parsed.
+pub fn parse_with_options(
+    source: &str,
+    options: ParseOptions,
+) -> (Option<Document>, Vec<Diagnostic>) {
+    let tokens = match lex(source.to_string()) {
+        Ok(tokens) => tokens,
+        Err(diagnostic) => return (None, vec![diagnostic]),
+    };
+
+    let mut parser = Parser::new(tokens);
+    parser.options = options;
+
+    match parser.parse() {
+        Ok(document) => (Some(document), parser.diagnostics),
+        Err(diagnostic) => {
+            let mut diagnostics = parser.diagnostics;
+            diagnostics.push(diagnostic);
+            (None, diagnostics)
+        }
+    }
+}
+
+/// Parses the source like `parse_strict`, tagging any diagnostic with the URI of the
+/// document it came from.
+pub fn parse_with_uri(source: &str, uri: &str) -> Result<Document, Diagnostic> {
+    parse_strict(source.to_string()).map_err(|diagnostic| diagnostic.with_uri(uri))
+}
+
+/// Parses a document that may only contain operations and fragments, such as
+/// a query sent to a server.
+/// https://spec.graphql.org/October2021/#ExecutableDocument
+pub fn parse_executable(source: &str) -> Result<Document, Diagnostic> {
+    let document = parse_strict(source.to_string())?;
+
+    for definition in &document.definitions {
+        match definition {
+            Definition::OperationDefinition(_) | Definition::FragmentDefinition(_) => {}
+            _ => {
+                return Err(Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    String::from("Unexpected type system definition in executable document"),
+                    definition.position().clone(),
+                ));
+            }
+        }
+    }
+
+    Ok(document)
+}
+
+/// Parses a document that may only contain type system definitions and
+/// extensions, such as a schema file.
+/// https://spec.graphql.org/October2021/#TypeSystemDocument
+pub fn parse_type_system(source: &str) -> Result<Document, Diagnostic> {
+    let document = parse_strict(source.to_string())?;
+
+    for definition in &document.definitions {
+        if let Definition::OperationDefinition(_) | Definition::FragmentDefinition(_) = definition {
+            return Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                String::from("Unexpected executable definition in type system document"),
+                definition.position().clone(),
+            ));
+        }
+    }
+
+    Ok(document)
+}
+
+/// Parses a single constant value, such as the `defaultValue` of an
+/// introspection result, which is the value as it would be written in GraphQL.
+/// https://spec.graphql.org/October2021/#sec-The-__InputValue-Type
+pub fn parse_const_value(source: &str) -> Result<Value, Diagnostic> {
+    let tokens = lex(source.to_string())?;
+    let mut parser = Parser::new(tokens);
+    let value = parser.parse_const_value()?;
+    parser.expect_next(LexicalTokenType::EOF)?;
+    Ok(value)
+}
+
+/// An operation without a name must be the only operation in the document,
+/// otherwise there would be no way to tell which one to execute.
+/// https://spec.graphql.org/October2021/#sec-Lone-Anonymous-Operation
+fn check_lone_anonymous_operation(definitions: &[Definition]) -> Result<(), Diagnostic> {
+    let operations: Vec<&OperationDefinition> = definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::OperationDefinition(operation) => Some(operation),
+            _ => None,
+        })
+        .collect();
+
+    if operations.len() < 2 {
+        return Ok(());
+    }
+
+    match operations.iter().find(|operation| operation.name.is_none()) {
+        Some(operation) => Err(Diagnostic::new(
+            DiagnosticSeverity::Error,
+            String::from(
+                "This anonymous operation must be the only defined operation in the document.",
+            ),
+            operation.position.clone(),
+        )),
+        None => Ok(()),
+    }
+}
+
+type TokenStream = Box<dyn Iterator<Item = Result<LexicalToken, Diagnostic>>>;
+
+pub struct Parser {
+    tokens: TokenStream,
+    /// The token under the cursor. It is pulled from `tokens` one step ahead
+    /// of time so it can be peeked without needing a mutable borrow.
+    current: Option<Result<LexicalToken, Diagnostic>>,
+    options: ParseOptions,
+    /// Errors that were recovered from while parsing, and warnings about
+    /// source that parses but is likely a mistake, such as an Int value out
+    /// of range.
+    diagnostics: Vec<Diagnostic>,
+    /// Where the last token consumed ends, which is where a node that was
+    /// just parsed ends.
+    previous_end: Position,
 }
 
 impl Parser {
     pub fn new(tokens: Vec<LexicalToken>) -> Parser {
-        Parser { ptr: 0, tokens }
+        Parser::from_stream(Box::new(tokens.into_iter().map(Ok)))
+    }
+
+    /// Creates a parser that pulls tokens from the lexer as it needs them
+    /// rather than lexing the entire source up front.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter(iter: LexerIter) -> Parser {
+        Parser::from_stream(Box::new(iter))
+    }
+
+    fn from_stream(tokens: TokenStream) -> Parser {
+        let mut parser = Parser {
+            tokens,
+            current: None,
+            options: ParseOptions::default(),
+            diagnostics: Vec::new(),
+            previous_end: Position::ZERO,
+        };
+
+        parser.next();
+        parser
     }
 
     pub fn parse(&mut self) -> Result<Document, Diagnostic> {
@@ -56,97 +222,156 @@ impl Parser {
         let mut definitions: Vec<Definition> = Vec::new();
 
         loop {
-            let token = self.peek_safe();
+            let token = self.peek()?.clone();
 
             if token.token_type == LexicalTokenType::EOF {
-                return Ok(definitions);
-            }
+                if let Err(diagnostic) = check_lone_anonymous_operation(&definitions) {
+                    if !self.options.recover_errors {
+                        return Err(diagnostic);
+                    }
 
-            let position = self.get_current_position();
+                    self.diagnostics.push(diagnostic);
+                }
 
-            if token.token_type == LexicalTokenType::Punctuator(Punctuator::LeftBrace) {
-                definitions.push(Definition::OperationDefinition(
-                    self.parse_operation_definition(OperationType::Query, true)?,
-                ));
-                continue;
+                return Ok(definitions);
             }
 
-            if let LexicalTokenType::Name(name) = &token.token_type {
-                if let Some(operation_type) = OperationType::parse(name) {
-                    definitions.push(Definition::OperationDefinition(
-                        self.parse_operation_definition(operation_type, false)?,
-                    ));
-                    continue;
+            match self.parse_definition() {
+                Ok(definition) => definitions.push(definition),
+                Err(diagnostic) if self.options.recover_errors => {
+                    self.diagnostics.push(diagnostic);
+                    self.synchronize(token.position.start.offset);
                 }
+                Err(diagnostic) => return Err(diagnostic),
             }
+        }
+    }
 
-            if token.token_type == LexicalTokenType::Name(String::from("fragment")) {
-                definitions.push(Definition::FragmentDefinition(
-                    self.parse_fragment_definition()?,
-                ));
-                continue;
-            }
+    fn parse_definition(&mut self) -> Result<Definition, Diagnostic> {
+        let token = self.peek()?.clone();
+        let position = self.get_current_position();
 
-            // see if type definition has a description
-            let description = self.parse_description();
-            // need to reset the token since description parsing may have consumed it
-            let token = self.peek()?;
+        if token.token_type == LexicalTokenType::Punctuator(Punctuator::LeftBrace) {
+            return Ok(Definition::OperationDefinition(
+                self.parse_operation_definition(OperationType::Query, true)?,
+            ));
+        }
 
-            if token.token_type == LexicalTokenType::Name(String::from("schema")) {
-                definitions.push(Definition::SchemaDefinition(
-                    self.parse_schema_definition(description)?,
+        if let LexicalTokenType::Name(name) = &token.token_type {
+            if let Some(operation_type) = OperationType::parse(name) {
+                return Ok(Definition::OperationDefinition(
+                    self.parse_operation_definition(operation_type, false)?,
                 ));
-                continue;
             }
+        }
 
-            if token.token_type == LexicalTokenType::Name(String::from("scalar")) {
-                definitions.push(Definition::ScalarTypeDefinition(
-                    self.parse_scalar_type_definition(description)?,
-                ));
-                continue;
-            }
+        if token.token_type == LexicalTokenType::Name(String::from("fragment")) {
+            return Ok(Definition::FragmentDefinition(
+                self.parse_fragment_definition()?,
+            ));
+        }
 
-            if token.token_type == LexicalTokenType::Name(String::from("type")) {
-                definitions.push(Definition::ObjectTypeDefinition(
-                    self.parse_object_type_definition(description)?,
-                ));
-                continue;
-            }
+        if token.token_type == LexicalTokenType::Name(String::from("extend")) {
+            return self.parse_type_system_extension();
+        }
 
-            if token.token_type == LexicalTokenType::Name(String::from("interface")) {
-                definitions.push(Definition::InterfaceTypeDefinition(
-                    self.parse_interface_type_definition(description)?,
-                ));
-                continue;
-            }
+        // see if type definition has a description
+        let description = self.parse_description();
+        // need to reset the token since description parsing may have consumed it
+        let token = self.peek()?;
 
-            if token.token_type == LexicalTokenType::Name(String::from("union")) {
-                definitions.push(Definition::UnionTypeDefinition(
-                    self.parse_union_type_definition(description)?,
-                ));
-                continue;
-            }
+        if token.token_type == LexicalTokenType::Name(String::from("schema")) {
+            return Ok(Definition::SchemaDefinition(
+                self.parse_schema_definition(description)?,
+            ));
+        }
 
-            if token.token_type == LexicalTokenType::Name(String::from("enum")) {
-                definitions.push(Definition::EnumTypeDefinition(
-                    self.parse_enum_type_definition(description)?,
-                ));
-                continue;
-            }
+        if token.token_type == LexicalTokenType::Name(String::from("scalar")) {
+            return Ok(Definition::ScalarTypeDefinition(
+                self.parse_scalar_type_definition(description)?,
+            ));
+        }
 
-            if token.token_type == LexicalTokenType::Name(String::from("input")) {
-                definitions.push(Definition::InputObjectTypeDefinition(
-                    self.parse_input_object_type_definition(description)?,
-                ));
-                continue;
-            }
+        if token.token_type == LexicalTokenType::Name(String::from("type")) {
+            return Ok(Definition::ObjectTypeDefinition(
+                self.parse_object_type_definition(description)?,
+            ));
+        }
 
-            return Err(Diagnostic::new(
-                DiagnosticSeverity::Error,
-                String::from("Expected operation definition"),
-                position,
+        if token.token_type == LexicalTokenType::Name(String::from("interface")) {
+            return Ok(Definition::InterfaceTypeDefinition(
+                self.parse_interface_type_definition(description)?,
+            ));
+        }
+
+        if token.token_type == LexicalTokenType::Name(String::from("union")) {
+            return Ok(Definition::UnionTypeDefinition(
+                self.parse_union_type_definition(description)?,
+            ));
+        }
+
+        if token.token_type == LexicalTokenType::Name(String::from("enum")) {
+            return Ok(Definition::EnumTypeDefinition(
+                self.parse_enum_type_definition(description)?,
+            ));
+        }
+
+        if token.token_type == LexicalTokenType::Name(String::from("input")) {
+            return Ok(Definition::InputObjectTypeDefinition(
+                self.parse_input_object_type_definition(description)?,
+            ));
+        }
+
+        if token.token_type == LexicalTokenType::Name(String::from("directive")) {
+            return Ok(Definition::DirectiveDefinition(
+                self.parse_directive_definition(description)?,
             ));
         }
+
+        Err(Diagnostic::new(
+            DiagnosticSeverity::Error,
+            String::from("Expected operation definition"),
+            position,
+        ))
+    }
+
+    /// Skips tokens until one that can start a new definition, so parsing can
+    /// resume after an error. The definition that failed always gives up at
+    /// least its first token, otherwise the parser would never make progress.
+    fn synchronize(&mut self, definition_offset: usize) {
+        loop {
+            let token = match self.peek() {
+                Ok(token) => token,
+                Err(_) => return,
+            };
+
+            let starts_definition = match &token.token_type {
+                LexicalTokenType::EOF => return,
+                LexicalTokenType::Name(name) => matches!(
+                    name.as_str(),
+                    "query"
+                        | "mutation"
+                        | "subscription"
+                        | "fragment"
+                        | "schema"
+                        | "scalar"
+                        | "type"
+                        | "interface"
+                        | "union"
+                        | "enum"
+                        | "input"
+                        | "directive"
+                        | "extend"
+                ),
+                _ => false,
+            };
+
+            if starts_definition && token.position.start.offset != definition_offset {
+                return;
+            }
+
+            self.next();
+        }
     }
 
     fn parse_description(&mut self) -> Option<StringValue> {
@@ -155,11 +380,19 @@ impl Parser {
         match &token.token_type {
             LexicalTokenType::StringValue(value) => {
                 self.next();
-                return Some(StringValue {
+                Some(StringValue {
                     value: value.clone(),
                     block: false,
                     position: token.position.clone(),
-                });
+                })
+            }
+            LexicalTokenType::BlockStringValue(value) => {
+                self.next();
+                Some(StringValue {
+                    value: value.clone(),
+                    block: true,
+                    position: token.position.clone(),
+                })
             }
             _ => None,
         }
@@ -172,14 +405,14 @@ impl Parser {
         let start_position = self.get_current_position();
 
         self.expect_next(LexicalTokenType::Name(String::from("scalar")))?;
-        let name = self.parse_name()?;
+        let name = self.parse_definition_name()?;
         let directives = self.parse_directives()?;
 
         Ok(ScalarTypeDefinition {
             name,
             description,
             directives,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -191,6 +424,19 @@ impl Parser {
 
         self.expect_next(LexicalTokenType::Name(String::from("schema")))?;
         let directives = self.parse_directives()?;
+        let operation_types = self.parse_root_operation_types()?;
+
+        Ok(SchemaDefinition {
+            description,
+            operation_types,
+            directives,
+            position: Range::new(start_position.start, self.previous_end.clone()),
+        })
+    }
+
+    fn parse_root_operation_types(
+        &mut self,
+    ) -> Result<Vec<RootOperationTypeDefinition>, Diagnostic> {
         self.expect_next(LexicalTokenType::Punctuator(Punctuator::LeftBrace))?;
 
         let mut operation_types: Vec<RootOperationTypeDefinition> = Vec::new();
@@ -223,16 +469,11 @@ impl Parser {
             operation_types.push(RootOperationTypeDefinition {
                 operation_type,
                 named_type,
-                position: Range::new(start_position.start, self.get_current_position().end),
+                position: Range::new(start_position.start, self.previous_end.clone()),
             });
         }
 
-        Ok(SchemaDefinition {
-            description,
-            operation_types,
-            directives,
-            position: Range::new(start_position.start, self.get_current_position().end),
-        })
+        Ok(operation_types)
     }
 
     fn parse_type_condition(&mut self) -> Result<NamedType, Diagnostic> {
@@ -243,7 +484,7 @@ impl Parser {
 
         Ok(NamedType {
             name,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -252,6 +493,16 @@ impl Parser {
 
         self.expect_next(LexicalTokenType::Name(String::from("fragment")))?;
         let name = self.parse_name()?;
+
+        // https://spec.graphql.org/October2021/#FragmentName
+        if name.value == "on" {
+            return Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                String::from("Fragment name 'on' is not allowed"),
+                name.position,
+            ));
+        }
+
         let type_condition = self.parse_type_condition()?;
         let directives = self.parse_directives()?;
         let selection_set = self.parse_selection_set()?;
@@ -261,10 +512,17 @@ impl Parser {
             type_condition,
             directives,
             selection_set,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
+    /// Parses an operation, starting from the token `parse_definition` peeked
+    /// to decide this is an operation. For a named operation that token is
+    /// the operation type keyword (`query`, `mutation` or `subscription`),
+    /// which has not been consumed yet. For the anonymous shorthand it is the
+    /// `{` that opens the selection set, which is left for
+    /// `parse_selection_set` to consume.
+    /// https://spec.graphql.org/October2021/#OperationDefinition
     fn parse_operation_definition(
         &mut self,
         operation_type: OperationType,
@@ -272,8 +530,8 @@ impl Parser {
     ) -> Result<OperationDefinition, Diagnostic> {
         let start_position = self.get_current_position();
 
-        // lol kinda don't know why this is needed shrug
         if !anonymous {
+            // skip over the operation type keyword
             self.next();
         }
 
@@ -289,7 +547,7 @@ impl Parser {
             directives,
             selection_set,
             anonymous,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -313,7 +571,7 @@ impl Parser {
             directives.push(Directive {
                 name,
                 arguments,
-                position: Range::new(start_position.start, self.get_current_position().end),
+                position: Range::new(start_position.start, self.previous_end.clone()),
             });
         }
     }
@@ -352,7 +610,7 @@ impl Parser {
         Ok(Argument {
             name,
             value,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -372,7 +630,7 @@ impl Parser {
 
                 return Ok(SelectionSet {
                     selections,
-                    position: Range::new(position.start, self.get_current_position().end),
+                    position: Range::new(position.start, self.previous_end.clone()),
                 });
             }
 
@@ -380,22 +638,18 @@ impl Parser {
         }
     }
 
-    fn parse_fragment_spread(&mut self) -> Result<FragmentSpread, Diagnostic> {
-        let position = self.get_current_position().clone();
-
+    fn parse_fragment_spread(&mut self, position: Range) -> Result<FragmentSpread, Diagnostic> {
         let name = self.parse_name()?;
         let directives = self.parse_directives()?;
 
         Ok(FragmentSpread {
             name,
             directives,
-            position: Range::new(position.start, self.get_current_position().end),
+            position: Range::new(position.start, self.previous_end.clone()),
         })
     }
 
-    fn parse_inline_fragment(&mut self) -> Result<InlineFragment, Diagnostic> {
-        let position = self.get_current_position().clone();
-
+    fn parse_inline_fragment(&mut self, position: Range) -> Result<InlineFragment, Diagnostic> {
         let mut type_condition: Option<NamedType> = None;
 
         let token = self.peek()?;
@@ -410,7 +664,7 @@ impl Parser {
             type_condition,
             directives,
             selection_set,
-            position: Range::new(position.start, self.get_current_position().end),
+            position: Range::new(position.start, self.previous_end.clone()),
         })
     }
 
@@ -421,35 +675,39 @@ impl Parser {
 
         match &token.token_type {
             LexicalTokenType::Punctuator(Punctuator::Ellipsis) => {
-                // TODO: start position needs to account for the `...`
+                // the fragment's range starts at the `...`, so its position is
+                // passed along
                 self.next();
 
                 let token = self.peek()?;
                 match &token.token_type {
-                    LexicalTokenType::Name(name) if name == "on" => {
-                        return Ok(Selection::InlineFragment(self.parse_inline_fragment()?));
-                    }
-                    LexicalTokenType::Name(_) => {
-                        return Ok(Selection::FragmentSpread(self.parse_fragment_spread()?));
-                    }
-                    _ => {
-                        return Err(Diagnostic::new(
-                            DiagnosticSeverity::Error,
-                            String::from("Expected Fragment Spread or Inline Fragment"),
-                            self.get_current_position(),
-                        ));
-                    }
+                    LexicalTokenType::Name(name) if name == "on" => Ok(Selection::InlineFragment(
+                        self.parse_inline_fragment(position)?,
+                    )),
+                    // inline fragments may omit the type condition
+                    LexicalTokenType::Punctuator(Punctuator::AtSign)
+                    | LexicalTokenType::Punctuator(Punctuator::LeftBrace) => Ok(
+                        Selection::InlineFragment(self.parse_inline_fragment(position)?),
+                    ),
+                    LexicalTokenType::Name(_) => Ok(Selection::FragmentSpread(
+                        self.parse_fragment_spread(position)?,
+                    )),
+                    _ => Err(Diagnostic::new(
+                        DiagnosticSeverity::Error,
+                        String::from("Expected Fragment Spread or Inline Fragment"),
+                        self.get_current_position(),
+                    )),
                 }
             }
             LexicalTokenType::Name(_) => {
-                let mut name = self.parse_name_maybe()?;
+                let mut name = self.parse_name()?;
                 let mut alias: Option<Name> = None;
 
                 let token = self.peek()?;
                 if token.token_type == LexicalTokenType::Punctuator(Punctuator::Colon) {
                     self.next();
-                    alias = name;
-                    name = self.parse_name_maybe()?;
+                    alias = Some(name);
+                    name = self.parse_name()?;
                 }
 
                 let arguments = self.parse_arguments()?;
@@ -462,22 +720,20 @@ impl Parser {
                     selection_set = Some(self.parse_selection_set()?);
                 }
 
-                return Ok(Selection::Field(Field {
+                Ok(Selection::Field(Field {
                     alias,
-                    name: name.unwrap(),
+                    name,
                     selection_set,
                     arguments,
                     directives,
-                    position: Range::new(position.start, self.get_current_position().end),
-                }));
-            }
-            _ => {
-                return Err(Diagnostic::new(
-                    DiagnosticSeverity::Error,
-                    String::from("Expected Selection"),
-                    self.get_current_position(),
-                ))
+                    position: Range::new(position.start, self.previous_end.clone()),
+                }))
             }
+            _ => Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                String::from("Expected Selection"),
+                self.get_current_position(),
+            )),
         }
     }
 
@@ -494,12 +750,28 @@ impl Parser {
         }
     }
 
+    /// Parses the name of a type system definition, which may not use the
+    /// `__` prefix reserved for introspection.
+    fn parse_definition_name(&mut self) -> Result<Name, Diagnostic> {
+        let name = self.parse_name()?;
+
+        if is_reserved_name(&name.value) {
+            return Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                String::from("Names starting with \"__\" are reserved"),
+                name.position,
+            ));
+        }
+
+        Ok(name)
+    }
+
     fn parse_name_maybe(&mut self) -> Result<Option<Name>, Diagnostic> {
         let position = self.get_current_position();
         let token = self.peek()?.clone();
 
         if let LexicalTokenType::Name(name) = &token.token_type {
-            if is_valid_name(&name) {
+            if is_valid_name(name) {
                 self.next();
 
                 return Ok(Some(Name {
@@ -557,6 +829,7 @@ impl Parser {
         self.next();
 
         let name = self.parse_name()?;
+        let variable_position = Range::new(position.start.clone(), self.previous_end.clone());
 
         let token = self.peek()?;
         if token.token_type != LexicalTokenType::Punctuator(Punctuator::Colon) {
@@ -575,18 +848,21 @@ impl Parser {
         let token = self.peek()?;
         if token.token_type == LexicalTokenType::Punctuator(Punctuator::EqualSign) {
             self.next();
-            default_value = Some(self.parse_value()?);
+            default_value = Some(self.parse_const_value()?);
         }
 
-        return Ok(VariableDefinition {
+        let directives = self.parse_directives()?;
+
+        Ok(VariableDefinition {
             variable: Variable {
                 name,
-                position: Range::new(position.start.clone(), self.get_current_position().end),
+                position: variable_position,
             },
             variable_type,
             default_value,
-            position: Range::new(position.start, self.get_current_position().end),
-        });
+            directives,
+            position: Range::new(position.start, self.previous_end.clone()),
+        })
     }
 
     fn parse_type(&mut self) -> Result<Type, Diagnostic> {
@@ -595,15 +871,15 @@ impl Parser {
 
         if token.token_type == LexicalTokenType::Punctuator(Punctuator::LeftBracket) {
             let list_type = self.parse_list_type()?;
-            return Ok(self.wrap_if_non_null(list_type)?);
+            return self.wrap_if_non_null(list_type);
         }
 
         let name_type = self.parse_name()?;
 
-        return Ok(self.wrap_if_non_null(Type::NamedType(NamedType {
+        self.wrap_if_non_null(Type::NamedType(NamedType {
             name: name_type,
-            position: Range::new(start_position.start, self.get_current_position().end),
-        }))?);
+            position: Range::new(start_position.start, self.previous_end.clone()),
+        }))
     }
 
     fn parse_named_type(&mut self) -> Result<NamedType, Diagnostic> {
@@ -612,7 +888,7 @@ impl Parser {
 
         Ok(NamedType {
             name,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -626,11 +902,9 @@ impl Parser {
 
         self.next();
 
-        let end_position = self.get_current_position().clone();
-
         Ok(Type::NonNullType(NonNullType {
             wrapped_type: Box::new(wrapped_type),
-            position: Range::new(start_position.start, end_position.end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         }))
     }
 
@@ -651,85 +925,122 @@ impl Parser {
 
         self.next();
 
-        let end_position = self.get_current_position().clone();
-
         Ok(Type::ListType(ListType {
             wrapped_type: Box::new(wrapped_type),
-            position: Range::new(start_position.start, end_position.end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         }))
     }
 
-    // TODO: when to allow variables?
     fn parse_value(&mut self) -> Result<Value, Diagnostic> {
+        self.parse_value_with_const(false)
+    }
+
+    /// Parses a value that must be known at parse time, such as a default
+    /// value, where variables may not be referenced.
+    /// https://spec.graphql.org/October2021/#Value
+    fn parse_const_value(&mut self) -> Result<Value, Diagnostic> {
+        self.parse_value_with_const(true)
+    }
+
+    fn parse_value_with_const(&mut self, is_const: bool) -> Result<Value, Diagnostic> {
         let token = self.peek()?;
         let position = token.position.clone();
 
         match &token.token_type {
             LexicalTokenType::IntValue(value) => {
-                let value = value.clone();
+                let value = *value;
                 self.next();
-                return Ok(Value::IntValue(IntValue { value, position }));
+
+                // https://spec.graphql.org/October2021/#sec-Int
+                if i32::try_from(value).is_err() {
+                    self.diagnostics.push(Diagnostic::new(
+                        DiagnosticSeverity::Warning,
+                        format!(
+                            "Int value {} cannot be represented as a 32-bit integer",
+                            value
+                        ),
+                        position.clone(),
+                    ));
+                }
+
+                Ok(Value::IntValue(IntValue { value, position }))
             }
             LexicalTokenType::FloatValue(value) => {
-                let value = value.clone();
+                let value = *value;
                 self.next();
-                return Ok(Value::FloatValue(FloatValue { value, position }));
+                Ok(Value::FloatValue(FloatValue { value, position }))
             }
             LexicalTokenType::StringValue(value) => {
                 let value = value.clone();
                 self.next();
-                return Ok(Value::StringValue(StringValue {
+                Ok(Value::StringValue(StringValue {
                     value,
                     block: false,
                     position,
-                }));
+                }))
+            }
+            LexicalTokenType::BlockStringValue(value) => {
+                let value = value.clone();
+                self.next();
+                Ok(Value::StringValue(StringValue {
+                    value,
+                    block: true,
+                    position,
+                }))
             }
             LexicalTokenType::Name(name) if name == "true" => {
                 self.next();
-                return Ok(Value::BooleanValue(BooleanValue {
+                Ok(Value::BooleanValue(BooleanValue {
                     value: true,
                     position,
-                }));
+                }))
             }
             LexicalTokenType::Name(name) if name == "false" => {
                 self.next();
-                return Ok(Value::BooleanValue(BooleanValue {
+                Ok(Value::BooleanValue(BooleanValue {
                     value: false,
                     position,
-                }));
+                }))
             }
             LexicalTokenType::Name(name) if name == "null" => {
                 self.next();
-                return Ok(Value::NullValue(NullValue { position }));
+                Ok(Value::NullValue(NullValue { position }))
             }
             LexicalTokenType::Punctuator(Punctuator::LeftBracket) => {
-                return Ok(self.parse_list_value()?);
+                self.parse_list_value(is_const)
             }
             LexicalTokenType::Punctuator(Punctuator::LeftBrace) => {
-                return Ok(self.parse_object_value()?);
+                self.parse_object_value(is_const)
+            }
+            LexicalTokenType::Punctuator(Punctuator::DollarSign) if is_const => {
+                Err(Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    String::from("Variables are not allowed in constant value context"),
+                    position,
+                ))
             }
             LexicalTokenType::Punctuator(Punctuator::DollarSign) => {
                 self.next();
                 let name = self.parse_name()?;
-                return Ok(Value::Variable(Variable { name, position }));
+                Ok(Value::Variable(Variable {
+                    name,
+                    position: Range::new(position.start, self.previous_end.clone()),
+                }))
             }
             LexicalTokenType::Name(name) => {
-                return Ok(Value::EnumValue(EnumValue {
-                    value: name.to_string(),
-                    position,
-                }));
-            }
-            _ => {
-                return Err(Diagnostic::new(
-                    DiagnosticSeverity::Error,
-                    String::from("Expected Value"),
-                    position,
-                ));
+                let value = name.to_string();
+                self.next();
+                Ok(Value::EnumValue(EnumValue { value, position }))
             }
+            _ => Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                String::from("Expected Value"),
+                position,
+            )),
         }
     }
 
-    fn parse_list_value(&mut self) -> Result<Value, Diagnostic> {
+    fn parse_list_value(&mut self, is_const: bool) -> Result<Value, Diagnostic> {
         let start_position = self.get_current_position().clone();
 
         self.next();
@@ -744,19 +1055,32 @@ impl Parser {
                 break;
             }
 
-            let value = self.parse_value()?;
+            // anything that can't start a value means the list was never closed
+            match &token.token_type {
+                LexicalTokenType::Punctuator(Punctuator::LeftBracket)
+                | LexicalTokenType::Punctuator(Punctuator::LeftBrace)
+                | LexicalTokenType::Punctuator(Punctuator::DollarSign) => {}
+                LexicalTokenType::Punctuator(_) | LexicalTokenType::EOF => {
+                    return Err(Diagnostic::new(
+                        DiagnosticSeverity::Error,
+                        String::from("Unclosed list value, expected \"]\""),
+                        token.position.clone(),
+                    ));
+                }
+                _ => {}
+            }
+
+            let value = self.parse_value_with_const(is_const)?;
             values.push(value);
         }
 
-        let end_position = self.get_current_position().clone();
-
         Ok(Value::ListValue(ListValue {
             values,
-            position: Range::new(start_position.start, end_position.end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         }))
     }
 
-    fn parse_object_value(&mut self) -> Result<Value, Diagnostic> {
+    fn parse_object_value(&mut self, is_const: bool) -> Result<Value, Diagnostic> {
         let start_position = self.get_current_position().clone();
 
         self.next();
@@ -764,51 +1088,61 @@ impl Parser {
         let mut object_fields: Vec<ObjectField> = Vec::new();
 
         while self.peek()?.token_type != LexicalTokenType::Punctuator(Punctuator::RightBrace) {
-            let object_field = self.parse_object_field()?;
+            let token = self.peek()?;
+
+            // object fields always start with a name, anything else means the
+            // object was never closed
+            if !matches!(token.token_type, LexicalTokenType::Name(_)) {
+                return Err(Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    String::from("Unclosed object value, expected \"}\""),
+                    token.position.clone(),
+                ));
+            }
+
+            let object_field = self.parse_object_field(is_const)?;
             object_fields.push(object_field);
         }
 
-        let end_position = self.get_current_position().clone();
+        // skip over the `}`
+        self.next();
 
         Ok(Value::ObjectValue(ObjectValue {
             fields: object_fields,
-            position: Range::new(start_position.start, end_position.end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         }))
     }
 
-    fn parse_object_field(&mut self) -> Result<ObjectField, Diagnostic> {
+    fn parse_object_field(&mut self, is_const: bool) -> Result<ObjectField, Diagnostic> {
         let start_position = self.get_current_position().clone();
 
         let name = self.parse_name()?;
         self.expect_next(LexicalTokenType::Punctuator(Punctuator::Colon))?;
-        let value = self.parse_value()?;
+        let value = self.parse_value_with_const(is_const)?;
 
         Ok(ObjectField {
             name,
             value,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
     fn peek(&self) -> Result<&LexicalToken, Diagnostic> {
-        let token = self.tokens.get(self.ptr);
-
-        match token {
-            Some(token) => Ok(token),
+        match &self.current {
+            Some(Ok(token)) => Ok(token),
+            Some(Err(diagnostic)) => Err(diagnostic.clone()),
             None => Err(Diagnostic::new(
                 DiagnosticSeverity::Error,
                 String::from("Unexpected EOF"),
-                self.get_current_position(),
+                Range::ZERO,
             )),
         }
     }
 
     fn peek_safe(&self) -> LexicalToken {
-        let token = self.tokens.get(self.ptr);
-
-        match token {
-            Some(token) => token.clone(),
-            None => LexicalToken {
+        match &self.current {
+            Some(Ok(token)) => token.clone(),
+            _ => LexicalToken {
                 token_type: LexicalTokenType::EOF,
                 position: self.get_current_position().clone(),
             },
@@ -816,7 +1150,14 @@ impl Parser {
     }
 
     fn next(&mut self) {
-        self.ptr += 1;
+        if let Some(Ok(token)) = &self.current {
+            self.previous_end = token.position.end.clone();
+        }
+
+        // comments are trivia, the parser never needs to see them
+        self.current = self.tokens.find(|token| {
+            !matches!(token, Ok(token) if matches!(token.token_type, LexicalTokenType::Comment(_)))
+        });
     }
 
     fn expect_next(&mut self, token_type: LexicalTokenType) -> Result<bool, Diagnostic> {
@@ -829,10 +1170,10 @@ impl Parser {
 
         Err(Diagnostic::new(
             DiagnosticSeverity::Error,
-            String::from(format!(
+            format!(
                 "Unexpected token. Expected {:?}, found {:?}",
                 token_type, token
-            )),
+            ),
             self.get_current_position(),
         ))
     }
@@ -842,7 +1183,7 @@ impl Parser {
 
         match token {
             Ok(token) => token.position.clone(),
-            Err(_) => Range::new(Position::new(0, 0), Position::new(0, 0)),
+            Err(_) => Range::ZERO,
         }
     }
 
@@ -853,7 +1194,7 @@ impl Parser {
         let start_position = self.get_current_position().clone();
 
         self.expect_next(LexicalTokenType::Name(String::from("type")))?;
-        let name = self.parse_name()?;
+        let name = self.parse_definition_name()?;
         let interfaces = self.parse_interfaces()?;
         let directives = self.parse_directives()?;
         let fields = self.parse_fields()?;
@@ -864,7 +1205,7 @@ impl Parser {
             interfaces,
             directives,
             fields,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -877,6 +1218,11 @@ impl Parser {
 
         self.next();
 
+        // https://spec.graphql.org/October2021/#ImplementsInterfaces
+        if self.peek_safe().token_type == LexicalTokenType::Punctuator(Punctuator::Ampersand) {
+            self.next();
+        }
+
         loop {
             let named_type = self.parse_named_type()?;
 
@@ -918,7 +1264,7 @@ impl Parser {
         let start_position = self.get_current_position().clone();
 
         let description = self.parse_description();
-        let name = self.parse_name()?;
+        let name = self.parse_definition_name()?;
         let arguments = self.parse_field_arguments()?;
         self.expect_next(LexicalTokenType::Punctuator(Punctuator::Colon))?;
         let field_type = self.parse_type()?;
@@ -930,7 +1276,7 @@ impl Parser {
             arguments,
             field_type,
             directives,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -963,7 +1309,7 @@ impl Parser {
         let start_position = self.get_current_position().clone();
 
         let description = self.parse_description();
-        let name = self.parse_name()?;
+        let name = self.parse_definition_name()?;
         self.expect_next(LexicalTokenType::Punctuator(Punctuator::Colon))?;
         let input_type = self.parse_type()?;
         let default_value = self.parse_default_value()?;
@@ -975,7 +1321,7 @@ impl Parser {
             input_type,
             default_value,
             directives,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -988,7 +1334,7 @@ impl Parser {
 
         self.next();
 
-        Ok(Some(self.parse_value()?))
+        Ok(Some(self.parse_const_value()?))
     }
 
     fn parse_interface_type_definition(
@@ -998,7 +1344,7 @@ impl Parser {
         let start_position = self.get_current_position().clone();
 
         self.expect_next(LexicalTokenType::Name(String::from("interface")))?;
-        let name = self.parse_name()?;
+        let name = self.parse_definition_name()?;
         let interfaces = self.parse_interfaces()?;
         let directives = self.parse_directives()?;
         let fields = self.parse_fields()?;
@@ -1009,7 +1355,7 @@ impl Parser {
             interfaces,
             directives,
             fields,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -1020,7 +1366,7 @@ impl Parser {
         let start_position = self.get_current_position().clone();
 
         self.expect_next(LexicalTokenType::Name(String::from("union")))?;
-        let name = self.parse_name()?;
+        let name = self.parse_definition_name()?;
         let directives = self.parse_directives()?;
         let member_types = self.parse_union_member_types()?;
 
@@ -1029,7 +1375,7 @@ impl Parser {
             description,
             directives,
             member_types,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -1038,6 +1384,11 @@ impl Parser {
 
         self.expect_next(LexicalTokenType::Punctuator(Punctuator::EqualSign))?;
 
+        // https://spec.graphql.org/October2021/#UnionMemberTypes
+        if self.peek_safe().token_type == LexicalTokenType::Punctuator(Punctuator::VerticalBar) {
+            self.next();
+        }
+
         member_types.push(self.parse_named_type()?);
 
         while let LexicalTokenType::Punctuator(Punctuator::VerticalBar) =
@@ -1057,7 +1408,7 @@ impl Parser {
         let start_position = self.get_current_position().clone();
 
         self.expect_next(LexicalTokenType::Name(String::from("enum")))?;
-        let name = self.parse_name()?;
+        let name = self.parse_definition_name()?;
         let directives = self.parse_directives()?;
         let values = self.parse_enum_values()?;
 
@@ -1066,14 +1417,25 @@ impl Parser {
             description,
             directives,
             values,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
     fn parse_enum_values(&mut self) -> Result<Vec<EnumValueDefinition>, Diagnostic> {
         let mut values = Vec::new();
 
+        let start_position = self.get_current_position();
+
         self.expect_next(LexicalTokenType::Punctuator(Punctuator::LeftBrace))?;
+
+        if self.peek_safe().token_type == LexicalTokenType::Punctuator(Punctuator::RightBrace) {
+            return Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                String::from("Enum type must define one or more unique enum values."),
+                start_position,
+            ));
+        }
+
         values.push(self.parse_enum_value_definition()?);
 
         while self.peek_safe().token_type != LexicalTokenType::Punctuator(Punctuator::RightBrace) {
@@ -1089,14 +1451,26 @@ impl Parser {
         let start_position = self.get_current_position().clone();
 
         let description = self.parse_description();
-        let name = self.parse_name()?;
+        let name = self.parse_definition_name()?;
+
+        // https://spec.graphql.org/October2021/#EnumValue
+        if name.value == "true" || name.value == "false" || name.value == "null" {
+            return Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                String::from(
+                    "'true', 'false', and 'null' are reserved and cannot be used as enum values",
+                ),
+                name.position,
+            ));
+        }
+
         let directives = self.parse_directives()?;
 
         Ok(EnumValueDefinition {
             description,
             name,
             directives,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -1107,7 +1481,7 @@ impl Parser {
         let start_position = self.get_current_position().clone();
 
         self.expect_next(LexicalTokenType::Name(String::from("input")))?;
-        let name = self.parse_name()?;
+        let name = self.parse_definition_name()?;
         let directives = self.parse_directives()?;
         let fields = self.parse_input_fields()?;
 
@@ -1116,7 +1490,7 @@ impl Parser {
             description,
             directives,
             fields,
-            position: Range::new(start_position.start, self.get_current_position().end),
+            position: Range::new(start_position.start, self.previous_end.clone()),
         })
     }
 
@@ -1133,4 +1507,318 @@ impl Parser {
 
         Ok(fields)
     }
+
+    /// https://spec.graphql.org/October2021/#sec-Type-System.Directives
+    fn parse_directive_definition(
+        &mut self,
+        description: Option<StringValue>,
+    ) -> Result<DirectiveDefinition, Diagnostic> {
+        let start_position = self.get_current_position().clone();
+
+        self.expect_next(LexicalTokenType::Name(String::from("directive")))?;
+        self.expect_next(LexicalTokenType::Punctuator(Punctuator::AtSign))?;
+        let name = self.parse_definition_name()?;
+        let arguments = self.parse_field_arguments()?;
+
+        let mut repeatable = false;
+        if self.peek_safe().token_type == LexicalTokenType::Name(String::from("repeatable")) {
+            self.next();
+            repeatable = true;
+        }
+
+        let locations = self.parse_directive_locations()?;
+
+        Ok(DirectiveDefinition {
+            description,
+            name,
+            arguments,
+            repeatable,
+            locations,
+            position: Range::new(start_position.start, self.previous_end.clone()),
+        })
+    }
+
+    /// https://spec.graphql.org/October2021/#DirectiveLocations
+    fn parse_directive_locations(&mut self) -> Result<Vec<DirectiveLocation>, Diagnostic> {
+        let mut locations = Vec::new();
+
+        self.expect_next(LexicalTokenType::Name(String::from("on")))?;
+
+        // a leading `|` is allowed so long location lists can be split over
+        // several lines
+        if self.peek_safe().token_type == LexicalTokenType::Punctuator(Punctuator::VerticalBar) {
+            self.next();
+        }
+
+        locations.push(self.parse_directive_location()?);
+
+        while let LexicalTokenType::Punctuator(Punctuator::VerticalBar) =
+            self.peek_safe().token_type
+        {
+            self.next();
+            locations.push(self.parse_directive_location()?);
+        }
+
+        Ok(locations)
+    }
+
+    fn parse_directive_location(&mut self) -> Result<DirectiveLocation, Diagnostic> {
+        let name = self.parse_name()?;
+
+        if name.value == "repeatable" {
+            return Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                String::from("\"repeatable\" must appear before \"on\" in a directive definition"),
+                name.position,
+            ));
+        }
+
+        match DirectiveLocation::parse(&name.value) {
+            Some(location) => Ok(location),
+            None => Err(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                format!("Unknown directive location: {}", name.value),
+                name.position,
+            )),
+        }
+    }
+
+    /// https://spec.graphql.org/October2021/#TypeSystemExtension
+    fn parse_type_system_extension(&mut self) -> Result<Definition, Diagnostic> {
+        let start_position = self.get_current_position().clone();
+
+        self.expect_next(LexicalTokenType::Name(String::from("extend")))?;
+
+        let token = self.peek()?.clone();
+
+        let definition = match &token.token_type {
+            LexicalTokenType::Name(name) if name == "schema" => {
+                Definition::SchemaExtension(self.parse_schema_extension(start_position)?)
+            }
+            LexicalTokenType::Name(name) if name == "scalar" => {
+                Definition::ScalarTypeExtension(self.parse_scalar_type_extension(start_position)?)
+            }
+            LexicalTokenType::Name(name) if name == "type" => {
+                Definition::ObjectTypeExtension(self.parse_object_type_extension(start_position)?)
+            }
+            LexicalTokenType::Name(name) if name == "interface" => {
+                Definition::InterfaceTypeExtension(
+                    self.parse_interface_type_extension(start_position)?,
+                )
+            }
+            LexicalTokenType::Name(name) if name == "union" => {
+                Definition::UnionTypeExtension(self.parse_union_type_extension(start_position)?)
+            }
+            LexicalTokenType::Name(name) if name == "enum" => {
+                Definition::EnumTypeExtension(self.parse_enum_type_extension(start_position)?)
+            }
+            LexicalTokenType::Name(name) if name == "input" => {
+                Definition::InputObjectTypeExtension(
+                    self.parse_input_object_type_extension(start_position)?,
+                )
+            }
+            _ => {
+                return Err(Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    String::from("Expected schema or type extension"),
+                    token.position,
+                ));
+            }
+        };
+
+        Ok(definition)
+    }
+
+    fn parse_schema_extension(
+        &mut self,
+        start_position: Range,
+    ) -> Result<SchemaExtension, Diagnostic> {
+        self.expect_next(LexicalTokenType::Name(String::from("schema")))?;
+        let directives = self.parse_directives()?;
+
+        let mut operation_types = Vec::new();
+        if self.peek_safe().token_type == LexicalTokenType::Punctuator(Punctuator::LeftBrace) {
+            operation_types = self.parse_root_operation_types()?;
+        }
+
+        if directives.is_empty() && operation_types.is_empty() {
+            return Err(
+                self.empty_extension_error("Schema extension", "directives or operation types")
+            );
+        }
+
+        Ok(SchemaExtension {
+            operation_types,
+            directives,
+            position: Range::new(start_position.start, self.previous_end.clone()),
+        })
+    }
+
+    fn parse_scalar_type_extension(
+        &mut self,
+        start_position: Range,
+    ) -> Result<ScalarTypeExtension, Diagnostic> {
+        self.expect_next(LexicalTokenType::Name(String::from("scalar")))?;
+        let name = self.parse_name()?;
+        let directives = self.parse_directives()?;
+
+        if directives.is_empty() {
+            return Err(self.empty_extension_error("Scalar type extension", "directives"));
+        }
+
+        Ok(ScalarTypeExtension {
+            name,
+            directives,
+            position: Range::new(start_position.start, self.previous_end.clone()),
+        })
+    }
+
+    fn parse_object_type_extension(
+        &mut self,
+        start_position: Range,
+    ) -> Result<ObjectTypeExtension, Diagnostic> {
+        self.expect_next(LexicalTokenType::Name(String::from("type")))?;
+        let name = self.parse_name()?;
+        let interfaces = self.parse_interfaces()?;
+        let directives = self.parse_directives()?;
+
+        let mut fields = Vec::new();
+        if self.peek_safe().token_type == LexicalTokenType::Punctuator(Punctuator::LeftBrace) {
+            fields = self.parse_fields()?;
+        }
+
+        if interfaces.is_empty() && directives.is_empty() && fields.is_empty() {
+            return Err(self.empty_extension_error(
+                "Object type extension",
+                "interfaces, directives or fields",
+            ));
+        }
+
+        Ok(ObjectTypeExtension {
+            name,
+            interfaces,
+            directives,
+            fields,
+            position: Range::new(start_position.start, self.previous_end.clone()),
+        })
+    }
+
+    fn parse_interface_type_extension(
+        &mut self,
+        start_position: Range,
+    ) -> Result<InterfaceTypeExtension, Diagnostic> {
+        self.expect_next(LexicalTokenType::Name(String::from("interface")))?;
+        let name = self.parse_name()?;
+        let interfaces = self.parse_interfaces()?;
+        let directives = self.parse_directives()?;
+
+        let mut fields = Vec::new();
+        if self.peek_safe().token_type == LexicalTokenType::Punctuator(Punctuator::LeftBrace) {
+            fields = self.parse_fields()?;
+        }
+
+        if interfaces.is_empty() && directives.is_empty() && fields.is_empty() {
+            return Err(self.empty_extension_error(
+                "Interface type extension",
+                "interfaces, directives or fields",
+            ));
+        }
+
+        Ok(InterfaceTypeExtension {
+            name,
+            interfaces,
+            directives,
+            fields,
+            position: Range::new(start_position.start, self.previous_end.clone()),
+        })
+    }
+
+    fn parse_union_type_extension(
+        &mut self,
+        start_position: Range,
+    ) -> Result<UnionTypeExtension, Diagnostic> {
+        self.expect_next(LexicalTokenType::Name(String::from("union")))?;
+        let name = self.parse_name()?;
+        let directives = self.parse_directives()?;
+
+        let mut member_types = Vec::new();
+        if self.peek_safe().token_type == LexicalTokenType::Punctuator(Punctuator::EqualSign) {
+            member_types = self.parse_union_member_types()?;
+        }
+
+        if directives.is_empty() && member_types.is_empty() {
+            return Err(
+                self.empty_extension_error("Union type extension", "directives or member types")
+            );
+        }
+
+        Ok(UnionTypeExtension {
+            name,
+            directives,
+            member_types,
+            position: Range::new(start_position.start, self.previous_end.clone()),
+        })
+    }
+
+    fn parse_enum_type_extension(
+        &mut self,
+        start_position: Range,
+    ) -> Result<EnumTypeExtension, Diagnostic> {
+        self.expect_next(LexicalTokenType::Name(String::from("enum")))?;
+        let name = self.parse_name()?;
+        let directives = self.parse_directives()?;
+
+        let mut values = Vec::new();
+        if self.peek_safe().token_type == LexicalTokenType::Punctuator(Punctuator::LeftBrace) {
+            values = self.parse_enum_values()?;
+        }
+
+        if directives.is_empty() && values.is_empty() {
+            return Err(self.empty_extension_error("Enum type extension", "directives or values"));
+        }
+
+        Ok(EnumTypeExtension {
+            name,
+            directives,
+            values,
+            position: Range::new(start_position.start, self.previous_end.clone()),
+        })
+    }
+
+    fn parse_input_object_type_extension(
+        &mut self,
+        start_position: Range,
+    ) -> Result<InputObjectTypeExtension, Diagnostic> {
+        self.expect_next(LexicalTokenType::Name(String::from("input")))?;
+        let name = self.parse_name()?;
+        let directives = self.parse_directives()?;
+
+        let mut fields = Vec::new();
+        if self.peek_safe().token_type == LexicalTokenType::Punctuator(Punctuator::LeftBrace) {
+            fields = self.parse_input_fields()?;
+        }
+
+        if directives.is_empty() && fields.is_empty() {
+            return Err(
+                self.empty_extension_error("Input object type extension", "directives or fields")
+            );
+        }
+
+        Ok(InputObjectTypeExtension {
+            name,
+            directives,
+            fields,
+            position: Range::new(start_position.start, self.previous_end.clone()),
+        })
+    }
+
+    /// Extensions must add something to the type they extend, so an
+    /// extension with no additions is reported at the token that follows it.
+    fn empty_extension_error(&self, kind: &str, additions: &str) -> Diagnostic {
+        Diagnostic::new(
+            DiagnosticSeverity::Error,
+            format!("{} must add at least one of {}", kind, additions),
+            self.get_current_position(),
+        )
+    }
 }
@@ -1,6 +1,7 @@
 #![cfg(test)]
 
 use super::*;
+use crate::lsp::types::Position;
 
 #[test]
 fn it_parses_unnamed_queries() {
@@ -9,15 +10,15 @@ fn it_parses_unnamed_queries() {
                 test
             }"#;
 
-    let document = parse(source.to_string()).unwrap();
+    let document = parse_strict(source.to_string()).unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::OperationDefinition(operation_definition)) => {
             assert_eq!(operation_definition.operation, OperationType::Query);
             assert_eq!(operation_definition.name, None);
             assert_eq!(operation_definition.variable_definitions.len(), 0);
             assert_eq!(operation_definition.directives.len(), 0);
-            assert_eq!(operation_definition.anonymous, false);
+            assert!(!operation_definition.anonymous);
             assert_eq!(operation_definition.selection_set.selections.len(), 1);
         }
         _ => panic!("Expected OperationDefinition"),
@@ -31,9 +32,9 @@ fn it_parses_named_queries() {
                 test
             }"#;
 
-    let document = parse(source.to_string()).unwrap();
+    let document = parse_strict(source.to_string()).unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::OperationDefinition(operation_definition)) => {
             if let Some(name) = &operation_definition.name {
                 assert_eq!(name.value, "Test");
@@ -52,13 +53,13 @@ fn it_parses_anonymous_queries() {
                 test
             }"#;
 
-    let document = parse(source.to_string()).unwrap();
+    let document = parse_strict(source.to_string()).unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::OperationDefinition(operation_definition)) => {
             assert_eq!(operation_definition.operation, OperationType::Query);
             assert_eq!(operation_definition.name, None);
-            assert_eq!(operation_definition.anonymous, true);
+            assert!(operation_definition.anonymous);
         }
         _ => panic!("Expected OperationDefinition"),
     }
@@ -71,13 +72,13 @@ fn it_parses_queries_with_variables() {
                 test(id: $id, name: $name)
             }"#;
 
-    let document = parse(source.to_string()).unwrap();
+    let document = parse_strict(source.to_string()).unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::OperationDefinition(operation_definition)) => {
             assert_eq!(operation_definition.variable_definitions.len(), 2);
 
-            let var_1 = operation_definition.variable_definitions.get(0).unwrap();
+            let var_1 = operation_definition.variable_definitions.first().unwrap();
             assert_eq!(var_1.variable.name.value, "id");
             // TODO assert values
 
@@ -110,7 +111,7 @@ fn it_successfully_parses_a_complex_query() {
                 }
             }"#;
 
-    let document = parse(source.to_string());
+    let document = parse_strict(source.to_string());
     assert!(document.is_ok());
 }
 
@@ -127,9 +128,9 @@ fn it_parses_fragment_definitions() {
                 }
             }"#;
 
-    let document = parse(source.to_string()).unwrap();
+    let document = parse_strict(source.to_string()).unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::FragmentDefinition(fragment_definition)) => {
             assert_eq!(fragment_definition.name.value, "UserFields");
             assert_eq!(fragment_definition.type_condition.name.value, "User");
@@ -148,12 +149,12 @@ fn it_can_parse_fragment_spreads() {
                 ...TestDirective @test
             }"#;
 
-    let document = parse(source.to_string()).unwrap();
+    let document = parse_strict(source.to_string()).unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::OperationDefinition(operation_definition)) => {
             let selection_set = &operation_definition.selection_set.selections;
-            let fragment_spread_1 = selection_set.get(0).unwrap();
+            let fragment_spread_1 = selection_set.first().unwrap();
             let fragment_spread_2 = selection_set.get(1).unwrap();
 
             match fragment_spread_1 {
@@ -186,12 +187,12 @@ fn it_can_parse_inline_fragments() {
                 }
             }"#;
 
-    let document = parse(source.to_string()).unwrap();
+    let document = parse_strict(source.to_string()).unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::OperationDefinition(operation_definition)) => {
             let selection_set = &operation_definition.selection_set.selections;
-            let inline_fragment = selection_set.get(0).unwrap();
+            let inline_fragment = selection_set.first().unwrap();
 
             match inline_fragment {
                 Selection::InlineFragment(inline_fragment) => {
@@ -218,11 +219,11 @@ fn it_can_parse_schema_definitions() {
                 subscription: Subscription
             }"#;
 
-    let document = parse(source.to_string()).unwrap();
+    let document = parse_strict(source.to_string()).unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::SchemaDefinition(schema_definition)) => {
-            let query = schema_definition.operation_types.get(0).unwrap();
+            let query = schema_definition.operation_types.first().unwrap();
             assert_eq!(query.operation_type, OperationType::Query);
             assert_eq!(query.named_type.name.value, "Query");
 
@@ -248,7 +249,7 @@ fn it_errors_for_invalid_schema_definitions() {
                 foo: Foo
             }"#;
 
-    let document = parse(source.to_string());
+    let document = parse_strict(source.to_string());
     assert!(document.is_err());
 }
 
@@ -261,10 +262,10 @@ fn it_can_parse_scalar_type_definitions() {
             scalar DateTime
         "#;
 
-    let document = parse(source.to_string());
+    let document = parse_strict(source.to_string());
     let document = document.unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::ScalarTypeDefinition(scalar_type_definition)) => {
             assert_eq!(scalar_type_definition.name.value, "Date");
         }
@@ -280,7 +281,7 @@ fn it_errs_for_operations_with_description() {
                 test
             }"#;
 
-    let document = parse(source.to_string());
+    let document = parse_strict(source.to_string());
     assert!(document.is_err());
 }
 
@@ -295,10 +296,10 @@ fn it_can_parse_object_types() {
         }
     "#;
 
-    let document = parse(source.to_string());
+    let document = parse_strict(source.to_string());
     let document = document.unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::ObjectTypeDefinition(object_type_definition)) => {
             assert_eq!(object_type_definition.name.value, "User");
             assert_eq!(object_type_definition.fields.len(), 4);
@@ -318,10 +319,10 @@ fn it_can_parse_interface_types() {
         }
     "#;
 
-    let document = parse(source.to_string());
+    let document = parse_strict(source.to_string());
     let document = document.unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::InterfaceTypeDefinition(interface_type_definition)) => {
             assert_eq!(interface_type_definition.name.value, "User");
             assert_eq!(interface_type_definition.fields.len(), 4);
@@ -336,10 +337,10 @@ fn it_can_parse_union_types() {
         union User = Admin | Member
     "#;
 
-    let document = parse(source.to_string());
+    let document = parse_strict(source.to_string());
     let document = document.unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::UnionTypeDefinition(union_type_definition)) => {
             assert_eq!(union_type_definition.name.value, "User");
             assert_eq!(union_type_definition.member_types.len(), 2);
@@ -358,10 +359,10 @@ fn it_can_parse_enum_types() {
         }
     "#;
 
-    let document = parse(source.to_string());
+    let document = parse_strict(source.to_string());
     let document = document.unwrap();
 
-    match document.definitions.get(0) {
+    match document.definitions.first() {
         Some(Definition::EnumTypeDefinition(enum_type_definition)) => {
             assert_eq!(enum_type_definition.name.value, "Role");
             assert_eq!(enum_type_definition.values.len(), 3);
@@ -369,3 +370,1362 @@ fn it_can_parse_enum_types() {
         _ => panic!("Expected EnumTypeDefinition"),
     }
 }
+
+#[test]
+fn it_parses_mutations_returning_scalars() {
+    let source = r#"
+        mutation {
+            incrementCounter
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::OperationDefinition(operation_definition)) => {
+            assert_eq!(operation_definition.operation, OperationType::Mutation);
+            assert_eq!(operation_definition.selection_set.selections.len(), 1);
+
+            match operation_definition.selection_set.selections.first() {
+                Some(Selection::Field(field)) => {
+                    assert_eq!(field.name.value, "incrementCounter");
+                    assert!(field.selection_set.is_none());
+                }
+                _ => panic!("Expected Field"),
+            }
+        }
+        _ => panic!("Expected OperationDefinition"),
+    }
+}
+
+#[test]
+fn it_parses_mutations_with_arguments_returning_scalars() {
+    let source = r#"
+        mutation CreateUser($input: UserInput!) {
+            createUser(input: $input)
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::OperationDefinition(operation_definition)) => {
+            assert_eq!(operation_definition.operation, OperationType::Mutation);
+            assert_eq!(operation_definition.variable_definitions.len(), 1);
+
+            match operation_definition.selection_set.selections.first() {
+                Some(Selection::Field(field)) => {
+                    assert_eq!(field.name.value, "createUser");
+                    assert_eq!(field.arguments.len(), 1);
+                    assert!(field.selection_set.is_none());
+                }
+                _ => panic!("Expected Field"),
+            }
+        }
+        _ => panic!("Expected OperationDefinition"),
+    }
+}
+
+#[test]
+fn it_can_parse_schema_definitions_with_only_a_mutation() {
+    let source = r#"
+            schema {
+                mutation: Mutation
+            }"#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::SchemaDefinition(schema_definition)) => {
+            assert_eq!(schema_definition.operation_types.len(), 1);
+
+            let mutation = schema_definition.operation_types.first().unwrap();
+            assert_eq!(mutation.operation_type, OperationType::Mutation);
+            assert_eq!(mutation.named_type.name.value, "Mutation");
+        }
+        _ => panic!("Expected SchemaDefinition"),
+    }
+}
+
+#[test]
+fn it_errs_for_unclosed_object_values() {
+    let source = "query { field(arg: {a: 1) }";
+
+    let diagnostic = parse_strict(source.to_string()).unwrap_err();
+
+    assert_eq!(diagnostic.message, "Unclosed object value, expected \"}\"");
+    assert_eq!(diagnostic.range.start, Position::new(0, 24, 24));
+    assert_eq!(diagnostic.range.end, Position::new(0, 25, 25));
+}
+
+#[test]
+fn it_errs_for_unclosed_list_values() {
+    let source = "query { field(arg: [1, 2) }";
+
+    let diagnostic = parse_strict(source.to_string()).unwrap_err();
+
+    assert_eq!(diagnostic.message, "Unclosed list value, expected \"]\"");
+    assert_eq!(diagnostic.range.start, Position::new(0, 24, 24));
+    assert_eq!(diagnostic.range.end, Position::new(0, 25, 25));
+}
+
+#[test]
+fn it_parses_directives_on_input_value_definitions() {
+    let source = r#"
+        input Foo {
+            oldField: String @deprecated(reason: "use newField")
+            newField: String
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::InputObjectTypeDefinition(input_object_type_definition)) => {
+            assert_eq!(input_object_type_definition.fields.len(), 2);
+
+            let old_field = &input_object_type_definition.fields[0];
+            assert_eq!(old_field.directives.len(), 1);
+
+            let directive = &old_field.directives[0];
+            assert_eq!(directive.name.value, "deprecated");
+            assert_eq!(directive.arguments.len(), 1);
+            assert_eq!(directive.arguments[0].name.value, "reason");
+
+            match &directive.arguments[0].value {
+                Value::StringValue(string_value) => {
+                    assert_eq!(string_value.value, "use newField");
+                }
+                _ => panic!("Expected StringValue"),
+            }
+
+            let new_field = &input_object_type_definition.fields[1];
+            assert_eq!(new_field.name.value, "newField");
+            assert_eq!(new_field.directives.len(), 0);
+        }
+        _ => panic!("Expected InputObjectTypeDefinition"),
+    }
+}
+
+#[test]
+fn it_parses_block_string_descriptions() {
+    let source = r#"
+        """
+        A user of the application.
+
+        Users can have many friends.
+        """
+        type User {
+            id: ID!
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::ObjectTypeDefinition(object_type_definition)) => {
+            let description = object_type_definition.description.as_ref().unwrap();
+            assert_eq!(
+                description.value,
+                "A user of the application.\n\nUsers can have many friends."
+            );
+            assert!(description.block);
+            assert_eq!(object_type_definition.name.value, "User");
+        }
+        _ => panic!("Expected ObjectTypeDefinition"),
+    }
+}
+
+#[test]
+fn it_parses_int_values_larger_than_32_bits() {
+    let source = "query { foo(x: 2147483648) }";
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::OperationDefinition(operation_definition)) => {
+            match operation_definition.selection_set.selections.first() {
+                Some(Selection::Field(field)) => match &field.arguments[0].value {
+                    Value::IntValue(int_value) => {
+                        assert_eq!(int_value.value, 2147483648_i64);
+                    }
+                    _ => panic!("Expected IntValue"),
+                },
+                _ => panic!("Expected Field"),
+            }
+        }
+        _ => panic!("Expected OperationDefinition"),
+    }
+}
+
+#[test]
+fn it_skips_comment_tokens() {
+    use crate::lexer::lex_with_options;
+    use crate::lexer::types::LexerOptions;
+
+    let source = r#"
+        # leading comment
+        query Test { # trailing comment
+            test
+        }
+    "#;
+
+    let options = LexerOptions {
+        preserve_comments: true,
+        ..LexerOptions::default()
+    };
+    let tokens = lex_with_options(source, options).unwrap();
+    let document = Parser::new(tokens).parse().unwrap();
+
+    assert_eq!(document.definitions.len(), 1);
+}
+
+#[test]
+fn it_tags_diagnostics_with_the_source_uri() {
+    let diagnostic = parse_with_uri("bad source", "file:///test.graphql").unwrap_err();
+
+    assert_eq!(
+        diagnostic.source_uri,
+        Some("file:///test.graphql".to_string())
+    );
+}
+
+#[test]
+fn it_does_not_tag_diagnostics_without_a_uri() {
+    let diagnostic = parse_strict("bad source".to_string()).unwrap_err();
+
+    assert_eq!(diagnostic.source_uri, None);
+}
+
+#[test]
+fn it_parses_from_a_token_iterator() {
+    use crate::lexer::lex_iter;
+
+    let source = r#"
+        query Test($id: ID!) {
+            user(id: $id) {
+                ...UserFields
+            }
+        }
+
+        fragment UserFields on User {
+            name
+        }
+    "#;
+
+    let eager = parse_strict(source.to_string()).unwrap();
+    let lazy = Parser::from_iter(lex_iter(source)).parse().unwrap();
+
+    assert_eq!(eager, lazy);
+}
+
+#[test]
+fn it_surfaces_lexer_errors_from_a_token_iterator() {
+    use crate::lexer::lex_iter;
+
+    let result = Parser::from_iter(lex_iter("query { ? }")).parse();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn it_can_parse_directive_definitions() {
+    let source = r#"
+        directive @internal on FIELD_DEFINITION
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::DirectiveDefinition(directive_definition)) => {
+            assert_eq!(directive_definition.name.value, "internal");
+            assert!(directive_definition.arguments.is_empty());
+            assert!(!directive_definition.repeatable);
+            assert_eq!(
+                directive_definition.locations,
+                vec![DirectiveLocation::TypeSystem(
+                    types::TypeSystemDirectiveLocation::FieldDefinition
+                )]
+            );
+        }
+        _ => panic!("Expected DirectiveDefinition"),
+    }
+}
+
+#[test]
+fn it_can_parse_directive_definitions_with_arguments() {
+    let source = r#"
+        "Marks an element as deprecated"
+        directive @deprecated(reason: String = "No longer supported") on FIELD_DEFINITION
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::DirectiveDefinition(directive_definition)) => {
+            assert_eq!(directive_definition.name.value, "deprecated");
+            assert!(directive_definition.description.is_some());
+            assert_eq!(directive_definition.arguments.len(), 1);
+            assert_eq!(directive_definition.arguments[0].name.value, "reason");
+        }
+        _ => panic!("Expected DirectiveDefinition"),
+    }
+}
+
+#[test]
+fn it_can_parse_repeatable_directive_definitions() {
+    let source = r#"
+        directive @tag(name: String!) repeatable on OBJECT
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::DirectiveDefinition(directive_definition)) => {
+            assert!(directive_definition.repeatable);
+        }
+        _ => panic!("Expected DirectiveDefinition"),
+    }
+}
+
+#[test]
+fn it_can_parse_directive_definitions_with_multiple_locations() {
+    let source = r#"
+        directive @skip(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::DirectiveDefinition(directive_definition)) => {
+            assert_eq!(
+                directive_definition.locations,
+                vec![
+                    DirectiveLocation::Executable(types::ExecutableDirectiveLocation::Field),
+                    DirectiveLocation::Executable(
+                        types::ExecutableDirectiveLocation::FragmentSpread
+                    ),
+                    DirectiveLocation::Executable(
+                        types::ExecutableDirectiveLocation::InlineFragment
+                    ),
+                ]
+            );
+        }
+        _ => panic!("Expected DirectiveDefinition"),
+    }
+}
+
+#[test]
+fn it_can_parse_schema_extensions() {
+    let source = r#"
+        extend schema @link {
+            subscription: Subscription
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::SchemaExtension(schema_extension)) => {
+            assert_eq!(schema_extension.directives.len(), 1);
+            assert_eq!(schema_extension.operation_types.len(), 1);
+        }
+        _ => panic!("Expected SchemaExtension"),
+    }
+}
+
+#[test]
+fn it_can_parse_scalar_type_extensions() {
+    let source = r#"
+        extend scalar Date @specifiedBy(url: "https://tools.ietf.org/html/rfc3339")
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::ScalarTypeExtension(scalar_type_extension)) => {
+            assert_eq!(scalar_type_extension.name.value, "Date");
+            assert_eq!(scalar_type_extension.directives.len(), 1);
+        }
+        _ => panic!("Expected ScalarTypeExtension"),
+    }
+}
+
+#[test]
+fn it_can_parse_object_type_extensions() {
+    let source = r#"
+        extend type User implements Node {
+            avatar: String
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::ObjectTypeExtension(object_type_extension)) => {
+            assert_eq!(object_type_extension.name.value, "User");
+            assert_eq!(object_type_extension.interfaces.len(), 1);
+            assert_eq!(object_type_extension.fields.len(), 1);
+        }
+        _ => panic!("Expected ObjectTypeExtension"),
+    }
+}
+
+#[test]
+fn it_can_parse_interface_type_extensions() {
+    let source = r#"
+        extend interface Node @key(fields: "id")
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::InterfaceTypeExtension(interface_type_extension)) => {
+            assert_eq!(interface_type_extension.name.value, "Node");
+            assert_eq!(interface_type_extension.directives.len(), 1);
+            assert!(interface_type_extension.fields.is_empty());
+        }
+        _ => panic!("Expected InterfaceTypeExtension"),
+    }
+}
+
+#[test]
+fn it_can_parse_union_type_extensions() {
+    let source = r#"
+        extend union SearchResult = Photo | Person
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::UnionTypeExtension(union_type_extension)) => {
+            assert_eq!(union_type_extension.name.value, "SearchResult");
+            assert_eq!(union_type_extension.member_types.len(), 2);
+        }
+        _ => panic!("Expected UnionTypeExtension"),
+    }
+}
+
+#[test]
+fn it_can_parse_enum_type_extensions() {
+    let source = r#"
+        extend enum Role {
+            OWNER
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::EnumTypeExtension(enum_type_extension)) => {
+            assert_eq!(enum_type_extension.name.value, "Role");
+            assert_eq!(enum_type_extension.values.len(), 1);
+        }
+        _ => panic!("Expected EnumTypeExtension"),
+    }
+}
+
+#[test]
+fn it_can_parse_input_object_type_extensions() {
+    let source = r#"
+        extend input UserFilter {
+            email: String
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::InputObjectTypeExtension(input_object_type_extension)) => {
+            assert_eq!(input_object_type_extension.name.value, "UserFilter");
+            assert_eq!(input_object_type_extension.fields.len(), 1);
+        }
+        _ => panic!("Expected InputObjectTypeExtension"),
+    }
+}
+
+#[test]
+fn it_errs_for_extensions_without_additions() {
+    assert!(parse_strict("extend type User".to_string()).is_err());
+    assert!(parse_strict("extend scalar Date".to_string()).is_err());
+    assert!(parse_strict("extend schema".to_string()).is_err());
+}
+
+#[test]
+fn it_recovers_from_errors_in_multiple_definitions() {
+    let source = r#"
+        query First {
+            user(
+        }
+
+        type User {
+            name
+        }
+
+        query Last {
+            user
+        }
+    "#;
+
+    let options = types::ParseOptions {
+        recover_errors: true,
+    };
+    let (document, diagnostics) = parse_with_options(source, options);
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].range.start.line, 3);
+    assert_eq!(diagnostics[1].range.start.line, 7);
+
+    let document = document.unwrap();
+    assert_eq!(document.definitions.len(), 1);
+    match document.definitions.first() {
+        Some(Definition::OperationDefinition(operation)) => {
+            assert_eq!(operation.name.as_ref().unwrap().value, "Last");
+        }
+        _ => panic!("Expected OperationDefinition"),
+    }
+}
+
+#[test]
+fn it_stops_at_the_first_error_without_recovery() {
+    let source = "query First { user( } query Last { user }";
+
+    let (document, diagnostics) = parse_with_options(source, types::ParseOptions::default());
+
+    assert!(document.is_none());
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+fn it_errs_for_fragments_named_on() {
+    let source = "fragment on on User { id }";
+
+    let diagnostic = parse_strict(source.to_string()).unwrap_err();
+
+    assert!(diagnostic.message.contains("on"));
+}
+
+#[test]
+fn it_parses_correctly_named_fragments() {
+    let source = "fragment myFrag on User { id }";
+
+    assert!(parse_strict(source.to_string()).is_ok());
+}
+
+#[test]
+fn it_errs_for_reserved_enum_values() {
+    assert!(parse_strict("enum Bad { true }".to_string()).is_err());
+    assert!(parse_strict("enum Bad { false }".to_string()).is_err());
+    assert!(parse_strict("enum Bad { null }".to_string()).is_err());
+}
+
+#[test]
+fn it_allows_uppercase_boolean_enum_values() {
+    assert!(parse_strict("enum Good { TRUE }".to_string()).is_ok());
+    assert!(parse_strict("enum Good { FALSE }".to_string()).is_ok());
+}
+
+#[test]
+fn it_errs_for_reserved_names_in_definitions() {
+    let diagnostic = parse_strict("type __Foo { id: ID }".to_string()).unwrap_err();
+    assert_eq!(
+        diagnostic.message,
+        "Names starting with \"__\" are reserved"
+    );
+
+    assert!(parse_strict("scalar __Bar".to_string()).is_err());
+    assert!(parse_strict("type Foo { __id: ID }".to_string()).is_err());
+}
+
+#[test]
+fn it_allows_reserved_names_in_selections() {
+    assert!(parse_strict("{ __typename }".to_string()).is_ok());
+}
+
+#[test]
+fn it_errs_for_variables_in_input_default_values() {
+    let diagnostic = parse_strict("input Filter { limit: Int = $limit }".to_string()).unwrap_err();
+    assert_eq!(
+        diagnostic.message,
+        "Variables are not allowed in constant value context"
+    );
+
+    assert!(parse_strict("type Query { users(ids: [ID] = [$id]): [User] }".to_string()).is_err());
+    assert!(parse_strict("input Filter { page: Page = { size: $size } }".to_string()).is_err());
+}
+
+#[test]
+fn it_parses_constant_input_default_values() {
+    let source = r#"
+        input Filter {
+            limit: Int = 42
+            order: Order = ASC
+            tags: [String] = ["a", "b"]
+            page: Page = { size: 10, tags: ["a", "b"] }
+        }
+    "#;
+
+    assert!(parse_strict(source.to_string()).is_ok());
+}
+
+#[test]
+fn it_parses_repeatable_before_locations() {
+    let source = "directive @tag repeatable on FIELD | OBJECT";
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::DirectiveDefinition(directive_definition)) => {
+            assert!(directive_definition.repeatable);
+            assert_eq!(directive_definition.locations.len(), 2);
+        }
+        _ => panic!("Expected DirectiveDefinition"),
+    }
+}
+
+#[test]
+fn it_defaults_directive_definitions_to_not_repeatable() {
+    let source = "directive @tag on FIELD | OBJECT";
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::DirectiveDefinition(directive_definition)) => {
+            assert!(!directive_definition.repeatable);
+        }
+        _ => panic!("Expected DirectiveDefinition"),
+    }
+}
+
+#[test]
+fn it_errs_for_misplaced_repeatable() {
+    let diagnostic = parse_strict("directive @tag on repeatable FIELD".to_string()).unwrap_err();
+    assert!(diagnostic.message.contains("repeatable"));
+
+    assert!(parse_strict("directive @tag on FIELD repeatable".to_string()).is_err());
+}
+
+#[test]
+fn it_parses_every_directive_location() {
+    let source = r#"
+        directive @everywhere on
+            | QUERY
+            | MUTATION
+            | SUBSCRIPTION
+            | FIELD
+            | FRAGMENT_DEFINITION
+            | FRAGMENT_SPREAD
+            | INLINE_FRAGMENT
+            | VARIABLE_DEFINITION
+            | SCHEMA
+            | SCALAR
+            | OBJECT
+            | FIELD_DEFINITION
+            | ARGUMENT_DEFINITION
+            | INTERFACE
+            | UNION
+            | ENUM
+            | ENUM_VALUE
+            | INPUT_OBJECT
+            | INPUT_FIELD_DEFINITION
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::DirectiveDefinition(directive_definition)) => {
+            assert_eq!(directive_definition.locations.len(), 19);
+            assert_eq!(
+                directive_definition.locations.last(),
+                Some(&DirectiveLocation::TypeSystem(
+                    types::TypeSystemDirectiveLocation::InputFieldDefinition
+                ))
+            );
+        }
+        _ => panic!("Expected DirectiveDefinition"),
+    }
+}
+
+#[test]
+fn it_parses_directive_locations_with_a_leading_bar() {
+    let source = "directive @tag on | FIELD";
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::DirectiveDefinition(directive_definition)) => {
+            assert_eq!(
+                directive_definition.locations,
+                vec![DirectiveLocation::Executable(
+                    types::ExecutableDirectiveLocation::Field
+                )]
+            );
+        }
+        _ => panic!("Expected DirectiveDefinition"),
+    }
+}
+
+#[test]
+fn it_errs_for_unknown_directive_locations() {
+    let diagnostic = parse_strict("directive @tag on FIELD | NOWHERE".to_string()).unwrap_err();
+
+    assert_eq!(diagnostic.message, "Unknown directive location: NOWHERE");
+}
+
+#[test]
+fn it_errs_for_variables_in_variable_default_values() {
+    let source = "query Foo($x: Int = $y) { a }";
+
+    let diagnostic = parse_strict(source.to_string()).unwrap_err();
+
+    assert!(diagnostic
+        .message
+        .contains("Variables are not allowed in constant value context"));
+}
+
+#[test]
+fn it_parses_constant_variable_default_values() {
+    let source = "query Foo($x: Int = 42) { a }";
+
+    assert!(parse_strict(source.to_string()).is_ok());
+}
+
+#[test]
+fn it_parses_executable_documents() {
+    let source = r#"
+        query User { user { ...UserFields } }
+        fragment UserFields on User { id }
+    "#;
+
+    let document = parse_executable(source).unwrap();
+
+    assert_eq!(document.definitions.len(), 2);
+}
+
+#[test]
+fn it_errs_for_type_system_definitions_in_executable_documents() {
+    let source = r#"
+        type User { id: ID }
+        query { user { id } }
+    "#;
+
+    let diagnostic = parse_executable(source).unwrap_err();
+
+    assert_eq!(
+        diagnostic.message,
+        "Unexpected type system definition in executable document"
+    );
+    assert_eq!(diagnostic.range.start.line, 1);
+}
+
+#[test]
+fn it_parses_type_system_documents() {
+    let source = r#"
+        type User { id: ID }
+        type Query { user: User }
+    "#;
+
+    let document = parse_type_system(source).unwrap();
+
+    assert_eq!(document.definitions.len(), 2);
+}
+
+#[test]
+fn it_errs_for_executable_definitions_in_type_system_documents() {
+    let source = r#"
+        type User {}
+        query { user }
+    "#;
+
+    let diagnostic = parse_type_system(source).unwrap_err();
+
+    assert_eq!(
+        diagnostic.message,
+        "Unexpected executable definition in type system document"
+    );
+    assert_eq!(diagnostic.range.start.line, 2);
+}
+
+#[test]
+fn it_errs_for_anonymous_operations_alongside_other_operations() {
+    let diagnostic = parse_strict("{ a } query B { b }".to_string()).unwrap_err();
+
+    assert_eq!(
+        diagnostic.message,
+        "This anonymous operation must be the only defined operation in the document."
+    );
+    assert_eq!(diagnostic.range.start.character, 0);
+}
+
+#[test]
+fn it_parses_a_lone_anonymous_operation() {
+    assert!(parse_strict("{ a }".to_string()).is_ok());
+    assert!(parse_strict("{ a } fragment F on User { id }".to_string()).is_ok());
+}
+
+#[test]
+fn it_parses_multiple_named_operations() {
+    assert!(parse_strict("query A { a } query B { b }".to_string()).is_ok());
+}
+
+#[test]
+fn it_starts_operations_at_the_first_token() {
+    let document = parse_strict("   { a }".to_string()).unwrap();
+    match document.definitions.first() {
+        Some(Definition::OperationDefinition(operation)) => {
+            assert_eq!(operation.position.start.character, 3);
+        }
+        _ => panic!("Expected OperationDefinition"),
+    }
+
+    let document = parse_strict("  query Named { a }".to_string()).unwrap();
+    match document.definitions.first() {
+        Some(Definition::OperationDefinition(operation)) => {
+            assert_eq!(operation.position.start.character, 2);
+            assert_eq!(operation.name.as_ref().unwrap().value, "Named");
+        }
+        _ => panic!("Expected OperationDefinition"),
+    }
+}
+
+#[test]
+fn it_parses_implements_with_a_leading_ampersand() {
+    let source = "type Foo implements & Bar & Baz { id: ID }";
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::ObjectTypeDefinition(object_type_definition)) => {
+            assert_eq!(object_type_definition.interfaces.len(), 2);
+            assert_eq!(object_type_definition.interfaces[0].name.value, "Bar");
+            assert_eq!(object_type_definition.interfaces[1].name.value, "Baz");
+        }
+        _ => panic!("Expected ObjectTypeDefinition"),
+    }
+}
+
+#[test]
+fn it_parses_union_members_with_a_leading_bar() {
+    let with_bar = parse_strict("union A = | B | C".to_string()).unwrap();
+    let without_bar = parse_strict("union A = B | C".to_string()).unwrap();
+
+    let member_names = |document: &Document| match document.definitions.first() {
+        Some(Definition::UnionTypeDefinition(union_type_definition)) => union_type_definition
+            .member_types
+            .iter()
+            .map(|member_type| member_type.name.value.clone())
+            .collect::<Vec<_>>(),
+        _ => panic!("Expected UnionTypeDefinition"),
+    };
+
+    assert_eq!(member_names(&with_bar), vec!["B", "C"]);
+    assert_eq!(member_names(&with_bar), member_names(&without_bar));
+}
+
+#[test]
+fn it_errs_for_empty_enums() {
+    let diagnostic = parse_strict("enum Empty {}".to_string()).unwrap_err();
+
+    assert!(diagnostic.message.contains("one or more"));
+    assert_eq!(diagnostic.range.start.character, 11);
+}
+
+#[test]
+fn it_parses_enums_with_a_single_value() {
+    assert!(parse_strict("enum Good { A }".to_string()).is_ok());
+}
+
+#[test]
+fn it_parses_directives_on_variable_definitions() {
+    let source = r#"query Foo($x: Int = 1 @deprecated(reason: "old")) { foo }"#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::OperationDefinition(operation)) => {
+            let variable_definition = &operation.variable_definitions[0];
+            assert_eq!(variable_definition.directives.len(), 1);
+            assert_eq!(variable_definition.directives[0].name.value, "deprecated");
+        }
+        _ => panic!("Expected OperationDefinition"),
+    }
+
+    assert!(parse_strict("query Foo($x: Int @deprecated) { foo }".to_string()).is_ok());
+}
+
+#[test]
+fn it_consumes_the_closing_brace_of_object_values() {
+    let source = "{ field(arg: {a: 1, b: 2}) { id } }";
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    let operation = document.operations().next().unwrap();
+    match &operation.selection_set.selections[0] {
+        Selection::Field(field) => {
+            match &field.arguments[0].value {
+                Value::ObjectValue(object_value) => assert_eq!(object_value.fields.len(), 2),
+                _ => panic!("Expected ObjectValue"),
+            }
+            assert!(field.selection_set.is_some());
+        }
+        _ => panic!("Expected Field"),
+    }
+}
+
+#[test]
+fn it_warns_for_int_values_outside_32_bits() {
+    let result = parse("query { foo(x: 2147483648) }").unwrap();
+
+    assert_eq!(result.diagnostics.len(), 1);
+    assert!(matches!(
+        result.diagnostics[0].severity,
+        DiagnosticSeverity::Warning
+    ));
+    assert_eq!(result.document.definitions.len(), 1);
+}
+
+#[test]
+fn it_returns_no_diagnostics_for_clean_documents() {
+    let result = parse("query { foo(x: 2147483647) }").unwrap();
+
+    assert!(result.diagnostics.is_empty());
+}
+
+#[test]
+fn it_returns_errors_as_diagnostics() {
+    let diagnostics = parse("query {").unwrap_err();
+
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(diagnostics[0].severity, DiagnosticSeverity::Error));
+}
+
+#[test]
+fn it_returns_node_positions_through_enums() {
+    let document = parse_strict("{ user }".to_string()).unwrap();
+    let operation = document.operations().next().unwrap();
+
+    let selection = &operation.selection_set.selections[0];
+    match selection {
+        Selection::Field(field) => assert_eq!(selection.position(), &field.position),
+        _ => panic!("Expected Field"),
+    }
+
+    let definition = document.definitions.first().unwrap();
+    assert_eq!(definition.position(), &operation.position);
+}
+
+#[test]
+fn it_displays_types() {
+    let source = r#"
+        type Query {
+            name: String
+            id: ID!
+            tags: [String]
+            users: [User!]!
+            matrix: [[Int]]
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::ObjectTypeDefinition(object_type_definition)) => {
+            let types = object_type_definition
+                .fields
+                .iter()
+                .map(|field| field.field_type.to_string())
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                types,
+                vec!["String", "ID!", "[String]", "[User!]!", "[[Int]]"]
+            );
+        }
+        _ => panic!("Expected ObjectTypeDefinition"),
+    }
+}
+
+#[test]
+fn it_displays_constructed_types() {
+    let range = Range::new(Position::new(0, 0, 0), Position::new(0, 0, 0));
+    let named_type = Type::NamedType(NamedType {
+        name: Name {
+            value: String::from("String"),
+            position: range.clone(),
+        },
+        position: range.clone(),
+    });
+
+    let non_null_type = Type::NonNullType(NonNullType {
+        wrapped_type: Box::new(named_type),
+        position: range.clone(),
+    });
+
+    assert_eq!(format!("{}", non_null_type), "String!");
+}
+
+#[test]
+fn it_displays_values() {
+    let source = r#"
+        query ($v: Int) {
+            field(
+                variable: $v
+                int: 42
+                float: 1.5
+                whole: 2.0
+                string: "say \"hi\"\n"
+                boolean: true
+                null: null
+                enum: ACTIVE
+                list: [1, 2]
+                object: {a: 1, b: "two"}
+                nested: [{id: 1, tags: ["a"]}, {id: 2, tags: []}]
+            )
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+    let operation = document.operations().next().unwrap();
+
+    let values = match &operation.selection_set.selections[0] {
+        Selection::Field(field) => field
+            .arguments
+            .iter()
+            .map(|argument| argument.value.to_string())
+            .collect::<Vec<_>>(),
+        _ => panic!("Expected Field"),
+    };
+
+    assert_eq!(
+        values,
+        vec![
+            "$v",
+            "42",
+            "1.5",
+            "2.0",
+            r#""say \"hi\"\n""#,
+            "true",
+            "null",
+            "ACTIVE",
+            "[1, 2]",
+            r#"{a: 1, b: "two"}"#,
+            r#"[{id: 1, tags: ["a"]}, {id: 2, tags: []}]"#,
+        ]
+    );
+}
+
+#[test]
+fn it_compares_and_hashes_names_by_value() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+    use std::hash::{Hash, Hasher};
+
+    let hash = |name: &Name| {
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    let first = Name {
+        value: String::from("user"),
+        position: Range::new(Position::new(0, 0, 0), Position::new(0, 4, 4)),
+    };
+    let second = Name {
+        value: String::from("user"),
+        position: Range::new(Position::new(3, 2, 40), Position::new(3, 6, 44)),
+    };
+    let other = Name {
+        value: String::from("post"),
+        position: first.position.clone(),
+    };
+
+    assert_eq!(first, second);
+    assert_eq!(hash(&first), hash(&second));
+    assert_ne!(first, other);
+    assert_ne!(hash(&first), hash(&other));
+
+    let named_type = |name: &Name| NamedType {
+        name: name.clone(),
+        position: name.position.clone(),
+    };
+    assert_eq!(named_type(&first), named_type(&second));
+    assert_ne!(named_type(&first), named_type(&other));
+
+    let mut symbols = HashMap::new();
+    symbols.insert(first, 1);
+    assert_eq!(symbols.get(&second), Some(&1));
+}
+
+#[test]
+fn it_compares_names_structurally() {
+    use super::types::AstEq;
+
+    let first = Name {
+        value: String::from("user"),
+        position: Range::new(Position::new(0, 0, 0), Position::new(0, 4, 4)),
+    };
+    let moved = Name {
+        value: String::from("user"),
+        position: Range::new(Position::new(2, 8, 30), Position::new(2, 12, 34)),
+    };
+    let other = Name {
+        value: String::from("post"),
+        position: first.position.clone(),
+    };
+
+    assert!(first.ast_eq(&moved));
+    assert!(!first.ast_eq(&other));
+}
+
+#[test]
+fn it_compares_documents_structurally() {
+    use super::types::AstEq;
+
+    let compact = parse_strict("query Q($id: ID = 1) { user(id: $id) { name ...F } }".to_string());
+    let spread = parse_strict(
+        r#"
+        query Q($id: ID = 1) {
+            user(id: $id) {
+                name
+                ...F
+            }
+        }
+    "#
+        .to_string(),
+    );
+    let different =
+        parse_strict("query Q($id: ID = 2) { user(id: $id) { name ...F } }".to_string());
+
+    let compact = compact.unwrap();
+    assert!(compact.ast_eq(&spread.unwrap()));
+    assert!(!compact.ast_eq(&different.unwrap()));
+}
+
+#[test]
+fn it_returns_definition_names() {
+    let source = r#"
+        schema { query: Query }
+        type Query { user: User }
+        extend schema @link
+        query GetUser { user { ...UserFields } }
+        fragment UserFields on User { id }
+        directive @cached on QUERY
+        extend type User @key
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    let names = document
+        .definitions
+        .iter()
+        .map(|definition| definition.name().map(|name| name.value.as_str()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        names,
+        vec![
+            None,
+            Some("Query"),
+            None,
+            Some("GetUser"),
+            Some("UserFields"),
+            Some("cached"),
+            Some("User"),
+        ]
+    );
+
+    let anonymous = parse_strict("{ a }".to_string()).unwrap();
+    assert_eq!(anonymous.definitions[0].name(), None);
+}
+
+#[test]
+fn it_finds_operations_and_fragments_by_name() {
+    let source = r#"
+        query First { a { ...Fields } }
+        mutation Second { b }
+        fragment Fields on A { id }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    let first = document.find_operation(Some("First")).unwrap();
+    assert_eq!(first.operation, OperationType::Query);
+
+    let second = document.find_operation(Some("Second")).unwrap();
+    assert_eq!(second.operation, OperationType::Mutation);
+
+    assert!(document.find_operation(Some("Missing")).is_none());
+    assert!(document.find_operation(None).is_none());
+
+    assert_eq!(
+        document.find_fragment("Fields").unwrap().name.value,
+        "Fields"
+    );
+    assert!(document.find_fragment("Missing").is_none());
+}
+
+#[test]
+fn it_finds_anonymous_operations() {
+    let document = parse_strict("{ a }".to_string()).unwrap();
+
+    assert!(document.find_operation(None).is_some());
+}
+
+#[test]
+fn it_returns_field_response_names() {
+    let document = parse_strict("{ user smallPic: profilePic(size: 64) }".to_string()).unwrap();
+    let operation = document.operations().next().unwrap();
+
+    let response_names = operation
+        .selection_set
+        .selections
+        .iter()
+        .map(|selection| match selection {
+            Selection::Field(field) => field.response_name(),
+            _ => panic!("Expected Field"),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(response_names, vec!["user", "smallPic"]);
+}
+
+#[test]
+fn it_returns_the_named_type_of_wrapped_types() {
+    let document = parse_strict("type Query { tags: [String!]! name: ID }".to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::ObjectTypeDefinition(object_type_definition)) => {
+            assert_eq!(
+                object_type_definition.fields[0]
+                    .field_type
+                    .named_type_name(),
+                "String"
+            );
+            assert_eq!(
+                object_type_definition.fields[1]
+                    .field_type
+                    .named_type_name(),
+                "ID"
+            );
+        }
+        _ => panic!("Expected ObjectTypeDefinition"),
+    }
+}
+
+#[test]
+fn it_inspects_type_wrappers() {
+    let document = parse_strict("type Query { tags: [String!]! }".to_string()).unwrap();
+
+    let field_type = match document.definitions.first() {
+        Some(Definition::ObjectTypeDefinition(object_type_definition)) => {
+            object_type_definition.fields[0].field_type.clone()
+        }
+        _ => panic!("Expected ObjectTypeDefinition"),
+    };
+
+    assert!(field_type.is_non_null());
+    assert!(field_type.is_list());
+
+    let list_type = field_type.unwrap_non_null();
+    assert_eq!(list_type.to_string(), "[String!]");
+    assert!(!list_type.is_non_null());
+    assert!(list_type.is_list());
+    assert_eq!(list_type.unwrap_non_null(), list_type);
+
+    assert_eq!(field_type.inner_type(), list_type);
+
+    let item_type = list_type.inner_type();
+    assert_eq!(item_type.to_string(), "String!");
+    assert!(item_type.is_non_null());
+    assert!(!item_type.is_list());
+
+    let named_type = item_type.inner_type();
+    assert_eq!(named_type.to_string(), "String");
+    assert_eq!(named_type.inner_type(), named_type);
+}
+
+#[test]
+fn it_inspects_values() {
+    let source = r#"query ($id: ID) { user(id: $id, name: "Ada", active: true, limit: 1) }"#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+    let operation = document.operations().next().unwrap();
+
+    let values = match &operation.selection_set.selections[0] {
+        Selection::Field(field) => field
+            .arguments
+            .iter()
+            .map(|argument| argument.value.clone())
+            .collect::<Vec<_>>(),
+        _ => panic!("Expected Field"),
+    };
+
+    assert!(values[0].is_variable());
+    assert_eq!(values[0].variable_name(), Some("id"));
+    assert_eq!(values[0].as_str(), None);
+
+    assert!(!values[1].is_variable());
+    assert_eq!(values[1].variable_name(), None);
+    assert_eq!(values[1].as_str(), Some("Ada"));
+
+    assert_eq!(values[2].as_bool(), Some(true));
+    assert_eq!(values[3].as_bool(), None);
+}
+
+#[test]
+fn it_ends_node_ranges_at_their_last_token() {
+    let source = "{ user(id: 1) { name }  ...Fields }";
+
+    let document = parse_strict(source.to_string()).unwrap();
+    let operation = document.operations().next().unwrap();
+
+    let user = match &operation.selection_set.selections[0] {
+        Selection::Field(field) => field,
+        _ => panic!("Expected Field"),
+    };
+    assert_eq!(user.position.start, Position::new(0, 2, 2));
+    assert_eq!(user.position.end, Position::new(0, 22, 22));
+    assert_eq!(user.arguments[0].position.end, Position::new(0, 12, 12));
+
+    let spread = &operation.selection_set.selections[1];
+    assert_eq!(spread.position().start, Position::new(0, 24, 24));
+    assert_eq!(spread.position().end, Position::new(0, 33, 33));
+
+    assert_eq!(operation.position.end, Position::new(0, 35, 35));
+}
+
+#[test]
+fn it_spans_variables_from_the_dollar_sign_to_the_name() {
+    let source = "query ($id: ID = 1) { user(id: $id) }";
+
+    let document = parse_strict(source.to_string()).unwrap();
+    let operation = document.operations().next().unwrap();
+
+    let variable_definition = &operation.variable_definitions[0];
+    assert_eq!(
+        variable_definition.variable.position,
+        Range::new(Position::new(0, 7, 7), Position::new(0, 10, 10))
+    );
+    assert_eq!(variable_definition.position.end, Position::new(0, 18, 18));
+
+    let user = match &operation.selection_set.selections[0] {
+        Selection::Field(field) => field,
+        _ => panic!("Expected Field"),
+    };
+    assert_eq!(
+        user.arguments[0].value.position(),
+        &Range::new(Position::new(0, 31, 31), Position::new(0, 34, 34))
+    );
+}
+
+#[test]
+fn it_parses_a_standalone_const_value() {
+    let value = parse_const_value(r#"{ order: ASC, ids: [1, 2], name: "me" }"#).unwrap();
+    assert_eq!(
+        value.to_string(),
+        r#"{order: ASC, ids: [1, 2], name: "me"}"#
+    );
+
+    assert!(parse_const_value("$id").is_err());
+    assert!(parse_const_value("1 2").is_err());
+}
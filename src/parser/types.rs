@@ -1,12 +1,68 @@
-use crate::lsp::types::Range;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::lsp::types::{Diagnostic, Range};
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Document {
     pub definitions: Vec<Definition>,
     pub position: Range,
 }
 
+impl Document {
+    /// Iterates over the operation definitions in the document, in source order.
+    pub fn operations(&self) -> impl Iterator<Item = &OperationDefinition> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::OperationDefinition(operation) => Some(operation),
+                _ => None,
+            })
+    }
+
+    /// Iterates over the fragment definitions in the document, in source order.
+    pub fn fragments(&self) -> impl Iterator<Item = &FragmentDefinition> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::FragmentDefinition(fragment) => Some(fragment),
+                _ => None,
+            })
+    }
+
+    /// Finds the first operation with the given name, or the first anonymous
+    /// operation when `name` is `None`.
+    pub fn find_operation<'a>(&'a self, name: Option<&str>) -> Option<&'a OperationDefinition> {
+        self.operations()
+            .find(|operation| operation.name.as_ref().map(|name| name.value.as_str()) == name)
+    }
+
+    /// Finds the fragment with the given name.
+    pub fn find_fragment<'a>(&'a self, name: &str) -> Option<&'a FragmentDefinition> {
+        self.fragments()
+            .find(|fragment| fragment.name.value == name)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseResult {
+    pub document: Document,
+    /// Non-fatal diagnostics, such as warnings, found while parsing.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseOptions {
+    /// Keep parsing after a syntax error by skipping ahead to the next
+    /// definition, so every broken definition gets a diagnostic.
+    pub recover_errors: bool,
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Definition {
     OperationDefinition(OperationDefinition),
     FragmentDefinition(FragmentDefinition),
@@ -17,17 +73,114 @@ pub enum Definition {
     UnionTypeDefinition(UnionTypeDefinition),
     EnumTypeDefinition(EnumTypeDefinition),
     InputObjectTypeDefinition(InputObjectTypeDefinition),
+    DirectiveDefinition(DirectiveDefinition),
     SchemaExtension(SchemaExtension),
+    ScalarTypeExtension(ScalarTypeExtension),
+    ObjectTypeExtension(ObjectTypeExtension),
+    InterfaceTypeExtension(InterfaceTypeExtension),
+    UnionTypeExtension(UnionTypeExtension),
+    EnumTypeExtension(EnumTypeExtension),
+    InputObjectTypeExtension(InputObjectTypeExtension),
 }
 
+impl Definition {
+    /// The name the definition introduces or extends. Schema definitions,
+    /// schema extensions and anonymous operations have no name.
+    pub fn name(&self) -> Option<&Name> {
+        match self {
+            Definition::OperationDefinition(definition) => definition.name.as_ref(),
+            Definition::FragmentDefinition(definition) => Some(&definition.name),
+            Definition::SchemaDefinition(_) => None,
+            Definition::ScalarTypeDefinition(definition) => Some(&definition.name),
+            Definition::ObjectTypeDefinition(definition) => Some(&definition.name),
+            Definition::InterfaceTypeDefinition(definition) => Some(&definition.name),
+            Definition::UnionTypeDefinition(definition) => Some(&definition.name),
+            Definition::EnumTypeDefinition(definition) => Some(&definition.name),
+            Definition::InputObjectTypeDefinition(definition) => Some(&definition.name),
+            Definition::DirectiveDefinition(definition) => Some(&definition.name),
+            Definition::SchemaExtension(_) => None,
+            Definition::ScalarTypeExtension(definition) => Some(&definition.name),
+            Definition::ObjectTypeExtension(definition) => Some(&definition.name),
+            Definition::InterfaceTypeExtension(definition) => Some(&definition.name),
+            Definition::UnionTypeExtension(definition) => Some(&definition.name),
+            Definition::EnumTypeExtension(definition) => Some(&definition.name),
+            Definition::InputObjectTypeExtension(definition) => Some(&definition.name),
+        }
+    }
+}
+
+/// https://spec.graphql.org/October2021/#sec-Schema-Extension
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SchemaExtension {
     pub operation_types: Vec<RootOperationTypeDefinition>,
     pub directives: Vec<Directive>,
     pub position: Range,
 }
 
+/// https://spec.graphql.org/October2021/#sec-Scalar-Extensions
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScalarTypeExtension {
+    pub name: Name,
+    pub directives: Vec<Directive>,
+    pub position: Range,
+}
+
+/// https://spec.graphql.org/October2021/#sec-Object-Extensions
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectTypeExtension {
+    pub name: Name,
+    pub interfaces: Vec<NamedType>,
+    pub directives: Vec<Directive>,
+    pub fields: Vec<FieldDefinition>,
+    pub position: Range,
+}
+
+/// https://spec.graphql.org/October2021/#sec-Interface-Extensions
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterfaceTypeExtension {
+    pub name: Name,
+    pub interfaces: Vec<NamedType>,
+    pub directives: Vec<Directive>,
+    pub fields: Vec<FieldDefinition>,
+    pub position: Range,
+}
+
+/// https://spec.graphql.org/October2021/#sec-Union-Extensions
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnionTypeExtension {
+    pub name: Name,
+    pub directives: Vec<Directive>,
+    pub member_types: Vec<NamedType>,
+    pub position: Range,
+}
+
+/// https://spec.graphql.org/October2021/#sec-Enum-Extensions
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumTypeExtension {
+    pub name: Name,
+    pub directives: Vec<Directive>,
+    pub values: Vec<EnumValueDefinition>,
+    pub position: Range,
+}
+
+/// https://spec.graphql.org/October2021/#sec-Input-Object-Extensions
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputObjectTypeExtension {
+    pub name: Name,
+    pub directives: Vec<Directive>,
+    pub fields: Vec<InputValueDefinition>,
+    pub position: Range,
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputObjectTypeDefinition {
     pub description: Option<StringValue>,
     pub name: Name,
@@ -37,6 +190,7 @@ pub struct InputObjectTypeDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnumValueDefinition {
     pub description: Option<StringValue>,
     pub name: Name,
@@ -45,6 +199,7 @@ pub struct EnumValueDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnumTypeDefinition {
     pub description: Option<StringValue>,
     pub name: Name,
@@ -54,6 +209,7 @@ pub struct EnumTypeDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnionTypeDefinition {
     pub description: Option<StringValue>,
     pub name: Name,
@@ -63,6 +219,7 @@ pub struct UnionTypeDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InterfaceTypeDefinition {
     pub description: Option<StringValue>,
     pub name: Name,
@@ -73,6 +230,7 @@ pub struct InterfaceTypeDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectTypeDefinition {
     pub description: Option<StringValue>,
     pub name: Name,
@@ -83,6 +241,7 @@ pub struct ObjectTypeDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FieldDefinition {
     pub description: Option<StringValue>,
     pub name: Name,
@@ -93,6 +252,7 @@ pub struct FieldDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InputValueDefinition {
     pub description: Option<StringValue>,
     pub name: Name,
@@ -102,7 +262,17 @@ pub struct InputValueDefinition {
     pub position: Range,
 }
 
+impl InputValueDefinition {
+    /// Whether a value must be provided, which is the case for non-null types
+    /// without a default.
+    /// https://spec.graphql.org/October2021/#sec-Required-Arguments
+    pub fn is_required(&self) -> bool {
+        self.input_type.is_non_null() && self.default_value.is_none()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScalarTypeDefinition {
     pub description: Option<StringValue>,
     pub name: Name,
@@ -110,7 +280,20 @@ pub struct ScalarTypeDefinition {
     pub position: Range,
 }
 
+/// https://spec.graphql.org/October2021/#sec-Type-System.Directives
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectiveDefinition {
+    pub description: Option<StringValue>,
+    pub name: Name,
+    pub arguments: Vec<InputValueDefinition>,
+    pub repeatable: bool,
+    pub locations: Vec<DirectiveLocation>,
+    pub position: Range,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RootOperationTypeDefinition {
     pub operation_type: OperationType,
     pub named_type: NamedType,
@@ -118,6 +301,7 @@ pub struct RootOperationTypeDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SchemaDefinition {
     pub description: Option<StringValue>,
     pub operation_types: Vec<RootOperationTypeDefinition>,
@@ -143,6 +327,7 @@ pub struct SchemaDefinition {
 // }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OperationDefinition {
     pub name: Option<Name>,
     pub operation: OperationType,
@@ -154,6 +339,7 @@ pub struct OperationDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FragmentDefinition {
     pub name: Name,
     pub type_condition: NamedType,
@@ -163,6 +349,7 @@ pub struct FragmentDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OperationType {
     Query,
     Mutation,
@@ -180,23 +367,43 @@ impl OperationType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Name {
     pub value: String,
     pub position: Range,
 }
 
+// Names are compared and hashed by value alone so they can be used as keys in
+// symbol tables, wherever in the source they were written.
+impl PartialEq for Name {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for Name {}
+
+impl Hash for Name {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Variable {
     pub name: Name,
     pub position: Range,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VariableDefinition {
     pub variable: Variable,
     pub variable_type: Type,
     pub default_value: Option<Value>,
+    pub directives: Vec<Directive>,
     pub position: Range,
 }
 
@@ -204,31 +411,125 @@ pub struct VariableDefinition {
 // https://spec.graphql.org/October2021/#sec-Type-References
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Type {
     NamedType(NamedType),
     ListType(ListType),
     NonNullType(NonNullType),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NamedType {
     pub name: Name,
     pub position: Range,
 }
 
+impl PartialEq for NamedType {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for NamedType {}
+
+impl Hash for NamedType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListType {
     pub wrapped_type: Box<Type>,
     pub position: Range,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NonNullType {
     pub wrapped_type: Box<Type>,
     pub position: Range,
 }
 
+impl Type {
+    /// The named type at the core of the type, with any list and non-null
+    /// wrappers removed.
+    pub fn named_type(&self) -> &NamedType {
+        match self {
+            Type::NamedType(named_type) => named_type,
+            Type::ListType(list_type) => list_type.wrapped_type.named_type(),
+            Type::NonNullType(non_null_type) => non_null_type.wrapped_type.named_type(),
+        }
+    }
+
+    /// The name of the named type at the core of the type, with any list and
+    /// non-null wrappers removed.
+    pub fn named_type_name(&self) -> &str {
+        &self.named_type().name.value
+    }
+
+    pub fn is_non_null(&self) -> bool {
+        matches!(self, Type::NonNullType(_))
+    }
+
+    /// Whether the type is a list, ignoring whether the list itself is
+    /// non-null.
+    pub fn is_list(&self) -> bool {
+        matches!(self.unwrap_non_null(), Type::ListType(_))
+    }
+
+    /// The type without its non-null wrapper, or the type itself when it is
+    /// nullable.
+    pub fn unwrap_non_null(&self) -> &Type {
+        match self {
+            Type::NonNullType(non_null_type) => &non_null_type.wrapped_type,
+            _ => self,
+        }
+    }
+
+    /// The type with one list or non-null wrapper removed. Named types have
+    /// nothing to unwrap and return themselves.
+    pub fn inner_type(&self) -> &Type {
+        match self {
+            Type::NamedType(_) => self,
+            Type::ListType(list_type) => &list_type.wrapped_type,
+            Type::NonNullType(non_null_type) => &non_null_type.wrapped_type,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::NamedType(named_type) => named_type.fmt(f),
+            Type::ListType(list_type) => list_type.fmt(f),
+            Type::NonNullType(non_null_type) => non_null_type.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for NamedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name.value)
+    }
+}
+
+impl fmt::Display for ListType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", self.wrapped_type)
+    }
+}
+
+impl fmt::Display for NonNullType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}!", self.wrapped_type)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
     Variable(Variable),
     IntValue(IntValue),
@@ -241,19 +542,106 @@ pub enum Value {
     ObjectValue(ObjectValue),
 }
 
+impl Value {
+    pub fn is_variable(&self) -> bool {
+        matches!(self, Value::Variable(_))
+    }
+
+    pub fn variable_name(&self) -> Option<&str> {
+        match self {
+            Value::Variable(variable) => Some(&variable.name.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::StringValue(string_value) => Some(&string_value.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::BooleanValue(boolean_value) => Some(boolean_value.value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Variable(variable) => write!(f, "${}", variable.name.value),
+            Value::IntValue(int_value) => write!(f, "{}", int_value.value),
+            // `Debug` keeps the fraction on whole numbers, so `1.0` does not
+            // print as the int `1`
+            Value::FloatValue(float_value) => write!(f, "{:?}", float_value.value),
+            Value::StringValue(string_value) if string_value.block => {
+                write!(
+                    f,
+                    "\"\"\"{}\"\"\"",
+                    string_value.value.replace("\"\"\"", "\\\"\"\"")
+                )
+            }
+            Value::StringValue(string_value) => {
+                write!(f, "\"")?;
+                for c in string_value.value.chars() {
+                    match c {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\r' => write!(f, "\\r")?,
+                        '\t' => write!(f, "\\t")?,
+                        c if c.is_control() => write!(f, "\\u{:04X}", c as u32)?,
+                        c => write!(f, "{}", c)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Value::BooleanValue(boolean_value) => write!(f, "{}", boolean_value.value),
+            Value::NullValue(_) => write!(f, "null"),
+            Value::EnumValue(enum_value) => write!(f, "{}", enum_value.value),
+            Value::ListValue(list_value) => {
+                write!(f, "[")?;
+                for (i, value) in list_value.values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Value::ObjectValue(object_value) => {
+                write!(f, "{{")?;
+                for (i, field) in object_value.fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", field.name.value, field.value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IntValue {
-    pub value: i32,
+    pub value: i64,
     pub position: Range,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FloatValue {
-    pub value: f32,
+    pub value: f64,
     pub position: Range,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StringValue {
     pub value: String,
     pub block: bool,
@@ -261,35 +649,41 @@ pub struct StringValue {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BooleanValue {
     pub value: bool,
     pub position: Range,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NullValue {
     pub position: Range,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EnumValue {
     pub value: String,
     pub position: Range,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListValue {
     pub values: Vec<Value>,
     pub position: Range,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectValue {
     pub fields: Vec<ObjectField>,
     pub position: Range,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ObjectField {
     pub name: Name,
     pub value: Value,
@@ -297,6 +691,7 @@ pub struct ObjectField {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Selection {
     Field(Field),
     FragmentSpread(FragmentSpread),
@@ -304,6 +699,7 @@ pub enum Selection {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Field {
     pub alias: Option<Name>,
     pub name: Name,
@@ -313,7 +709,19 @@ pub struct Field {
     pub position: Range,
 }
 
+impl Field {
+    /// The key the field is returned under in the response, which is its
+    /// alias if it has one.
+    pub fn response_name(&self) -> &str {
+        self.alias
+            .as_ref()
+            .map(|alias| alias.value.as_str())
+            .unwrap_or(self.name.value.as_str())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FragmentSpread {
     pub name: Name,
     pub directives: Vec<Directive>,
@@ -321,6 +729,7 @@ pub struct FragmentSpread {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InlineFragment {
     pub type_condition: Option<NamedType>,
     pub directives: Vec<Directive>,
@@ -329,12 +738,14 @@ pub struct InlineFragment {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SelectionSet {
     pub selections: Vec<Selection>,
     pub position: Range,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Directive {
     pub name: Name,
     pub position: Range,
@@ -342,6 +753,7 @@ pub struct Directive {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Argument {
     pub name: Name,
     pub value: Value,
@@ -349,6 +761,7 @@ pub struct Argument {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExecutableDirectiveLocation {
     Query,
     Mutation,
@@ -374,9 +787,23 @@ impl ExecutableDirectiveLocation {
             _ => None,
         }
     }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutableDirectiveLocation::Query => "QUERY",
+            ExecutableDirectiveLocation::Mutation => "MUTATION",
+            ExecutableDirectiveLocation::Subscription => "SUBSCRIPTION",
+            ExecutableDirectiveLocation::Field => "FIELD",
+            ExecutableDirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
+            ExecutableDirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
+            ExecutableDirectiveLocation::InlineFragment => "INLINE_FRAGMENT",
+            ExecutableDirectiveLocation::VariableDefinition => "VARIABLE_DEFINITION",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TypeSystemDirectiveLocation {
     Schema,
     Scalar,
@@ -408,44 +835,444 @@ impl TypeSystemDirectiveLocation {
             _ => None,
         }
     }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TypeSystemDirectiveLocation::Schema => "SCHEMA",
+            TypeSystemDirectiveLocation::Scalar => "SCALAR",
+            TypeSystemDirectiveLocation::Object => "OBJECT",
+            TypeSystemDirectiveLocation::FieldDefinition => "FIELD_DEFINITION",
+            TypeSystemDirectiveLocation::ArgumentDefinition => "ARGUMENT_DEFINITION",
+            TypeSystemDirectiveLocation::Interface => "INTERFACE",
+            TypeSystemDirectiveLocation::Union => "UNION",
+            TypeSystemDirectiveLocation::Enum => "ENUM",
+            TypeSystemDirectiveLocation::EnumValue => "ENUM_VALUE",
+            TypeSystemDirectiveLocation::InputObject => "INPUT_OBJECT",
+            TypeSystemDirectiveLocation::InputFieldDefinition => "INPUT_FIELD_DEFINITION",
+        }
+    }
 }
 
-// maybe this should be a trait?
-pub enum Node {
-    Document(Document),
-    OperationDefinition(OperationDefinition),
-    FragmentDefinition(FragmentDefinition),
-    SchemaDefinition(SchemaDefinition),
-    ScalarTypeDefinition(ScalarTypeDefinition),
-    ObjectTypeDefinition(ObjectTypeDefinition),
-    InterfaceTypeDefinition(InterfaceTypeDefinition),
-    UnionTypeDefinition(UnionTypeDefinition),
-    EnumTypeDefinition(EnumTypeDefinition),
-    InputObjectTypeDefinition(InputObjectTypeDefinition),
-    SchemaExtension(SchemaExtension),
-    RootOperationTypeDefinition(RootOperationTypeDefinition),
-    InputValueDefinition(InputValueDefinition),
-    EnumValueDefinition(EnumValueDefinition),
-    FieldDefinition(FieldDefinition),
-    VariableDefinition(VariableDefinition),
-    NamedType(NamedType),
-    ListType(ListType),
-    NonNullType(NonNullType),
-    Variable(Variable),
-    IntValue(IntValue),
-    FloatValue(FloatValue),
-    StringValue(StringValue),
-    BooleanValue(BooleanValue),
-    NullValue(NullValue),
-    EnumValue(EnumValue),
-    ListValue(ListValue),
-    ObjectValue(ObjectValue),
-    ObjectField(ObjectField),
-    SelectionSet(SelectionSet),
-    Selection(Selection),
-    Field(Field),
-    FragmentSpread(FragmentSpread),
-    InlineFragment(InlineFragment),
-    Directive(Directive),
-    Argument(Argument),
+/// https://spec.graphql.org/October2021/#DirectiveLocation
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DirectiveLocation {
+    Executable(ExecutableDirectiveLocation),
+    TypeSystem(TypeSystemDirectiveLocation),
 }
+
+impl DirectiveLocation {
+    pub fn parse(value: &str) -> Option<DirectiveLocation> {
+        if let Some(location) = ExecutableDirectiveLocation::parse(value) {
+            return Some(DirectiveLocation::Executable(location));
+        }
+
+        TypeSystemDirectiveLocation::parse(value).map(DirectiveLocation::TypeSystem)
+    }
+
+    /// The name of the location as written in a directive definition.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DirectiveLocation::Executable(location) => location.as_str(),
+            DirectiveLocation::TypeSystem(location) => location.as_str(),
+        }
+    }
+}
+
+/// Any node of the AST that maps back to a range of the source.
+pub trait Node {
+    fn position(&self) -> &Range;
+}
+
+macro_rules! impl_node {
+    ($($node:ty),* $(,)?) => {
+        $(
+            impl Node for $node {
+                fn position(&self) -> &Range {
+                    &self.position
+                }
+            }
+        )*
+    };
+}
+
+impl_node!(
+    Document,
+    SchemaExtension,
+    ScalarTypeExtension,
+    ObjectTypeExtension,
+    InterfaceTypeExtension,
+    UnionTypeExtension,
+    EnumTypeExtension,
+    InputObjectTypeExtension,
+    InputObjectTypeDefinition,
+    EnumValueDefinition,
+    EnumTypeDefinition,
+    UnionTypeDefinition,
+    InterfaceTypeDefinition,
+    ObjectTypeDefinition,
+    FieldDefinition,
+    InputValueDefinition,
+    ScalarTypeDefinition,
+    DirectiveDefinition,
+    RootOperationTypeDefinition,
+    SchemaDefinition,
+    OperationDefinition,
+    FragmentDefinition,
+    Name,
+    Variable,
+    VariableDefinition,
+    NamedType,
+    ListType,
+    NonNullType,
+    IntValue,
+    FloatValue,
+    StringValue,
+    BooleanValue,
+    NullValue,
+    EnumValue,
+    ListValue,
+    ObjectValue,
+    ObjectField,
+    Field,
+    FragmentSpread,
+    InlineFragment,
+    SelectionSet,
+    Directive,
+    Argument,
+);
+
+impl Node for Definition {
+    fn position(&self) -> &Range {
+        match self {
+            Definition::OperationDefinition(node) => node.position(),
+            Definition::FragmentDefinition(node) => node.position(),
+            Definition::SchemaDefinition(node) => node.position(),
+            Definition::ScalarTypeDefinition(node) => node.position(),
+            Definition::ObjectTypeDefinition(node) => node.position(),
+            Definition::InterfaceTypeDefinition(node) => node.position(),
+            Definition::UnionTypeDefinition(node) => node.position(),
+            Definition::EnumTypeDefinition(node) => node.position(),
+            Definition::InputObjectTypeDefinition(node) => node.position(),
+            Definition::DirectiveDefinition(node) => node.position(),
+            Definition::SchemaExtension(node) => node.position(),
+            Definition::ScalarTypeExtension(node) => node.position(),
+            Definition::ObjectTypeExtension(node) => node.position(),
+            Definition::InterfaceTypeExtension(node) => node.position(),
+            Definition::UnionTypeExtension(node) => node.position(),
+            Definition::EnumTypeExtension(node) => node.position(),
+            Definition::InputObjectTypeExtension(node) => node.position(),
+        }
+    }
+}
+
+impl Node for Type {
+    fn position(&self) -> &Range {
+        match self {
+            Type::NamedType(node) => node.position(),
+            Type::ListType(node) => node.position(),
+            Type::NonNullType(node) => node.position(),
+        }
+    }
+}
+
+impl Node for Value {
+    fn position(&self) -> &Range {
+        match self {
+            Value::Variable(node) => node.position(),
+            Value::IntValue(node) => node.position(),
+            Value::FloatValue(node) => node.position(),
+            Value::StringValue(node) => node.position(),
+            Value::BooleanValue(node) => node.position(),
+            Value::NullValue(node) => node.position(),
+            Value::EnumValue(node) => node.position(),
+            Value::ListValue(node) => node.position(),
+            Value::ObjectValue(node) => node.position(),
+        }
+    }
+}
+
+impl Node for Selection {
+    fn position(&self) -> &Range {
+        match self {
+            Selection::Field(node) => node.position(),
+            Selection::FragmentSpread(node) => node.position(),
+            Selection::InlineFragment(node) => node.position(),
+        }
+    }
+}
+
+/// Structural equality that ignores where nodes appear in the source, so
+/// documents parsed from differently formatted sources can be compared.
+pub trait AstEq {
+    fn ast_eq(&self, other: &Self) -> bool;
+}
+
+impl<T: AstEq> AstEq for Option<T> {
+    fn ast_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(a), Some(b)) => a.ast_eq(b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: AstEq> AstEq for Vec<T> {
+    fn ast_eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.ast_eq(b))
+    }
+}
+
+impl<T: AstEq> AstEq for Box<T> {
+    fn ast_eq(&self, other: &Self) -> bool {
+        self.as_ref().ast_eq(other.as_ref())
+    }
+}
+
+macro_rules! impl_ast_eq_by_value {
+    ($($node:ty),* $(,)?) => {
+        $(
+            impl AstEq for $node {
+                fn ast_eq(&self, other: &Self) -> bool {
+                    self == other
+                }
+            }
+        )*
+    };
+}
+
+impl_ast_eq_by_value!(String, bool, i64, f64, OperationType, DirectiveLocation);
+
+macro_rules! impl_ast_eq_for_structs {
+    ($($node:ident { $($field:ident),* }),* $(,)?) => {
+        $(
+            impl AstEq for $node {
+                fn ast_eq(&self, other: &Self) -> bool {
+                    true $(&& self.$field.ast_eq(&other.$field))*
+                }
+            }
+        )*
+    };
+}
+
+impl_ast_eq_for_structs!(
+    Document { definitions },
+    SchemaExtension {
+        operation_types,
+        directives
+    },
+    ScalarTypeExtension { name, directives },
+    ObjectTypeExtension {
+        name,
+        interfaces,
+        directives,
+        fields
+    },
+    InterfaceTypeExtension {
+        name,
+        interfaces,
+        directives,
+        fields
+    },
+    UnionTypeExtension {
+        name,
+        directives,
+        member_types
+    },
+    EnumTypeExtension {
+        name,
+        directives,
+        values
+    },
+    InputObjectTypeExtension {
+        name,
+        directives,
+        fields
+    },
+    InputObjectTypeDefinition {
+        description,
+        name,
+        directives,
+        fields
+    },
+    EnumValueDefinition {
+        description,
+        name,
+        directives
+    },
+    EnumTypeDefinition {
+        description,
+        name,
+        directives,
+        values
+    },
+    UnionTypeDefinition {
+        description,
+        name,
+        directives,
+        member_types
+    },
+    InterfaceTypeDefinition {
+        description,
+        name,
+        interfaces,
+        directives,
+        fields
+    },
+    ObjectTypeDefinition {
+        description,
+        name,
+        interfaces,
+        directives,
+        fields
+    },
+    FieldDefinition {
+        description,
+        name,
+        arguments,
+        field_type,
+        directives
+    },
+    InputValueDefinition {
+        description,
+        name,
+        input_type,
+        default_value,
+        directives
+    },
+    ScalarTypeDefinition {
+        description,
+        name,
+        directives
+    },
+    DirectiveDefinition {
+        description,
+        name,
+        arguments,
+        repeatable,
+        locations
+    },
+    RootOperationTypeDefinition {
+        operation_type,
+        named_type
+    },
+    SchemaDefinition {
+        description,
+        operation_types,
+        directives
+    },
+    OperationDefinition {
+        name,
+        operation,
+        variable_definitions,
+        selection_set,
+        directives,
+        anonymous
+    },
+    FragmentDefinition {
+        name,
+        type_condition,
+        directives,
+        selection_set
+    },
+    Name { value },
+    Variable { name },
+    VariableDefinition {
+        variable,
+        variable_type,
+        default_value,
+        directives
+    },
+    NamedType { name },
+    ListType { wrapped_type },
+    NonNullType { wrapped_type },
+    IntValue { value },
+    FloatValue { value },
+    StringValue { value, block },
+    BooleanValue { value },
+    EnumValue { value },
+    ListValue { values },
+    ObjectValue { fields },
+    ObjectField { name, value },
+    Field {
+        alias,
+        name,
+        arguments,
+        directives,
+        selection_set
+    },
+    FragmentSpread { name, directives },
+    InlineFragment {
+        type_condition,
+        directives,
+        selection_set
+    },
+    SelectionSet { selections },
+    Directive { name, arguments },
+    Argument { name, value },
+);
+
+impl AstEq for NullValue {
+    fn ast_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+macro_rules! impl_ast_eq_for_enums {
+    ($($node:ident { $($variant:ident),* }),* $(,)?) => {
+        $(
+            impl AstEq for $node {
+                fn ast_eq(&self, other: &Self) -> bool {
+                    match (self, other) {
+                        $(($node::$variant(a), $node::$variant(b)) => a.ast_eq(b),)*
+                        _ => false,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_ast_eq_for_enums!(
+    Definition {
+        OperationDefinition,
+        FragmentDefinition,
+        SchemaDefinition,
+        ScalarTypeDefinition,
+        ObjectTypeDefinition,
+        InterfaceTypeDefinition,
+        UnionTypeDefinition,
+        EnumTypeDefinition,
+        InputObjectTypeDefinition,
+        DirectiveDefinition,
+        SchemaExtension,
+        ScalarTypeExtension,
+        ObjectTypeExtension,
+        InterfaceTypeExtension,
+        UnionTypeExtension,
+        EnumTypeExtension,
+        InputObjectTypeExtension
+    },
+    Type {
+        NamedType,
+        ListType,
+        NonNullType
+    },
+    Value {
+        Variable,
+        IntValue,
+        FloatValue,
+        StringValue,
+        BooleanValue,
+        NullValue,
+        EnumValue,
+        ListValue,
+        ObjectValue
+    },
+    Selection {
+        Field,
+        FragmentSpread,
+        InlineFragment
+    },
+);
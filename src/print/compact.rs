@@ -0,0 +1,201 @@
+use crate::parser::types::{
+    Argument, Definition, Directive, Document, Field, FragmentDefinition, FragmentSpread,
+    InlineFragment, OperationDefinition, OperationType, Selection, SelectionSet, Value,
+    VariableDefinition,
+};
+
+/// Prints the executable definitions of the document with no more whitespace
+/// than is needed to keep tokens apart, for sending queries where size
+/// matters. Type system definitions are not printed. Block strings stay block
+/// strings, so any line breaks in them are kept.
+pub fn print_compact(document: &Document) -> String {
+    let mut printer = CompactPrinter::default();
+    printer.print_document(document);
+    printer.output
+}
+
+#[derive(Default)]
+struct CompactPrinter {
+    output: String,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+impl CompactPrinter {
+    /// Appends the token, separated from the previous one by a space only
+    /// when the two would otherwise run together, like two names, or an empty
+    /// string followed by another string reading as `"""`.
+    fn write(&mut self, token: &str) {
+        let needs_space = match (self.output.chars().last(), token.chars().next()) {
+            (Some(last), Some(first)) => {
+                (is_name_char(last) && is_name_char(first)) || (last == '"' && first == '"')
+            }
+            _ => false,
+        };
+
+        if needs_space {
+            self.output.push(' ');
+        }
+
+        self.output.push_str(token);
+    }
+
+    fn print_document(&mut self, document: &Document) {
+        for definition in &document.definitions {
+            match definition {
+                Definition::OperationDefinition(node) => self.print_operation_definition(node),
+                Definition::FragmentDefinition(node) => self.print_fragment_definition(node),
+                _ => {}
+            }
+        }
+    }
+
+    fn print_operation_definition(&mut self, node: &OperationDefinition) {
+        let is_shorthand = node.operation == OperationType::Query
+            && node.name.is_none()
+            && node.variable_definitions.is_empty()
+            && node.directives.is_empty();
+
+        if !is_shorthand {
+            self.write(match node.operation {
+                OperationType::Query => "query",
+                OperationType::Mutation => "mutation",
+                OperationType::Subscription => "subscription",
+            });
+
+            if let Some(name) = &node.name {
+                self.write(&name.value);
+            }
+        }
+
+        if !node.variable_definitions.is_empty() {
+            self.write("(");
+            for variable_definition in &node.variable_definitions {
+                self.print_variable_definition(variable_definition);
+            }
+            self.write(")");
+        }
+
+        self.print_directives(&node.directives);
+        self.print_selection_set(&node.selection_set);
+    }
+
+    fn print_variable_definition(&mut self, node: &VariableDefinition) {
+        self.write(&format!("${}", node.variable.name.value));
+        self.write(":");
+        self.write(&node.variable_type.to_string());
+
+        if let Some(default_value) = &node.default_value {
+            self.write("=");
+            self.print_value(default_value);
+        }
+
+        self.print_directives(&node.directives);
+    }
+
+    fn print_fragment_definition(&mut self, node: &FragmentDefinition) {
+        self.write("fragment");
+        self.write(&node.name.value);
+        self.write("on");
+        self.write(&node.type_condition.name.value);
+        self.print_directives(&node.directives);
+        self.print_selection_set(&node.selection_set);
+    }
+
+    fn print_selection_set(&mut self, node: &SelectionSet) {
+        self.write("{");
+
+        for selection in &node.selections {
+            match selection {
+                Selection::Field(field) => self.print_field(field),
+                Selection::FragmentSpread(fragment_spread) => {
+                    self.print_fragment_spread(fragment_spread)
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    self.print_inline_fragment(inline_fragment)
+                }
+            }
+        }
+
+        self.write("}");
+    }
+
+    fn print_field(&mut self, node: &Field) {
+        if let Some(alias) = &node.alias {
+            self.write(&alias.value);
+            self.write(":");
+        }
+
+        self.write(&node.name.value);
+        self.print_arguments(&node.arguments);
+        self.print_directives(&node.directives);
+
+        if let Some(selection_set) = &node.selection_set {
+            self.print_selection_set(selection_set);
+        }
+    }
+
+    fn print_fragment_spread(&mut self, node: &FragmentSpread) {
+        self.write("...");
+        self.write(&node.name.value);
+        self.print_directives(&node.directives);
+    }
+
+    fn print_inline_fragment(&mut self, node: &InlineFragment) {
+        self.write("...");
+
+        if let Some(type_condition) = &node.type_condition {
+            self.write("on");
+            self.write(&type_condition.name.value);
+        }
+
+        self.print_directives(&node.directives);
+        self.print_selection_set(&node.selection_set);
+    }
+
+    fn print_directives(&mut self, directives: &[Directive]) {
+        for directive in directives {
+            self.write("@");
+            self.write(&directive.name.value);
+            self.print_arguments(&directive.arguments);
+        }
+    }
+
+    fn print_arguments(&mut self, arguments: &[Argument]) {
+        if arguments.is_empty() {
+            return;
+        }
+
+        self.write("(");
+        for argument in arguments {
+            self.write(&argument.name.value);
+            self.write(":");
+            self.print_value(&argument.value);
+        }
+        self.write(")");
+    }
+
+    fn print_value(&mut self, value: &Value) {
+        match value {
+            Value::ListValue(list) => {
+                self.write("[");
+                for value in &list.values {
+                    self.print_value(value);
+                }
+                self.write("]");
+            }
+            Value::ObjectValue(object) => {
+                self.write("{");
+                for field in &object.fields {
+                    self.write(&field.name.value);
+                    self.write(":");
+                    self.print_value(&field.value);
+                }
+                self.write("}");
+            }
+            _ => self.write(&value.to_string()),
+        }
+    }
+}
@@ -1 +1,4 @@
+pub mod compact;
 pub mod pretty_print;
+
+mod tests;
@@ -1,29 +1,84 @@
 use crate::parser::types::{
-    Argument, Definition, Directive, Document, Field, Name, OperationDefinition, OperationType,
-    Selection, Value,
+    Argument, Definition, Directive, DirectiveDefinition, Document, EnumTypeDefinition,
+    EnumTypeExtension, EnumValueDefinition, Field, FieldDefinition, FragmentDefinition,
+    FragmentSpread, InlineFragment, InputObjectTypeDefinition, InputObjectTypeExtension,
+    InputValueDefinition, InterfaceTypeDefinition, InterfaceTypeExtension, Name, NamedType,
+    ObjectTypeDefinition, ObjectTypeExtension, OperationDefinition, OperationType,
+    ScalarTypeDefinition, ScalarTypeExtension, SchemaDefinition, SchemaExtension, Selection,
+    SelectionSet, StringValue, Type, UnionTypeDefinition, UnionTypeExtension, Value,
+    VariableDefinition,
 };
 
+/// Object values longer than this are split over several lines.
+const MAX_LINE_WIDTH: usize = 80;
+
+/// How the pretty printer indents nested lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Spaces,
+    Tabs,
+}
+
+#[derive(Debug, Clone)]
+pub struct PrintOptions {
+    pub indent_style: IndentStyle,
+    /// The number of spaces or tabs in each level of indentation.
+    pub indent_width: usize,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions {
+            indent_style: IndentStyle::Spaces,
+            indent_width: 2,
+        }
+    }
+}
+
+impl PrintOptions {
+    fn indentation(&self, depth: usize) -> String {
+        let unit = match self.indent_style {
+            IndentStyle::Spaces => " ",
+            IndentStyle::Tabs => "\t",
+        };
+
+        unit.repeat(depth * self.indent_width)
+    }
+}
+
 macro_rules! indent {
-    ($n:expr, $s:expr) => {{
-        let padding = " ".repeat($n * 2);
-        format!("{}{}", padding, $s)
+    ($options:expr, $n:expr, $s:expr) => {{
+        format!("{}{}", $options.indentation($n), $s)
     }};
 }
 
 pub fn print(document: &Document) -> String {
-    return document.pretty_print(0);
+    print_impl(document, &PrintOptions::default())
+}
+
+pub fn print_with_options(document: &Document, options: &PrintOptions) -> String {
+    print_impl(document, options)
+}
+
+/// Prints a type reference like `[String!]!`.
+pub fn print_type(ty: &Type) -> String {
+    ty.pretty_print(0, &PrintOptions::default())
+}
+
+fn print_impl(document: &Document, options: &PrintOptions) -> String {
+    document.pretty_print(0, options)
 }
 
 trait PrettyPrint {
-    fn pretty_print(&self, depth: usize) -> String;
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String;
 }
 
 impl PrettyPrint for Document {
-    fn pretty_print(&self, depth: usize) -> String {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
         let mut output = String::new();
 
         for definition in &self.definitions {
-            output.push_str(&definition.pretty_print(depth));
+            output.push_str(&definition.pretty_print(depth, options));
             output.push('\n');
         }
 
@@ -32,24 +87,71 @@ impl PrettyPrint for Document {
 }
 
 impl PrettyPrint for Definition {
-    fn pretty_print(&self, depth: usize) -> String {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
         match self {
             Definition::OperationDefinition(operation_definition) => {
-                operation_definition.pretty_print(depth)
+                operation_definition.pretty_print(depth, options)
+            }
+            Definition::FragmentDefinition(fragment_definition) => {
+                fragment_definition.pretty_print(depth, options)
+            }
+            Definition::SchemaDefinition(schema_definition) => {
+                schema_definition.pretty_print(depth, options)
+            }
+            Definition::ScalarTypeDefinition(scalar_type_definition) => {
+                scalar_type_definition.pretty_print(depth, options)
+            }
+            Definition::ObjectTypeDefinition(object_type_definition) => {
+                object_type_definition.pretty_print(depth, options)
+            }
+            Definition::InterfaceTypeDefinition(interface_type_definition) => {
+                interface_type_definition.pretty_print(depth, options)
+            }
+            Definition::UnionTypeDefinition(union_type_definition) => {
+                union_type_definition.pretty_print(depth, options)
+            }
+            Definition::EnumTypeDefinition(enum_type_definition) => {
+                enum_type_definition.pretty_print(depth, options)
+            }
+            Definition::InputObjectTypeDefinition(input_object_type_definition) => {
+                input_object_type_definition.pretty_print(depth, options)
+            }
+            Definition::DirectiveDefinition(directive_definition) => {
+                directive_definition.pretty_print(depth, options)
+            }
+            Definition::SchemaExtension(schema_extension) => {
+                schema_extension.pretty_print(depth, options)
+            }
+            Definition::ScalarTypeExtension(scalar_type_extension) => {
+                scalar_type_extension.pretty_print(depth, options)
+            }
+            Definition::ObjectTypeExtension(object_type_extension) => {
+                object_type_extension.pretty_print(depth, options)
+            }
+            Definition::InterfaceTypeExtension(interface_type_extension) => {
+                interface_type_extension.pretty_print(depth, options)
+            }
+            Definition::UnionTypeExtension(union_type_extension) => {
+                union_type_extension.pretty_print(depth, options)
+            }
+            Definition::EnumTypeExtension(enum_type_extension) => {
+                enum_type_extension.pretty_print(depth, options)
+            }
+            Definition::InputObjectTypeExtension(input_object_type_extension) => {
+                input_object_type_extension.pretty_print(depth, options)
             }
-            _ => "".to_string(),
         }
     }
 }
 
 impl PrettyPrint for Name {
-    fn pretty_print(&self, _depth: usize) -> String {
+    fn pretty_print(&self, _depth: usize, _options: &PrintOptions) -> String {
         self.value.to_string()
     }
 }
 
 impl PrettyPrint for OperationDefinition {
-    fn pretty_print(&self, depth: usize) -> String {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
         let mut output: Vec<String> = vec![];
 
         match &self.operation {
@@ -58,62 +160,174 @@ impl PrettyPrint for OperationDefinition {
             OperationType::Subscription => output.push(String::from("subscription")),
         }
 
-        // TODO - args, etc...
-
         if let Some(name) = &self.name {
-            output.push(name.pretty_print(depth));
+            output.push(name.pretty_print(depth, options));
         }
 
-        if &self.directives.len() > &0 {
+        if !self.variable_definitions.is_empty() {
+            let variable_definitions = self
+                .variable_definitions
+                .iter()
+                .map(|variable_definition| variable_definition.pretty_print(depth, options))
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            let variable_definitions = format!("({})", variable_definitions);
+            // the variables follow the name directly
+            match output.last_mut() {
+                Some(name) if self.name.is_some() => name.push_str(&variable_definitions),
+                _ => output.push(variable_definitions),
+            }
+        }
+
+        if !self.directives.is_empty() {
             for directive in &self.directives {
-                output.push(directive.pretty_print(depth));
+                output.push(directive.pretty_print(depth, options));
             }
         }
 
+        output.push(self.selection_set.pretty_print(depth, options));
+
+        output.join(" ")
+    }
+}
+
+impl PrettyPrint for VariableDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let mut output = format!(
+            "${}: {}",
+            self.variable.name.pretty_print(depth, options),
+            self.variable_type.pretty_print(depth, options)
+        );
+
+        if let Some(default_value) = &self.default_value {
+            output.push_str(" = ");
+            output.push_str(&default_value.pretty_print(depth, options));
+        }
+
+        output.push_str(&print_directives(&self.directives, depth, options));
+
+        output
+    }
+}
+
+impl PrettyPrint for Type {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        match self {
+            Type::NamedType(named_type) => named_type.name.pretty_print(depth, options),
+            Type::ListType(list_type) => {
+                format!("[{}]", list_type.wrapped_type.pretty_print(depth, options))
+            }
+            Type::NonNullType(non_null_type) => {
+                format!(
+                    "{}!",
+                    non_null_type.wrapped_type.pretty_print(depth, options)
+                )
+            }
+        }
+    }
+}
+
+impl PrettyPrint for FragmentDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let mut output: Vec<String> = vec![
+            String::from("fragment"),
+            self.name.pretty_print(depth, options),
+            String::from("on"),
+            self.type_condition.name.pretty_print(depth, options),
+        ];
+
+        for directive in &self.directives {
+            output.push(directive.pretty_print(depth, options));
+        }
+
+        output.push(self.selection_set.pretty_print(depth, options));
+
+        indent!(options, depth, output.join(" "))
+    }
+}
+
+impl PrettyPrint for SelectionSet {
+    /// Prints the selections one per line, indented one level deeper than
+    /// the closing brace.
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
         let mut selections = vec![];
 
         selections.push(String::from("{"));
 
-        for selection in &self.selection_set.selections {
-            selections.push(selection.pretty_print(depth + 1));
+        for selection in &self.selections {
+            selections.push(selection.pretty_print(depth + 1, options));
         }
 
-        selections.push(String::from("}"));
-        output.push(selections.join("\n"));
-
-        output.join(" ")
+        selections.push(indent!(options, depth, String::from("}")));
+        selections.join("\n")
     }
 }
 
 impl PrettyPrint for Selection {
-    fn pretty_print(&self, depth: usize) -> String {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
         match self {
-            Selection::Field(field) => field.pretty_print(depth),
-            _ => "TODO".to_string(),
-            // Selection::FragmentSpread(fragment_spread) => fragment_spread.pretty_print(depth),
-            // Selection::InlineFragment(inline_fragment) => inline_fragment.pretty_print(depth),
+            Selection::Field(field) => field.pretty_print(depth, options),
+            Selection::FragmentSpread(fragment_spread) => {
+                fragment_spread.pretty_print(depth, options)
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                inline_fragment.pretty_print(depth, options)
+            }
         }
     }
 }
 
+impl PrettyPrint for FragmentSpread {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let mut output: Vec<String> =
+            vec![format!("...{}", self.name.pretty_print(depth, options))];
+
+        for directive in &self.directives {
+            output.push(directive.pretty_print(depth, options));
+        }
+
+        indent!(options, depth, output.join(" "))
+    }
+}
+
+impl PrettyPrint for InlineFragment {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let mut output: Vec<String> = vec![String::from("...")];
+
+        if let Some(type_condition) = &self.type_condition {
+            output.push(String::from("on"));
+            output.push(type_condition.name.pretty_print(depth, options));
+        }
+
+        for directive in &self.directives {
+            output.push(directive.pretty_print(depth, options));
+        }
+
+        output.push(self.selection_set.pretty_print(depth, options));
+
+        indent!(options, depth, output.join(" "))
+    }
+}
+
 impl PrettyPrint for Field {
-    fn pretty_print(&self, depth: usize) -> String {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
         let mut output: Vec<String> = vec![];
 
         if let Some(alias) = &self.alias {
-            output.push(alias.pretty_print(depth));
+            output.push(alias.pretty_print(depth, options));
             output.push(String::from(": "));
         }
 
-        output.push(self.name.pretty_print(depth));
+        output.push(self.name.pretty_print(depth, options));
 
-        if &self.arguments.len() > &0 {
+        if !self.arguments.is_empty() {
             output.push(String::from("("));
 
             let arguments = &self
                 .arguments
                 .iter()
-                .map(|argument| argument.pretty_print(depth))
+                .map(|argument| argument.pretty_print(depth, options))
                 .collect::<Vec<String>>()
                 .join(", ");
 
@@ -121,51 +335,36 @@ impl PrettyPrint for Field {
             output.push(String::from(")"));
         }
 
-        if &self.directives.len() > &0 {
-            output.push(String::from(" "));
-            let directives = &self
-                .directives
-                .iter()
-                .map(|directive| directive.pretty_print(depth))
-                .collect::<Vec<String>>()
-                .join(" ");
-            output.push(directives.to_string());
+        for directive in &self.directives {
             output.push(String::from(" "));
+            output.push(directive.pretty_print(depth, options));
         }
 
         if let Some(selection_set) = &self.selection_set {
-            if &selection_set.selections.len() > &0 {
-                let mut selections = vec![];
-
-                selections.push(String::from("{"));
-
-                for selection in &selection_set.selections {
-                    selections.push(selection.pretty_print(depth + 1));
-                }
-
-                selections.push(indent!(depth, String::from("}")));
-                output.push(selections.join("\n"));
+            if !selection_set.selections.is_empty() {
+                output.push(String::from(" "));
+                output.push(selection_set.pretty_print(depth, options));
             }
         }
 
-        indent!(depth, output.join(""))
+        indent!(options, depth, output.join(""))
     }
 }
 
 impl PrettyPrint for Directive {
-    fn pretty_print(&self, depth: usize) -> String {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
         let mut output: Vec<String> = vec![];
 
         output.push(String::from("@"));
-        output.push(self.name.pretty_print(depth));
+        output.push(self.name.pretty_print(depth, options));
 
-        if &self.arguments.len() > &0 {
+        if !self.arguments.is_empty() {
             output.push(String::from("("));
 
             let arguments = &self
                 .arguments
                 .iter()
-                .map(|argument| argument.pretty_print(depth))
+                .map(|argument| argument.pretty_print(depth, options))
                 .collect::<Vec<String>>()
                 .join(", ");
 
@@ -178,19 +377,21 @@ impl PrettyPrint for Directive {
 }
 
 impl PrettyPrint for Argument {
-    fn pretty_print(&self, depth: usize) -> String {
-        let name = self.name.pretty_print(depth);
-        let value = self.value.pretty_print(depth);
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let name = self.name.pretty_print(depth, options);
+        let value = self.value.pretty_print(depth, options);
         format!("{}: {}", name, value)
     }
 }
 
 impl PrettyPrint for Value {
-    fn pretty_print(&self, depth: usize) -> String {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
         match self {
             Value::IntValue(node) => node.value.to_string(),
-            Value::FloatValue(node) => node.value.to_string(),
-            Value::StringValue(node) => format!("\"{}\"", node.value),
+            // `Debug` keeps the fraction on whole numbers, so `1.0` does not
+            // print as the int `1`
+            Value::FloatValue(node) => format!("{:?}", node.value),
+            Value::StringValue(node) => node.pretty_print(depth, options),
             Value::BooleanValue(node) => node.value.to_string(),
             Value::NullValue(_) => "null".to_string(),
             Value::EnumValue(node) => node.value.to_string(),
@@ -198,13 +399,562 @@ impl PrettyPrint for Value {
                 let values = node
                     .values
                     .iter()
-                    .map(|value| value.pretty_print(depth))
+                    .map(|value| value.pretty_print(depth, options))
                     .collect::<Vec<String>>();
                 format!("[{}]", values.join(", "))
             }
-            Value::Variable(node) => node.name.pretty_print(depth),
-            // TODO - Object Value
-            _ => "".to_string(),
+            Value::Variable(node) => format!("${}", node.name.pretty_print(depth, options)),
+            Value::ObjectValue(node) => {
+                let fields = node
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        format!(
+                            "{}: {}",
+                            field.name.pretty_print(depth, options),
+                            field.value.pretty_print(depth + 1, options)
+                        )
+                    })
+                    .collect::<Vec<String>>();
+
+                if fields.is_empty() {
+                    return String::from("{}");
+                }
+
+                let inline = format!("{{{}}}", fields.join(", "));
+                if options.indentation(depth).len() + inline.len() <= MAX_LINE_WIDTH {
+                    return inline;
+                }
+
+                let mut lines = vec![String::from("{")];
+                for field in fields {
+                    lines.push(indent!(options, depth + 1, field));
+                }
+                lines.push(indent!(options, depth, String::from("}")));
+                lines.join("\n")
+            }
+        }
+    }
+}
+
+impl PrettyPrint for StringValue {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        if self.block {
+            return print_block_string(&self.value, depth, options);
         }
+
+        let mut output = String::from("\"");
+        for c in self.value.chars() {
+            match c {
+                '"' => output.push_str("\\\""),
+                '\\' => output.push_str("\\\\"),
+                '\n' => output.push_str("\\n"),
+                '\r' => output.push_str("\\r"),
+                '\t' => output.push_str("\\t"),
+                c if c.is_control() => output.push_str(&format!("\\u{:04X}", c as u32)),
+                c => output.push(c),
+            }
+        }
+        output.push('"');
+
+        output
+    }
+}
+
+/// Prints the value as a block string with its content on the lines between
+/// the quotes, indented one level deeper than the closing quotes.
+/// https://spec.graphql.org/October2021/#BlockString
+fn print_block_string(value: &str, depth: usize, options: &PrintOptions) -> String {
+    let value = value.replace("\"\"\"", "\\\"\"\"");
+    let lines = value.lines().collect::<Vec<&str>>();
+
+    let first = lines.iter().position(|line| !line.trim().is_empty());
+    let last = lines.iter().rposition(|line| !line.trim().is_empty());
+
+    let mut output = vec![String::from("\"\"\"")];
+
+    if let (Some(first), Some(last)) = (first, last) {
+        for line in &lines[first..=last] {
+            if line.trim().is_empty() {
+                output.push(String::new());
+            } else {
+                output.push(indent!(options, depth + 1, line));
+            }
+        }
+    }
+
+    output.push(indent!(options, depth, "\"\"\""));
+    output.join("\n")
+}
+
+/// The description on its own line above the definition it describes, or
+/// nothing when there is none.
+fn print_description(
+    description: &Option<StringValue>,
+    depth: usize,
+    options: &PrintOptions,
+) -> String {
+    match description {
+        Some(description) => format!(
+            "{}\n",
+            indent!(options, depth, description.pretty_print(depth, options))
+        ),
+        None => String::new(),
+    }
+}
+
+/// Each directive preceded by a space, so the result can follow a name or
+/// type directly.
+fn print_directives(directives: &[Directive], depth: usize, options: &PrintOptions) -> String {
+    directives
+        .iter()
+        .map(|directive| format!(" {}", directive.pretty_print(depth, options)))
+        .collect()
+}
+
+/// The lines of a definition's body wrapped in braces, or nothing when the
+/// body is empty.
+fn print_block(lines: Vec<String>, depth: usize, options: &PrintOptions) -> String {
+    if lines.is_empty() {
+        return String::new();
+    }
+
+    format!(
+        " {{\n{}\n{}",
+        lines.join("\n"),
+        indent!(options, depth, String::from("}"))
+    )
+}
+
+fn print_implements(interfaces: &[NamedType], depth: usize, options: &PrintOptions) -> String {
+    if interfaces.is_empty() {
+        return String::new();
+    }
+
+    let interfaces = interfaces
+        .iter()
+        .map(|interface| interface.name.pretty_print(depth, options))
+        .collect::<Vec<String>>();
+
+    format!(" implements {}", interfaces.join(" & "))
+}
+
+/// Arguments are printed on one line, unless any of them has a description
+/// in which case each goes on its own line.
+fn print_argument_definitions(
+    arguments: &[InputValueDefinition],
+    depth: usize,
+    options: &PrintOptions,
+) -> String {
+    if arguments.is_empty() {
+        return String::new();
+    }
+
+    if arguments
+        .iter()
+        .all(|argument| argument.description.is_none())
+    {
+        let arguments = arguments
+            .iter()
+            .map(|argument| argument.pretty_print(0, options))
+            .collect::<Vec<String>>();
+
+        return format!("({})", arguments.join(", "));
+    }
+
+    let arguments = arguments
+        .iter()
+        .map(|argument| argument.pretty_print(depth + 1, options))
+        .collect::<Vec<String>>();
+
+    format!(
+        "(\n{}\n{})",
+        arguments.join("\n"),
+        indent!(options, depth, "")
+    )
+}
+
+impl PrettyPrint for SchemaDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let operation_types = self
+            .operation_types
+            .iter()
+            .map(|operation_type| {
+                let operation = match operation_type.operation_type {
+                    OperationType::Query => "query",
+                    OperationType::Mutation => "mutation",
+                    OperationType::Subscription => "subscription",
+                };
+                indent!(
+                    options,
+                    depth + 1,
+                    format!("{}: {}", operation, operation_type.named_type.name.value)
+                )
+            })
+            .collect::<Vec<String>>();
+
+        format!(
+            "{}{}{}",
+            print_description(&self.description, depth, options),
+            indent!(
+                options,
+                depth,
+                format!(
+                    "schema{}",
+                    print_directives(&self.directives, depth, options)
+                )
+            ),
+            print_block(operation_types, depth, options)
+        )
+    }
+}
+
+impl PrettyPrint for ScalarTypeDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        format!(
+            "{}{}",
+            print_description(&self.description, depth, options),
+            indent!(
+                options,
+                depth,
+                format!(
+                    "scalar {}{}",
+                    self.name.pretty_print(depth, options),
+                    print_directives(&self.directives, depth, options)
+                )
+            )
+        )
+    }
+}
+
+impl PrettyPrint for ObjectTypeDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| field.pretty_print(depth + 1, options))
+            .collect::<Vec<String>>();
+
+        format!(
+            "{}{}{}",
+            print_description(&self.description, depth, options),
+            indent!(
+                options,
+                depth,
+                format!(
+                    "type {}{}{}",
+                    self.name.pretty_print(depth, options),
+                    print_implements(&self.interfaces, depth, options),
+                    print_directives(&self.directives, depth, options)
+                )
+            ),
+            print_block(fields, depth, options)
+        )
+    }
+}
+
+impl PrettyPrint for InterfaceTypeDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| field.pretty_print(depth + 1, options))
+            .collect::<Vec<String>>();
+
+        format!(
+            "{}{}{}",
+            print_description(&self.description, depth, options),
+            indent!(
+                options,
+                depth,
+                format!(
+                    "interface {}{}{}",
+                    self.name.pretty_print(depth, options),
+                    print_implements(&self.interfaces, depth, options),
+                    print_directives(&self.directives, depth, options)
+                )
+            ),
+            print_block(fields, depth, options)
+        )
+    }
+}
+
+impl PrettyPrint for UnionTypeDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let mut output = format!(
+            "union {}{}",
+            self.name.pretty_print(depth, options),
+            print_directives(&self.directives, depth, options)
+        );
+
+        if !self.member_types.is_empty() {
+            let member_types = self
+                .member_types
+                .iter()
+                .map(|member_type| member_type.name.pretty_print(depth, options))
+                .collect::<Vec<String>>();
+
+            output.push_str(" = ");
+            output.push_str(&member_types.join(" | "));
+        }
+
+        format!(
+            "{}{}",
+            print_description(&self.description, depth, options),
+            indent!(options, depth, output)
+        )
+    }
+}
+
+impl PrettyPrint for EnumTypeDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let values = self
+            .values
+            .iter()
+            .map(|value| value.pretty_print(depth + 1, options))
+            .collect::<Vec<String>>();
+
+        format!(
+            "{}{}{}",
+            print_description(&self.description, depth, options),
+            indent!(
+                options,
+                depth,
+                format!(
+                    "enum {}{}",
+                    self.name.pretty_print(depth, options),
+                    print_directives(&self.directives, depth, options)
+                )
+            ),
+            print_block(values, depth, options)
+        )
+    }
+}
+
+impl PrettyPrint for EnumValueDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        format!(
+            "{}{}",
+            print_description(&self.description, depth, options),
+            indent!(
+                options,
+                depth,
+                format!(
+                    "{}{}",
+                    self.name.pretty_print(depth, options),
+                    print_directives(&self.directives, depth, options)
+                )
+            )
+        )
+    }
+}
+
+impl PrettyPrint for InputObjectTypeDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| field.pretty_print(depth + 1, options))
+            .collect::<Vec<String>>();
+
+        format!(
+            "{}{}{}",
+            print_description(&self.description, depth, options),
+            indent!(
+                options,
+                depth,
+                format!(
+                    "input {}{}",
+                    self.name.pretty_print(depth, options),
+                    print_directives(&self.directives, depth, options)
+                )
+            ),
+            print_block(fields, depth, options)
+        )
+    }
+}
+
+/// Prints an extension as the definition it extends, preceded by `extend`.
+/// Extensions have no descriptions, so the definition starts on its first
+/// line.
+/// https://spec.graphql.org/October2021/#sec-Type-System-Extensions
+fn print_extension(definition: &impl PrettyPrint, depth: usize, options: &PrintOptions) -> String {
+    let definition = definition.pretty_print(depth, options);
+    let indentation = options.indentation(depth);
+
+    indent!(
+        options,
+        depth,
+        format!("extend {}", &definition[indentation.len()..])
+    )
+}
+
+impl PrettyPrint for SchemaExtension {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let definition = SchemaDefinition {
+            description: None,
+            operation_types: self.operation_types.clone(),
+            directives: self.directives.clone(),
+            position: self.position.clone(),
+        };
+
+        print_extension(&definition, depth, options)
+    }
+}
+
+impl PrettyPrint for ScalarTypeExtension {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let definition = ScalarTypeDefinition {
+            description: None,
+            name: self.name.clone(),
+            directives: self.directives.clone(),
+            position: self.position.clone(),
+        };
+
+        print_extension(&definition, depth, options)
+    }
+}
+
+impl PrettyPrint for ObjectTypeExtension {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let definition = ObjectTypeDefinition {
+            description: None,
+            name: self.name.clone(),
+            interfaces: self.interfaces.clone(),
+            directives: self.directives.clone(),
+            fields: self.fields.clone(),
+            position: self.position.clone(),
+        };
+
+        print_extension(&definition, depth, options)
+    }
+}
+
+impl PrettyPrint for InterfaceTypeExtension {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let definition = InterfaceTypeDefinition {
+            description: None,
+            name: self.name.clone(),
+            interfaces: self.interfaces.clone(),
+            directives: self.directives.clone(),
+            fields: self.fields.clone(),
+            position: self.position.clone(),
+        };
+
+        print_extension(&definition, depth, options)
+    }
+}
+
+impl PrettyPrint for UnionTypeExtension {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let definition = UnionTypeDefinition {
+            description: None,
+            name: self.name.clone(),
+            directives: self.directives.clone(),
+            member_types: self.member_types.clone(),
+            position: self.position.clone(),
+        };
+
+        print_extension(&definition, depth, options)
+    }
+}
+
+impl PrettyPrint for EnumTypeExtension {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let definition = EnumTypeDefinition {
+            description: None,
+            name: self.name.clone(),
+            directives: self.directives.clone(),
+            values: self.values.clone(),
+            position: self.position.clone(),
+        };
+
+        print_extension(&definition, depth, options)
+    }
+}
+
+impl PrettyPrint for InputObjectTypeExtension {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let definition = InputObjectTypeDefinition {
+            description: None,
+            name: self.name.clone(),
+            directives: self.directives.clone(),
+            fields: self.fields.clone(),
+            position: self.position.clone(),
+        };
+
+        print_extension(&definition, depth, options)
+    }
+}
+
+impl PrettyPrint for FieldDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        format!(
+            "{}{}",
+            print_description(&self.description, depth, options),
+            indent!(
+                options,
+                depth,
+                format!(
+                    "{}{}: {}{}",
+                    self.name.pretty_print(depth, options),
+                    print_argument_definitions(&self.arguments, depth, options),
+                    self.field_type.pretty_print(depth, options),
+                    print_directives(&self.directives, depth, options)
+                )
+            )
+        )
+    }
+}
+
+impl PrettyPrint for InputValueDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let mut output = format!(
+            "{}: {}",
+            self.name.pretty_print(depth, options),
+            self.input_type.pretty_print(depth, options)
+        );
+
+        if let Some(default_value) = &self.default_value {
+            output.push_str(" = ");
+            output.push_str(&default_value.pretty_print(depth, options));
+        }
+
+        output.push_str(&print_directives(&self.directives, depth, options));
+
+        format!(
+            "{}{}",
+            print_description(&self.description, depth, options),
+            indent!(options, depth, output)
+        )
+    }
+}
+
+impl PrettyPrint for DirectiveDefinition {
+    fn pretty_print(&self, depth: usize, options: &PrintOptions) -> String {
+        let mut output = format!(
+            "directive @{}{}",
+            self.name.pretty_print(depth, options),
+            print_argument_definitions(&self.arguments, depth, options)
+        );
+
+        if self.repeatable {
+            output.push_str(" repeatable");
+        }
+
+        let locations = self
+            .locations
+            .iter()
+            .map(|location| location.as_str())
+            .collect::<Vec<&str>>();
+
+        output.push_str(" on ");
+        output.push_str(&locations.join(" | "));
+
+        format!(
+            "{}{}",
+            print_description(&self.description, depth, options),
+            indent!(options, depth, output)
+        )
     }
 }
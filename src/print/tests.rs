@@ -0,0 +1,361 @@
+#![cfg(test)]
+
+use crate::parser::parse_strict;
+use crate::parser::types::{AstEq, Definition, Type};
+use crate::print::compact::print_compact;
+use crate::print::pretty_print::{
+    print, print_type, print_with_options, IndentStyle, PrintOptions,
+};
+
+/// Prints the source and checks the output parses again.
+fn print_source(source: &str) -> String {
+    let document = parse_strict(source.to_string()).unwrap();
+    let output = print(&document);
+
+    if let Err(diagnostic) = parse_strict(output.clone()) {
+        panic!(
+            "Printed output failed to parse: {}\n{}",
+            diagnostic.message, output
+        );
+    }
+
+    output
+}
+
+#[test]
+fn it_prints_fragment_definitions() {
+    let source = "fragment UserFields on User @deprecated { name friends { name } }";
+
+    assert_eq!(
+        print_source(source),
+        "fragment UserFields on User @deprecated {\n  name\n  friends {\n    name\n  }\n}\n"
+    );
+}
+
+#[test]
+fn it_prints_inline_fragments() {
+    let source = "{ node { id ... on User { name } ... @include(if: true) { email } } }";
+
+    assert_eq!(
+        print_source(source),
+        concat!(
+            "query {\n",
+            "  node {\n",
+            "    id\n",
+            "    ... on User {\n",
+            "      name\n",
+            "    }\n",
+            "    ... @include(if: true) {\n",
+            "      email\n",
+            "    }\n",
+            "  }\n",
+            "}\n",
+        )
+    );
+}
+
+#[test]
+fn it_prints_fragment_spreads() {
+    let source = "{ user { ...UserFields ...AdminFields @include(if: true) @skip(if: false) } }";
+
+    assert_eq!(
+        print_source(source),
+        concat!(
+            "query {\n",
+            "  user {\n",
+            "    ...UserFields\n",
+            "    ...AdminFields @include(if: true) @skip(if: false)\n",
+            "  }\n",
+            "}\n",
+        )
+    );
+}
+
+#[test]
+fn it_prints_object_values() {
+    let source = r#"{ a(where: {}) b(where: { id: 1 }) c(where: { id: 1, name: "Ada" }) }"#;
+
+    assert_eq!(
+        print_source(source),
+        concat!(
+            "query {\n",
+            "  a(where: {})\n",
+            "  b(where: {id: 1})\n",
+            "  c(where: {id: 1, name: \"Ada\"})\n",
+            "}\n",
+        )
+    );
+}
+
+#[test]
+fn it_splits_long_object_values_over_several_lines() {
+    let source = concat!(
+        r#"{ users(where: { firstName: "Augusta Ada", lastName: "King", "#,
+        r#"title: "Countess of Lovelace", born: 1815 }) { id } }"#
+    );
+
+    assert_eq!(
+        print_source(source),
+        concat!(
+            "query {\n",
+            "  users(where: {\n",
+            "    firstName: \"Augusta Ada\"\n",
+            "    lastName: \"King\"\n",
+            "    title: \"Countess of Lovelace\"\n",
+            "    born: 1815\n",
+            "  }) {\n",
+            "    id\n",
+            "  }\n",
+            "}\n",
+        )
+    );
+}
+
+#[test]
+fn it_prints_schema_definitions() {
+    let source = r#"
+        schema @key { query: Query mutation: Mutation }
+
+        "A custom scalar"
+        scalar DateTime @specifiedBy(url: "https://example.com")
+
+        """
+        The root query type
+        """
+        type Query implements Node & Entity @key(fields: "id") {
+            "Look a user up by id"
+            user(id: ID!, limit: Int = 10 @deprecated): User
+            users(
+                "How many to return"
+                first: Int
+            ): [User!]!
+        }
+
+        interface Node implements Entity { id: ID! }
+
+        union SearchResult @key = User | Post
+
+        enum Role { ADMIN @deprecated(reason: "use OWNER") "Owns everything" OWNER }
+
+        input UserFilter { name: String = "Ada" roles: [Role!] = [ADMIN] where: Where = { a: 1 } }
+
+        "Caches the field"
+        directive @cached(ttl: Int) repeatable on FIELD_DEFINITION | OBJECT
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+    let output = print_source(source);
+    let reparsed = parse_strict(output.clone()).unwrap();
+
+    assert!(document.ast_eq(&reparsed), "{}", output);
+    assert!(output.contains(concat!(
+        "type Query implements Node & Entity @key(fields: \"id\") {\n",
+        "  \"Look a user up by id\"\n",
+        "  user(id: ID!, limit: Int = 10 @deprecated): User\n",
+        "  users(\n",
+        "    \"How many to return\"\n",
+        "    first: Int\n",
+        "  ): [User!]!\n",
+        "}\n",
+    )));
+    assert!(output.contains("union SearchResult @key = User | Post\n"));
+    assert!(
+        output.contains("directive @cached(ttl: Int) repeatable on FIELD_DEFINITION | OBJECT\n")
+    );
+}
+
+#[test]
+fn it_prints_type_system_extensions() {
+    let source = r#"
+        extend schema @key { subscription: Subscription }
+        extend scalar DateTime @key
+        extend type User implements Node { email: String }
+        extend interface Node @key
+        extend union SearchResult = Post
+        extend enum Role { GUEST }
+        extend input UserFilter { email: String }
+    "#;
+
+    assert_eq!(
+        print_source(source),
+        concat!(
+            "extend schema @key {\n  subscription: Subscription\n}\n",
+            "extend scalar DateTime @key\n",
+            "extend type User implements Node {\n  email: String\n}\n",
+            "extend interface Node @key\n",
+            "extend union SearchResult = Post\n",
+            "extend enum Role {\n  GUEST\n}\n",
+            "extend input UserFilter {\n  email: String\n}\n",
+        )
+    );
+}
+
+#[test]
+fn it_indents_with_four_spaces() {
+    let document = parse_strict("{ user { name } }".to_string()).unwrap();
+    let options = PrintOptions {
+        indent_style: IndentStyle::Spaces,
+        indent_width: 4,
+    };
+
+    assert_eq!(
+        print_with_options(&document, &options),
+        "query {\n    user {\n        name\n    }\n}\n"
+    );
+}
+
+#[test]
+fn it_indents_with_tabs() {
+    let document = parse_strict("type User { name: String }".to_string()).unwrap();
+    let options = PrintOptions {
+        indent_style: IndentStyle::Tabs,
+        indent_width: 1,
+    };
+
+    assert_eq!(
+        print_with_options(&document, &options),
+        "type User {\n\tname: String\n}\n"
+    );
+}
+
+#[test]
+fn it_prints_compact_documents() {
+    let source = r#"
+        query GetUsers($first: Int = 10, $after: String @deprecated, $ids: [ID!]!) @live {
+            users(
+                first: $first
+                after: $after
+                ids: $ids
+                where: { name: "Ada \"Lovelace\"", tags: ["" "b"] }
+            ) {
+                id
+                alias: name
+                friends(scale: 1.5, order: ASC, active: true, missing: null) @include(if: true) {
+                    ...UserFields
+                    ... on Admin { level }
+                    ... @skip(if: false) { email }
+                }
+            }
+        }
+
+        query Bio {
+            bio(format: """
+                Block
+            """)
+        }
+
+        fragment UserFields on User @cached {
+            id
+            name
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+    let output = print_compact(&document);
+    let reparsed = parse_strict(output.clone()).unwrap();
+
+    assert!(document.ast_eq(&reparsed), "{}", output);
+    assert!(output.starts_with(
+        "query GetUsers($first:Int=10$after:String@deprecated$ids:[ID!]!)@live{users(first:$first"
+    ));
+    assert!(output.contains(r#"tags:["" "b"]"#));
+    assert!(output.contains("...UserFields...on Admin{level}...@skip(if:false){email}"));
+    assert!(output.contains(r#"query Bio{bio(format:"""Block""")}"#));
+}
+
+#[test]
+fn it_prints_variable_definitions() {
+    assert_eq!(
+        print_source("query GetUser { user }"),
+        "query GetUser {\n  user\n}\n"
+    );
+    assert_eq!(
+        print_source("query GetUser($id: ID!) { user(id: $id) }"),
+        "query GetUser($id: ID!) {\n  user(id: $id)\n}\n"
+    );
+    assert_eq!(
+        print_source("query GetUsers($first: Int = 10, $scale: Float = 1.0) { users }"),
+        "query GetUsers($first: Int = 10, $scale: Float = 1.0) {\n  users\n}\n"
+    );
+    assert_eq!(
+        print_source("query ($ids: [ID!] @deprecated(reason: \"no\")) @live { users }"),
+        "query ($ids: [ID!] @deprecated(reason: \"no\")) @live {\n  users\n}\n"
+    );
+}
+
+#[test]
+fn it_prints_block_strings() {
+    let source = r#"
+        """
+
+        The root query type.
+
+        Contains a \""" quote.
+        """
+        type Query {
+            """
+            Looks a user up
+            """
+            user: User
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+    let output = print_source(source);
+
+    assert_eq!(
+        output,
+        concat!(
+            "\"\"\"\n",
+            "  The root query type.\n",
+            "\n",
+            "  Contains a \\\"\"\" quote.\n",
+            "\"\"\"\n",
+            "type Query {\n",
+            "  \"\"\"\n",
+            "    Looks a user up\n",
+            "  \"\"\"\n",
+            "  user: User\n",
+            "}\n",
+        )
+    );
+    assert!(document.ast_eq(&parse_strict(output).unwrap()));
+}
+
+/// The type of the `field` field of the single type defined in the source.
+fn field_type(source: &str) -> Type {
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match &document.definitions[0] {
+        Definition::ObjectTypeDefinition(object) => object.fields[0].field_type.clone(),
+        _ => panic!("Expected ObjectTypeDefinition"),
+    }
+}
+
+#[test]
+fn it_prints_types() {
+    assert_eq!(
+        print_type(&field_type("type A { field: String }")),
+        "String"
+    );
+    assert_eq!(print_type(&field_type("type A { field: [Int] }")), "[Int]");
+    assert_eq!(print_type(&field_type("type A { field: ID! }")), "ID!");
+    assert_eq!(
+        print_type(&field_type("type A { field: [Int!]! }")),
+        "[Int!]!"
+    );
+    assert_eq!(
+        print_type(&field_type("type A { field: [[String!]!] }")),
+        "[[String!]!]"
+    );
+}
+
+#[test]
+fn it_prints_types_of_fields_and_arguments() {
+    let source = "type A { field(ids: [[ID!]!]! = [[1]]): [[String!]!] }";
+
+    assert_eq!(
+        print_source(source),
+        "type A {\n  field(ids: [[ID!]!]! = [[1]]): [[String!]!]\n}\n"
+    );
+}
@@ -0,0 +1,344 @@
+use std::fmt;
+
+use serde_json::Value as JsonValue;
+
+use crate::lsp::types::Range;
+use crate::parser::parse_const_value;
+use crate::parser::types::{
+    Argument, Directive, DirectiveDefinition, DirectiveLocation, Document, EnumTypeDefinition,
+    EnumValueDefinition, FieldDefinition, InputObjectTypeDefinition, InputValueDefinition,
+    InterfaceTypeDefinition, ListType, Name, NamedType, NonNullType, ObjectTypeDefinition,
+    ScalarTypeDefinition, StringValue, Type, UnionTypeDefinition, Value,
+};
+
+use super::{Schema, TypeDefinition};
+
+/// Why an introspection result could not be turned into a schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaError {
+    pub message: String,
+}
+
+impl SchemaError {
+    fn new(message: impl Into<String>) -> SchemaError {
+        SchemaError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Builds a schema from the JSON result of an introspection query, either the
+/// whole response (`{"data": {"__schema": ...}}`) or just its `__schema`. The
+/// introspection types themselves are left out, the same as for a schema
+/// built from SDL, and since there is no source every node has a zero range.
+/// https://spec.graphql.org/October2021/#sec-Schema-Introspection
+pub fn schema_from_introspection(json_str: &str) -> Result<Schema, SchemaError> {
+    let json: JsonValue = serde_json::from_str(json_str)
+        .map_err(|error| SchemaError::new(format!("Invalid JSON: {}", error)))?;
+
+    let introspection = json
+        .get("data")
+        .unwrap_or(&json)
+        .get("__schema")
+        .ok_or_else(|| SchemaError::new("Expected an introspection result with a `__schema`"))?;
+
+    // starts out with the built-in scalars and directives
+    let mut schema = Schema::from_document(&Document {
+        definitions: Vec::new(),
+        position: Range::ZERO,
+    });
+
+    for full_type in array(introspection, "types")? {
+        let name = string(full_type, "name")?;
+        if name.starts_with("__") {
+            continue;
+        }
+
+        let definition = type_definition(full_type)?;
+        schema.types.insert(name.to_string(), definition);
+    }
+
+    for directive in optional_array(introspection, "directives")? {
+        let definition = directive_definition(directive)?;
+        schema
+            .directives
+            .insert(definition.name.value.clone(), definition);
+    }
+
+    schema.query_type = root_type_name(introspection, "queryType")?;
+    schema.mutation_type = root_type_name(introspection, "mutationType")?;
+    schema.subscription_type = root_type_name(introspection, "subscriptionType")?;
+
+    Ok(schema)
+}
+
+/// https://spec.graphql.org/October2021/#sec-The-__Type-Type
+fn type_definition(full_type: &JsonValue) -> Result<TypeDefinition, SchemaError> {
+    let description = description(full_type)?;
+    let name = name(full_type)?;
+    let directives = Vec::new();
+    let position = Range::ZERO;
+
+    let definition = match string(full_type, "kind")? {
+        "SCALAR" => TypeDefinition::Scalar(ScalarTypeDefinition {
+            description,
+            name,
+            directives,
+            position,
+        }),
+        "OBJECT" => TypeDefinition::Object(ObjectTypeDefinition {
+            description,
+            name,
+            interfaces: named_types(full_type, "interfaces")?,
+            directives,
+            fields: fields(full_type)?,
+            position,
+        }),
+        "INTERFACE" => TypeDefinition::Interface(InterfaceTypeDefinition {
+            description,
+            name,
+            interfaces: named_types(full_type, "interfaces")?,
+            directives,
+            fields: fields(full_type)?,
+            position,
+        }),
+        "UNION" => TypeDefinition::Union(UnionTypeDefinition {
+            description,
+            name,
+            directives,
+            member_types: named_types(full_type, "possibleTypes")?,
+            position,
+        }),
+        "ENUM" => TypeDefinition::Enum(EnumTypeDefinition {
+            description,
+            name,
+            directives,
+            values: optional_array(full_type, "enumValues")?
+                .iter()
+                .map(enum_value_definition)
+                .collect::<Result<_, _>>()?,
+            position,
+        }),
+        "INPUT_OBJECT" => TypeDefinition::InputObject(InputObjectTypeDefinition {
+            description,
+            name,
+            directives,
+            fields: input_values(full_type, "inputFields")?,
+            position,
+        }),
+        kind => {
+            return Err(SchemaError::new(format!(
+                "Unexpected kind `{}` for type `{}`",
+                kind, name.value
+            )))
+        }
+    };
+
+    Ok(definition)
+}
+
+/// https://spec.graphql.org/October2021/#sec-The-__Field-Type
+fn fields(full_type: &JsonValue) -> Result<Vec<FieldDefinition>, SchemaError> {
+    optional_array(full_type, "fields")?
+        .iter()
+        .map(|field| {
+            Ok(FieldDefinition {
+                description: description(field)?,
+                name: name(field)?,
+                arguments: input_values(field, "args")?,
+                field_type: type_ref(get(field, "type")?)?,
+                directives: deprecation(field)?,
+                position: Range::ZERO,
+            })
+        })
+        .collect()
+}
+
+/// https://spec.graphql.org/October2021/#sec-The-__InputValue-Type
+fn input_values(value: &JsonValue, key: &str) -> Result<Vec<InputValueDefinition>, SchemaError> {
+    optional_array(value, key)?
+        .iter()
+        .map(|input_value| {
+            let default_value = match input_value.get("defaultValue") {
+                Some(JsonValue::String(source)) => {
+                    Some(parse_const_value(source).map_err(|diagnostic| {
+                        SchemaError::new(format!(
+                            "Invalid default value `{}`: {}",
+                            source, diagnostic.message
+                        ))
+                    })?)
+                }
+                _ => None,
+            };
+
+            Ok(InputValueDefinition {
+                description: description(input_value)?,
+                name: name(input_value)?,
+                input_type: type_ref(get(input_value, "type")?)?,
+                default_value,
+                directives: deprecation(input_value)?,
+                position: Range::ZERO,
+            })
+        })
+        .collect()
+}
+
+/// https://spec.graphql.org/October2021/#sec-The-__EnumValue-Type
+fn enum_value_definition(enum_value: &JsonValue) -> Result<EnumValueDefinition, SchemaError> {
+    Ok(EnumValueDefinition {
+        description: description(enum_value)?,
+        name: name(enum_value)?,
+        directives: deprecation(enum_value)?,
+        position: Range::ZERO,
+    })
+}
+
+/// https://spec.graphql.org/October2021/#sec-The-__Directive-Type
+fn directive_definition(directive: &JsonValue) -> Result<DirectiveDefinition, SchemaError> {
+    let locations = array(directive, "locations")?
+        .iter()
+        .map(|location| {
+            location
+                .as_str()
+                .and_then(DirectiveLocation::parse)
+                .ok_or_else(|| SchemaError::new(format!("Unknown directive location {}", location)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(DirectiveDefinition {
+        description: description(directive)?,
+        name: name(directive)?,
+        arguments: input_values(directive, "args")?,
+        repeatable: directive
+            .get("isRepeatable")
+            .and_then(JsonValue::as_bool)
+            .unwrap_or(false),
+        locations,
+        position: Range::ZERO,
+    })
+}
+
+/// Unwraps a `__Type` reference, such as the type of a field, into the list
+/// and non-null wrappers around a named type.
+fn type_ref(type_ref: &JsonValue) -> Result<Type, SchemaError> {
+    match string(type_ref, "kind")? {
+        "NON_NULL" => Ok(Type::NonNullType(NonNullType {
+            wrapped_type: Box::new(type_ref_of(type_ref)?),
+            position: Range::ZERO,
+        })),
+        "LIST" => Ok(Type::ListType(ListType {
+            wrapped_type: Box::new(type_ref_of(type_ref)?),
+            position: Range::ZERO,
+        })),
+        _ => Ok(Type::NamedType(named_type(type_ref)?)),
+    }
+}
+
+fn type_ref_of(type_ref: &JsonValue) -> Result<Type, SchemaError> {
+    self::type_ref(get(type_ref, "ofType")?)
+}
+
+/// Introspection has no directives, so only `@deprecated` can be recovered
+/// from `isDeprecated` and `deprecationReason`.
+fn deprecation(value: &JsonValue) -> Result<Vec<Directive>, SchemaError> {
+    if value.get("isDeprecated").and_then(JsonValue::as_bool) != Some(true) {
+        return Ok(Vec::new());
+    }
+
+    let arguments = match value.get("deprecationReason") {
+        Some(JsonValue::String(reason)) => vec![Argument {
+            name: to_name("reason"),
+            value: Value::StringValue(to_string_value(reason)),
+            position: Range::ZERO,
+        }],
+        _ => Vec::new(),
+    };
+
+    Ok(vec![Directive {
+        name: to_name("deprecated"),
+        position: Range::ZERO,
+        arguments,
+    }])
+}
+
+fn root_type_name(introspection: &JsonValue, key: &str) -> Result<Option<String>, SchemaError> {
+    match introspection.get(key) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(root_type) => Ok(Some(string(root_type, "name")?.to_string())),
+    }
+}
+
+fn named_types(value: &JsonValue, key: &str) -> Result<Vec<NamedType>, SchemaError> {
+    optional_array(value, key)?.iter().map(named_type).collect()
+}
+
+fn named_type(value: &JsonValue) -> Result<NamedType, SchemaError> {
+    Ok(NamedType {
+        name: name(value)?,
+        position: Range::ZERO,
+    })
+}
+
+fn name(value: &JsonValue) -> Result<Name, SchemaError> {
+    string(value, "name").map(to_name)
+}
+
+fn description(value: &JsonValue) -> Result<Option<StringValue>, SchemaError> {
+    match value.get("description") {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(JsonValue::String(description)) => Ok(Some(to_string_value(description))),
+        Some(description) => Err(SchemaError::new(format!(
+            "Expected `description` to be a string, found {}",
+            description
+        ))),
+    }
+}
+
+fn to_name(value: &str) -> Name {
+    Name {
+        value: value.to_string(),
+        position: Range::ZERO,
+    }
+}
+
+fn to_string_value(value: &str) -> StringValue {
+    StringValue {
+        value: value.to_string(),
+        block: value.contains('\n'),
+        position: Range::ZERO,
+    }
+}
+
+fn get<'a>(value: &'a JsonValue, key: &str) -> Result<&'a JsonValue, SchemaError> {
+    value
+        .get(key)
+        .ok_or_else(|| SchemaError::new(format!("Missing `{}` in the introspection result", key)))
+}
+
+fn string<'a>(value: &'a JsonValue, key: &str) -> Result<&'a str, SchemaError> {
+    get(value, key)?
+        .as_str()
+        .ok_or_else(|| SchemaError::new(format!("Expected `{}` to be a string", key)))
+}
+
+fn array<'a>(value: &'a JsonValue, key: &str) -> Result<&'a [JsonValue], SchemaError> {
+    get(value, key)?
+        .as_array()
+        .map(Vec::as_slice)
+        .ok_or_else(|| SchemaError::new(format!("Expected `{}` to be a list", key)))
+}
+
+/// Lists that introspection leaves `null` when they don't apply to the kind
+/// of type, such as the fields of a scalar.
+fn optional_array<'a>(value: &'a JsonValue, key: &str) -> Result<&'a [JsonValue], SchemaError> {
+    match value.get(key) {
+        None | Some(JsonValue::Null) => Ok(&[]),
+        Some(_) => array(value, key),
+    }
+}
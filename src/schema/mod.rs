@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+
+use crate::lsp::types::Range;
+use crate::parser::parse_strict;
+use crate::parser::types::{
+    Definition, DirectiveDefinition, Document, EnumTypeDefinition, FieldDefinition,
+    InputObjectTypeDefinition, InterfaceTypeDefinition, Name, NamedType, ObjectTypeDefinition,
+    OperationType, ScalarTypeDefinition, UnionTypeDefinition,
+};
+
+pub use self::introspection::{schema_from_introspection, SchemaError};
+
+mod introspection;
+mod tests;
+
+/// https://spec.graphql.org/October2021/#sec-Scalars.Built-in-Scalars
+pub const BUILT_IN_SCALARS: [&str; 5] = ["Int", "Float", "String", "Boolean", "ID"];
+
+/// https://spec.graphql.org/October2021/#sec-Type-System.Directives.Built-in-Directives
+const BUILT_IN_DIRECTIVES: &str = r#"
+"Directs the executor to skip this field or fragment when the `if` argument is true."
+directive @skip("Skipped when true." if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+
+"Directs the executor to include this field or fragment only when the `if` argument is true."
+directive @include(
+  "Included when true."
+  if: Boolean!
+) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+
+"Marks an element of a GraphQL schema as no longer supported."
+directive @deprecated(
+  "Explains why this element was deprecated."
+  reason: String = "No longer supported"
+) on FIELD_DEFINITION | ENUM_VALUE
+
+"Exposes a URL that specifies the behavior of this scalar."
+directive @specifiedBy("The URL that specifies the behavior of this scalar." url: String!) on SCALAR
+"#;
+
+/// The types and directives a schema document defines, indexed by name.
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    pub types: HashMap<String, TypeDefinition>,
+    pub directives: HashMap<String, DirectiveDefinition>,
+    pub query_type: Option<String>,
+    pub mutation_type: Option<String>,
+    pub subscription_type: Option<String>,
+}
+
+/// https://spec.graphql.org/October2021/#TypeDefinition
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeDefinition {
+    Scalar(ScalarTypeDefinition),
+    Object(ObjectTypeDefinition),
+    Interface(InterfaceTypeDefinition),
+    Union(UnionTypeDefinition),
+    Enum(EnumTypeDefinition),
+    InputObject(InputObjectTypeDefinition),
+}
+
+impl TypeDefinition {
+    pub fn name(&self) -> &str {
+        match self {
+            TypeDefinition::Scalar(definition) => &definition.name.value,
+            TypeDefinition::Object(definition) => &definition.name.value,
+            TypeDefinition::Interface(definition) => &definition.name.value,
+            TypeDefinition::Union(definition) => &definition.name.value,
+            TypeDefinition::Enum(definition) => &definition.name.value,
+            TypeDefinition::InputObject(definition) => &definition.name.value,
+        }
+    }
+
+    /// The fields that can be selected on the type. Only object and interface
+    /// types have any.
+    pub fn fields(&self) -> &[FieldDefinition] {
+        match self {
+            TypeDefinition::Object(definition) => &definition.fields,
+            TypeDefinition::Interface(definition) => &definition.fields,
+            _ => &[],
+        }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldDefinition> {
+        self.fields().iter().find(|field| field.name.value == name)
+    }
+
+    /// Whether the type is an object, interface or union, and so must have a
+    /// selection set when selected.
+    /// https://spec.graphql.org/October2021/#sec-Types
+    pub fn is_composite(&self) -> bool {
+        matches!(
+            self,
+            TypeDefinition::Object(_) | TypeDefinition::Interface(_) | TypeDefinition::Union(_)
+        )
+    }
+
+    /// Whether the type is a scalar or enum, and so can not have a selection
+    /// set.
+    pub fn is_leaf(&self) -> bool {
+        matches!(self, TypeDefinition::Scalar(_) | TypeDefinition::Enum(_))
+    }
+}
+
+impl Schema {
+    /// Builds a schema from the type system definitions and extensions in the
+    /// document. Executable definitions are ignored. The built-in scalars and
+    /// directives are always present, and without a schema definition the
+    /// root types default to the types named `Query`, `Mutation` and
+    /// `Subscription`.
+    /// https://spec.graphql.org/October2021/#sec-Root-Operation-Types.Default-Root-Operation-Type-Names
+    pub fn from_document(document: &Document) -> Schema {
+        let mut schema = Schema::default();
+
+        for name in BUILT_IN_SCALARS {
+            schema.types.insert(
+                name.to_string(),
+                TypeDefinition::Scalar(ScalarTypeDefinition {
+                    description: None,
+                    name: Name {
+                        value: name.to_string(),
+                        position: Range::ZERO,
+                    },
+                    directives: Vec::new(),
+                    position: Range::ZERO,
+                }),
+            );
+        }
+
+        let mut has_schema_definition = false;
+
+        for definition in &document.definitions {
+            match definition {
+                Definition::SchemaDefinition(definition) => {
+                    has_schema_definition = true;
+                    for operation_type in &definition.operation_types {
+                        schema.set_root_type(
+                            &operation_type.operation_type,
+                            &operation_type.named_type,
+                        );
+                    }
+                }
+                Definition::SchemaExtension(definition) => {
+                    for operation_type in &definition.operation_types {
+                        schema.set_root_type(
+                            &operation_type.operation_type,
+                            &operation_type.named_type,
+                        );
+                    }
+                }
+                Definition::ScalarTypeDefinition(definition) => {
+                    schema.insert_type(TypeDefinition::Scalar(definition.clone()))
+                }
+                Definition::ObjectTypeDefinition(definition) => {
+                    schema.insert_type(TypeDefinition::Object(definition.clone()))
+                }
+                Definition::InterfaceTypeDefinition(definition) => {
+                    schema.insert_type(TypeDefinition::Interface(definition.clone()))
+                }
+                Definition::UnionTypeDefinition(definition) => {
+                    schema.insert_type(TypeDefinition::Union(definition.clone()))
+                }
+                Definition::EnumTypeDefinition(definition) => {
+                    schema.insert_type(TypeDefinition::Enum(definition.clone()))
+                }
+                Definition::InputObjectTypeDefinition(definition) => {
+                    schema.insert_type(TypeDefinition::InputObject(definition.clone()))
+                }
+                Definition::DirectiveDefinition(definition) => {
+                    schema
+                        .directives
+                        .insert(definition.name.value.clone(), definition.clone());
+                }
+                _ => {}
+            }
+        }
+
+        // extensions are applied once every type is known, since they may
+        // appear before the type they extend
+        for definition in &document.definitions {
+            schema.apply_extension(definition);
+        }
+
+        // the built-in directives can be redefined, in which case the
+        // schema's own definition wins
+        let built_in_directives = parse_strict(BUILT_IN_DIRECTIVES.to_string())
+            .expect("The built-in directives should parse");
+        for definition in built_in_directives.definitions {
+            if let Definition::DirectiveDefinition(definition) = definition {
+                schema
+                    .directives
+                    .entry(definition.name.value.clone())
+                    .or_insert(definition);
+            }
+        }
+
+        if !has_schema_definition {
+            for (operation_type, name) in [
+                (OperationType::Query, "Query"),
+                (OperationType::Mutation, "Mutation"),
+                (OperationType::Subscription, "Subscription"),
+            ] {
+                if schema.root_type_name(&operation_type).is_none()
+                    && schema.types.contains_key(name)
+                {
+                    schema.set_root_type_name(&operation_type, name.to_string());
+                }
+            }
+        }
+
+        schema
+    }
+
+    pub fn get_type(&self, name: &str) -> Option<&TypeDefinition> {
+        self.types.get(name)
+    }
+
+    pub fn root_type_name(&self, operation_type: &OperationType) -> Option<&str> {
+        match operation_type {
+            OperationType::Query => self.query_type.as_deref(),
+            OperationType::Mutation => self.mutation_type.as_deref(),
+            OperationType::Subscription => self.subscription_type.as_deref(),
+        }
+    }
+
+    /// The type that operations of the given kind select their fields from.
+    pub fn root_type(&self, operation_type: &OperationType) -> Option<&TypeDefinition> {
+        self.root_type_name(operation_type)
+            .and_then(|name| self.get_type(name))
+    }
+
+    fn insert_type(&mut self, definition: TypeDefinition) {
+        self.types.insert(definition.name().to_string(), definition);
+    }
+
+    fn set_root_type(&mut self, operation_type: &OperationType, named_type: &NamedType) {
+        self.set_root_type_name(operation_type, named_type.name.value.clone());
+    }
+
+    fn set_root_type_name(&mut self, operation_type: &OperationType, name: String) {
+        match operation_type {
+            OperationType::Query => self.query_type = Some(name),
+            OperationType::Mutation => self.mutation_type = Some(name),
+            OperationType::Subscription => self.subscription_type = Some(name),
+        }
+    }
+
+    fn apply_extension(&mut self, definition: &Definition) {
+        match definition {
+            Definition::ObjectTypeExtension(extension) => {
+                if let Some(TypeDefinition::Object(object)) =
+                    self.types.get_mut(&extension.name.value)
+                {
+                    object
+                        .interfaces
+                        .extend(extension.interfaces.iter().cloned());
+                    object
+                        .directives
+                        .extend(extension.directives.iter().cloned());
+                    object.fields.extend(extension.fields.iter().cloned());
+                }
+            }
+            Definition::InterfaceTypeExtension(extension) => {
+                if let Some(TypeDefinition::Interface(interface)) =
+                    self.types.get_mut(&extension.name.value)
+                {
+                    interface
+                        .interfaces
+                        .extend(extension.interfaces.iter().cloned());
+                    interface
+                        .directives
+                        .extend(extension.directives.iter().cloned());
+                    interface.fields.extend(extension.fields.iter().cloned());
+                }
+            }
+            Definition::UnionTypeExtension(extension) => {
+                if let Some(TypeDefinition::Union(union)) =
+                    self.types.get_mut(&extension.name.value)
+                {
+                    union
+                        .directives
+                        .extend(extension.directives.iter().cloned());
+                    union
+                        .member_types
+                        .extend(extension.member_types.iter().cloned());
+                }
+            }
+            Definition::EnumTypeExtension(extension) => {
+                if let Some(TypeDefinition::Enum(enum_type)) =
+                    self.types.get_mut(&extension.name.value)
+                {
+                    enum_type
+                        .directives
+                        .extend(extension.directives.iter().cloned());
+                    enum_type.values.extend(extension.values.iter().cloned());
+                }
+            }
+            Definition::InputObjectTypeExtension(extension) => {
+                if let Some(TypeDefinition::InputObject(input_object)) =
+                    self.types.get_mut(&extension.name.value)
+                {
+                    input_object
+                        .directives
+                        .extend(extension.directives.iter().cloned());
+                    input_object.fields.extend(extension.fields.iter().cloned());
+                }
+            }
+            Definition::ScalarTypeExtension(extension) => {
+                if let Some(TypeDefinition::Scalar(scalar)) =
+                    self.types.get_mut(&extension.name.value)
+                {
+                    scalar
+                        .directives
+                        .extend(extension.directives.iter().cloned());
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,178 @@
+#![cfg(test)]
+
+use super::*;
+use crate::parser::parse_strict;
+
+#[test]
+fn it_builds_a_schema_from_type_definitions() {
+    let source = r#"
+        type Query {
+            user(id: ID!): User
+        }
+
+        type User {
+            name: String
+        }
+
+        extend type User {
+            email: String
+        }
+
+        enum Role { ADMIN }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+    let schema = Schema::from_document(&document);
+
+    assert_eq!(schema.query_type.as_deref(), Some("Query"));
+    assert_eq!(schema.mutation_type, None);
+
+    let user = schema.get_type("User").unwrap();
+    assert!(user.is_composite());
+    assert_eq!(user.fields().len(), 2);
+    assert!(user.field("email").is_some());
+
+    assert!(schema.get_type("Role").unwrap().is_leaf());
+    assert!(schema.get_type("String").unwrap().is_leaf());
+}
+
+#[test]
+fn it_uses_the_root_types_of_the_schema_definition() {
+    let source = r#"
+        schema {
+            query: RootQuery
+            mutation: RootMutation
+        }
+
+        type Query { unused: String }
+        type RootQuery { user: String }
+        type RootMutation { save: Boolean }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+    let schema = Schema::from_document(&document);
+
+    assert_eq!(
+        schema.root_type(&OperationType::Query).unwrap().name(),
+        "RootQuery"
+    );
+    assert_eq!(
+        schema.root_type(&OperationType::Mutation).unwrap().name(),
+        "RootMutation"
+    );
+    assert!(schema.root_type(&OperationType::Subscription).is_none());
+}
+
+#[test]
+fn it_includes_the_built_in_directives() {
+    let source = r#"
+        "Skips nothing"
+        directive @skip(unless: Boolean) on FIELD
+
+        directive @cached on FIELD
+    "#;
+    let schema = Schema::from_document(&parse_strict(source.to_string()).unwrap());
+
+    let mut names = schema.directives.keys().cloned().collect::<Vec<String>>();
+    names.sort();
+    assert_eq!(
+        names,
+        vec!["cached", "deprecated", "include", "skip", "specifiedBy"]
+    );
+
+    let include = &schema.directives["include"];
+    assert_eq!(include.arguments[0].name.value, "if");
+    assert!(include.arguments[0].is_required());
+    assert_eq!(include.locations.len(), 3);
+
+    // redefining a built-in directive replaces it
+    let skip = &schema.directives["skip"];
+    assert_eq!(skip.arguments[0].name.value, "unless");
+}
+
+#[test]
+fn it_builds_a_schema_from_an_introspection_result() {
+    let json = include_str!("../../tests/fixtures/introspection.json");
+    let schema = schema_from_introspection(json).unwrap();
+
+    assert_eq!(schema.query_type.as_deref(), Some("Query"));
+    assert_eq!(schema.mutation_type, None);
+
+    // the introspection types are left out, the built-in ones are kept
+    assert!(schema.get_type("__Schema").is_none());
+    assert!(schema.get_type("Boolean").unwrap().is_leaf());
+    assert!(schema.directives.contains_key("skip"));
+
+    let query = schema.get_type("Query").unwrap();
+    assert_eq!(query.field("user").unwrap().field_type.to_string(), "User");
+    assert_eq!(
+        query.field("search").unwrap().field_type.to_string(),
+        "[SearchResult!]!"
+    );
+
+    let Some(TypeDefinition::Object(user)) = schema.get_type("User") else {
+        panic!("Expected User to be an object type");
+    };
+    assert_eq!(
+        user.description.as_ref().unwrap().value,
+        "Someone with an account"
+    );
+    assert_eq!(user.interfaces[0].name.value, "Node");
+    assert_eq!(user.fields[1].directives[0].name.value, "deprecated");
+
+    let friends = &user.fields[2];
+    assert_eq!(
+        friends.arguments[0]
+            .default_value
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "ASC"
+    );
+    assert_eq!(
+        friends.arguments[1]
+            .default_value
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        r#"{name: "me"}"#
+    );
+
+    assert!(matches!(
+        schema.get_type("Node"),
+        Some(TypeDefinition::Interface(_))
+    ));
+    assert!(matches!(
+        schema.get_type("SearchResult"),
+        Some(TypeDefinition::Union(_))
+    ));
+    assert!(matches!(
+        schema.get_type("UserFilter"),
+        Some(TypeDefinition::InputObject(_))
+    ));
+    assert!(matches!(
+        schema.get_type("DateTime"),
+        Some(TypeDefinition::Scalar(_))
+    ));
+
+    let Some(TypeDefinition::Enum(order)) = schema.get_type("Order") else {
+        panic!("Expected Order to be an enum type");
+    };
+    assert_eq!(order.values.len(), 2);
+
+    let cached = &schema.directives["cached"];
+    assert_eq!(cached.locations.len(), 2);
+    assert_eq!(cached.arguments[0].name.value, "ttl");
+}
+
+#[test]
+fn it_errs_for_an_invalid_introspection_result() {
+    assert!(schema_from_introspection("{").is_err());
+    assert!(schema_from_introspection(r#"{ "data": {} }"#).is_err());
+
+    let error = schema_from_introspection(
+        r#"{ "__schema": { "types": [{ "kind": "OBJECT", "fields": [] }] } }"#,
+    )
+    .unwrap_err();
+    assert_eq!(error.message, "Missing `name` in the introspection result");
+}
@@ -0,0 +1,1836 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::helpers::is_valid_name;
+use crate::lexer::lex_with_options;
+use crate::lexer::types::{LexerOptions, LexicalTokenType};
+use crate::lsp::transport::{read_lsp_message, write_lsp_message};
+use crate::lsp::types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use crate::parser::types::{
+    Definition, DirectiveDefinition, DirectiveLocation, Document, Field, FieldDefinition,
+    FragmentSpread, Name, Node, SelectionSet, StringValue, Value as AstValue, Variable,
+};
+use crate::parser::{parse, parse_type_system};
+use crate::print::pretty_print::{print_with_options, IndentStyle, PrintOptions};
+use crate::schema::{schema_from_introspection, Schema, TypeDefinition};
+use crate::server::semantic_tokens::{semantic_tokens, SEMANTIC_TOKEN_TYPES};
+use crate::visitor::{
+    directive_location_at, field_at_position, node_at_position, type_info_at_position,
+    walk_document, AstNodeRef, VariableUses, Visitor, VisitorAction, VisitorContext,
+};
+
+pub mod semantic_tokens;
+mod tests;
+
+/// https://www.jsonrpc.org/specification#error_object
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const SERVER_NOT_INITIALIZED: i64 = -32002;
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#errorCodes
+pub const REQUEST_FAILED: i64 = -32803;
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#messageType
+const MESSAGE_TYPE_ERROR: u64 = 1;
+
+/// Schema loads that finish sooner than this only report their beginning and
+/// end, rather than every step along the way.
+const PROGRESS_REPORT_DELAY: Duration = Duration::from_millis(100);
+
+/// Everything the server knows about a document the client has opened.
+#[derive(Debug, Clone)]
+pub struct DocumentState {
+    pub text: String,
+    pub version: i64,
+    pub language_id: String,
+    /// `None` when the text failed to parse
+    pub document: Option<Document>,
+    /// Parse errors and warnings for the current text
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl DocumentState {
+    pub fn new(text: String, version: i64, language_id: String) -> DocumentState {
+        let (document, diagnostics) = match parse(&text) {
+            Ok(result) => (Some(result.document), result.diagnostics),
+            Err(diagnostics) => (None, diagnostics),
+        };
+
+        DocumentState {
+            text,
+            version,
+            language_id,
+            document,
+            diagnostics,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ServerState {
+    pub documents: HashMap<String, DocumentState>,
+    pub schema: Option<Schema>,
+    /// The URI of the SDL file the schema was loaded from, if any. Positions
+    /// in the schema refer to this file.
+    pub schema_uri: Option<String>,
+    /// The file named by the `schemaPath` or `schemaIntrospectionPath`
+    /// initialization option.
+    schema_file: Option<SchemaFile>,
+    /// Set when the schema file should be (re)loaded once the messages for
+    /// the current one have been sent.
+    schema_load_pending: bool,
+    /// Whether the client accepts progress created by the server.
+    work_done_progress: bool,
+    /// The id of the `window/workDoneProgress/create` request the next load
+    /// waits on, and the token it asks for.
+    progress_request: Option<(i64, String)>,
+    /// The token the client created for the next load to report on.
+    progress_token: Option<String>,
+    /// Whether the client lets the server register file watchers.
+    watched_files_registration: bool,
+    /// The id of the last request the server sent to the client.
+    last_request_id: i64,
+    /// Whether the client can resolve the edits of code actions later, so
+    /// they can be left out of `textDocument/codeAction` responses.
+    resolve_code_action_edits: bool,
+    initialized: bool,
+    shutdown_requested: bool,
+}
+
+/// Where the schema is loaded from, and in which format.
+#[derive(Debug, Clone, PartialEq)]
+enum SchemaFile {
+    Sdl(PathBuf),
+    /// The JSON result of an introspection query.
+    Introspection(PathBuf),
+}
+
+impl SchemaFile {
+    fn path(&self) -> &Path {
+        match self {
+            SchemaFile::Sdl(path) | SchemaFile::Introspection(path) => path,
+        }
+    }
+}
+
+/// The error half of a response. Handlers return this to have the server
+/// answer the request with an error instead of a result.
+#[derive(Debug, Clone)]
+pub struct ResponseError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl ResponseError {
+    pub fn new(code: i64, message: impl Into<String>) -> ResponseError {
+        ResponseError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({ "code": self.code, "message": self.message })
+    }
+}
+
+/// Runs the language server until the client sends `exit` or closes the
+/// connection.
+pub fn serve(mut stdin: impl BufRead, mut stdout: impl Write) -> Result<(), io::Error> {
+    let mut state = ServerState::default();
+
+    loop {
+        let message = match read_lsp_message(&mut stdin) {
+            Ok(message) => message,
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(error) => return Err(error),
+        };
+
+        if message.get("method").and_then(Value::as_str) == Some("exit") {
+            return Ok(());
+        }
+
+        for outgoing in handle_message(&mut state, &message) {
+            write_lsp_message(&mut stdout, &outgoing)?;
+        }
+
+        // loading happens after the response has been written, so that
+        // progress reaches the client while the schema is loading
+        if state.schema_load_pending {
+            state.schema_load_pending = false;
+            load_schema(&mut state, &mut |outgoing| {
+                write_lsp_message(&mut stdout, &outgoing)
+            })?;
+        }
+    }
+}
+
+/// Handles one incoming message and returns the messages to send back, in
+/// order. Requests always produce exactly one response.
+fn handle_message(state: &mut ServerState, message: &Value) -> Vec<Value> {
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        handle_response(state, message);
+        return vec![];
+    };
+
+    trace!("received {}", method);
+
+    let params = message.get("params").unwrap_or(&Value::Null);
+    let mut outgoing = Vec::new();
+
+    match message.get("id") {
+        Some(id) => {
+            let result = handle_request(state, method, params);
+            outgoing.push(response(id, result));
+        }
+        None => handle_notification(state, method, params, &mut outgoing),
+    }
+
+    outgoing
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#requestMessage
+fn handle_request(
+    state: &mut ServerState,
+    method: &str,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    if state.shutdown_requested {
+        return Err(ResponseError::new(
+            INVALID_REQUEST,
+            "Server is shutting down",
+        ));
+    }
+
+    if method == "initialize" {
+        return handle_initialize(state, params);
+    }
+
+    if !state.initialized {
+        return Err(ResponseError::new(
+            SERVER_NOT_INITIALIZED,
+            "Server has not been initialized",
+        ));
+    }
+
+    match method {
+        "shutdown" => handle_shutdown(state),
+        "textDocument/formatting" => handle_text_document_formatting(state, params),
+        "textDocument/hover" => handle_text_document_hover(state, params),
+        "textDocument/completion" => handle_text_document_completion(state, params),
+        "textDocument/definition" => handle_text_document_definition(state, params),
+        "textDocument/documentSymbol" => handle_text_document_document_symbol(state, params),
+        "textDocument/signatureHelp" => handle_text_document_signature_help(state, params),
+        "textDocument/foldingRange" => handle_text_document_folding_range(state, params),
+        "textDocument/rename" => handle_text_document_rename(state, params),
+        "textDocument/codeAction" => handle_text_document_code_action(state, params),
+        "codeAction/resolve" => handle_code_action_resolve(params),
+        "textDocument/semanticTokens/full" => {
+            handle_text_document_semantic_tokens_full(state, params)
+        }
+        _ => Err(ResponseError::new(
+            METHOD_NOT_FOUND,
+            format!("Unhandled method: {}", method),
+        )),
+    }
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#notificationMessage
+fn handle_notification(
+    state: &mut ServerState,
+    method: &str,
+    params: &Value,
+    outgoing: &mut Vec<Value>,
+) {
+    // notifications sent before `initialize` are dropped
+    if !state.initialized {
+        return;
+    }
+
+    match method {
+        "textDocument/didOpen" => handle_text_document_did_open(state, params, outgoing),
+        "textDocument/didChange" => handle_text_document_did_change(state, params, outgoing),
+        "textDocument/didClose" => handle_text_document_did_close(state, params),
+        "workspace/didChangeWatchedFiles" => {
+            handle_workspace_did_change_watched_files(state, params, outgoing)
+        }
+        "initialized" => handle_initialized(state, outgoing),
+        // unknown notifications, including `$/` ones, may be ignored
+        _ => {}
+    }
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#initialize
+pub fn handle_initialize(state: &mut ServerState, params: &Value) -> Result<Value, ResponseError> {
+    if state.initialized {
+        return Err(ResponseError::new(
+            INVALID_REQUEST,
+            "Server is already initialized",
+        ));
+    }
+
+    state.initialized = true;
+    state.resolve_code_action_edits = params["capabilities"]["textDocument"]["codeAction"]
+        ["resolveSupport"]["properties"]
+        .as_array()
+        .is_some_and(|properties| properties.contains(&json!("edit")));
+    state.work_done_progress = params["capabilities"]["window"]["workDoneProgress"] == true;
+    state.watched_files_registration =
+        params["capabilities"]["workspace"]["didChangeWatchedFiles"]["dynamicRegistration"] == true;
+
+    let root = params["rootUri"]
+        .as_str()
+        .and_then(uri_to_path)
+        .or_else(|| params["rootPath"].as_str().map(PathBuf::from));
+    let resolve = |path: &str| match &root {
+        Some(root) => root.join(path),
+        None => PathBuf::from(path),
+    };
+
+    let options = &params["initializationOptions"];
+    if let Some(schema_path) = options["schemaPath"].as_str() {
+        state.schema_file = Some(SchemaFile::Sdl(resolve(schema_path)));
+    } else if let Some(schema_path) = options["schemaIntrospectionPath"].as_str() {
+        state.schema_file = Some(SchemaFile::Introspection(resolve(schema_path)));
+    }
+
+    Ok(json!({
+        "capabilities": {
+            // full sync, every change sends the whole text
+            "textDocumentSync": 1,
+            "documentFormattingProvider": true,
+            "hoverProvider": true,
+            "completionProvider": { "triggerCharacters": ["@"] },
+            "definitionProvider": true,
+            "documentSymbolProvider": true,
+            "signatureHelpProvider": { "triggerCharacters": ["(", ","] },
+            "foldingRangeProvider": true,
+            "renameProvider": true,
+            "codeActionProvider": {
+                "codeActionKinds": ["quickfix"],
+                "resolveProvider": true,
+            },
+            "workDoneProgress": true,
+            "semanticTokensProvider": {
+                "legend": { "tokenTypes": SEMANTIC_TOKEN_TYPES, "tokenModifiers": [] },
+                "full": true,
+            },
+        },
+        "serverInfo": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+    }))
+}
+
+/// Loads the schema, and asks the client to watch the schema file so that it
+/// is reloaded when it changes. Clients only send
+/// `workspace/didChangeWatchedFiles` for the files a server registers.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#initialized
+pub fn handle_initialized(state: &mut ServerState, outgoing: &mut Vec<Value>) {
+    let Some(schema_file) = &state.schema_file else {
+        return;
+    };
+
+    if state.watched_files_registration {
+        register_schema_watcher(state, schema_file.path().to_path_buf(), outgoing);
+    }
+
+    request_schema_load(state, outgoing);
+}
+
+fn register_schema_watcher(
+    state: &mut ServerState,
+    schema_path: PathBuf,
+    outgoing: &mut Vec<Value>,
+) {
+    let glob_pattern = schema_path.to_string_lossy().into_owned();
+    state.last_request_id += 1;
+    outgoing.push(request(
+        state.last_request_id,
+        "client/registerCapability",
+        json!({
+            "registrations": [{
+                "id": "gql_lsp/schema",
+                "method": "workspace/didChangeWatchedFiles",
+                "registerOptions": { "watchers": [{ "globPattern": glob_pattern }] },
+            }]
+        }),
+    ));
+}
+
+/// Queues a load of the schema file. When the client supports progress, the
+/// load waits until the client has created the token to report it with.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#window_workDoneProgress_create
+fn request_schema_load(state: &mut ServerState, outgoing: &mut Vec<Value>) {
+    if state.schema_file.is_none() {
+        return;
+    }
+
+    if !state.work_done_progress {
+        state.schema_load_pending = true;
+        return;
+    }
+
+    state.last_request_id += 1;
+    let token = format!("gql_lsp/schema/{}", state.last_request_id);
+    outgoing.push(request(
+        state.last_request_id,
+        "window/workDoneProgress/create",
+        json!({ "token": token }),
+    ));
+    state.progress_request = Some((state.last_request_id, token));
+}
+
+/// Handles the client's response to a request the server sent. Only the
+/// response to `window/workDoneProgress/create` matters: the token may only
+/// be used if it was created, but the schema is loaded either way.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#responseMessage
+fn handle_response(state: &mut ServerState, message: &Value) {
+    let Some((request_id, _)) = &state.progress_request else {
+        return;
+    };
+
+    if message["id"].as_i64() != Some(*request_id) {
+        return;
+    }
+
+    let (_, token) = state.progress_request.take().unwrap();
+    state.progress_token = message["error"].is_null().then_some(token);
+    state.schema_load_pending = true;
+}
+
+/// Reads and parses the schema file, sending each message as soon as it is
+/// ready. With a progress token the load is reported as progress. A schema
+/// that fails to load leaves the server without one, and the user is told
+/// why.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#workDoneProgress
+fn load_schema(
+    state: &mut ServerState,
+    send: &mut impl FnMut(Value) -> Result<(), io::Error>,
+) -> Result<(), io::Error> {
+    let Some(schema_file) = state.schema_file.clone() else {
+        return Ok(());
+    };
+    let schema_path = schema_file.path();
+    let file_name = schema_path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let token = state.progress_token.take();
+    if let Some(token) = &token {
+        send(progress(
+            token,
+            json!({ "kind": "begin", "title": "Loading GraphQL schema", "message": file_name }),
+        ))?;
+    }
+
+    let started = Instant::now();
+    let mut report = |message: String, percentage: u32| match &token {
+        Some(token) if started.elapsed() > PROGRESS_REPORT_DELAY => send(progress(
+            token,
+            json!({ "kind": "report", "message": message, "percentage": percentage }),
+        )),
+        _ => Ok(()),
+    };
+
+    let result = match fs::read_to_string(schema_path) {
+        Ok(source) => {
+            report(format!("Parsing {}", file_name), 50)?;
+            let result = match &schema_file {
+                SchemaFile::Sdl(_) => parse_type_system(&source)
+                    .map(|document| Schema::from_document(&document))
+                    .map_err(|diagnostic| {
+                        format!(
+                            "{}:{}:{}: {}",
+                            schema_path.display(),
+                            diagnostic.range.start.line + 1,
+                            diagnostic.range.start.character + 1,
+                            diagnostic.message
+                        )
+                    }),
+                SchemaFile::Introspection(_) => schema_from_introspection(&source)
+                    .map_err(|error| format!("{}: {}", schema_path.display(), error)),
+            };
+            report(format!("Parsed {}", file_name), 100)?;
+            result
+        }
+        Err(error) => Err(format!("{}: {}", schema_path.display(), error)),
+    };
+
+    if let Some(token) = &token {
+        send(progress(token, json!({ "kind": "end" })))?;
+    }
+
+    trace!(
+        "loaded {} in {:?}",
+        schema_path.display(),
+        started.elapsed()
+    );
+
+    match result {
+        Ok(schema) => {
+            state.schema = Some(schema);
+            // an introspection result has no positions to point into
+            state.schema_uri = match schema_file {
+                SchemaFile::Sdl(_) => Some(path_to_uri(schema_path)),
+                SchemaFile::Introspection(_) => None,
+            };
+        }
+        Err(message) => {
+            state.schema = None;
+            state.schema_uri = None;
+            send(notification(
+                "window/showMessage",
+                json!({
+                    "type": MESSAGE_TYPE_ERROR,
+                    "message": format!("Failed to load schema: {}", message),
+                }),
+            ))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reloads the schema when its file changes on disk.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#workspace_didChangeWatchedFiles
+pub fn handle_workspace_did_change_watched_files(
+    state: &mut ServerState,
+    params: &Value,
+    outgoing: &mut Vec<Value>,
+) {
+    let Some(schema_file) = &state.schema_file else {
+        return;
+    };
+    let schema_path = schema_file.path();
+
+    let schema_changed = params["changes"].as_array().is_some_and(|changes| {
+        changes.iter().any(|change| {
+            change["uri"].as_str().and_then(uri_to_path).as_deref() == Some(schema_path)
+        })
+    });
+
+    if schema_changed {
+        request_schema_load(state, outgoing);
+    }
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#shutdown
+pub fn handle_shutdown(state: &mut ServerState) -> Result<Value, ResponseError> {
+    state.shutdown_requested = true;
+    Ok(Value::Null)
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_didOpen
+pub fn handle_text_document_did_open(
+    state: &mut ServerState,
+    params: &Value,
+    outgoing: &mut Vec<Value>,
+) {
+    let text_document = &params["textDocument"];
+    let (Some(uri), Some(text)) = (
+        text_document["uri"].as_str(),
+        text_document["text"].as_str(),
+    ) else {
+        return;
+    };
+
+    let document = DocumentState::new(
+        text.to_string(),
+        text_document["version"].as_i64().unwrap_or(0),
+        text_document["languageId"]
+            .as_str()
+            .unwrap_or("graphql")
+            .to_string(),
+    );
+
+    outgoing.push(publish_diagnostics(uri, &document));
+    state.documents.insert(uri.to_string(), document);
+}
+
+/// Only full sync is advertised, so the last change holds the entire text.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_didChange
+pub fn handle_text_document_did_change(
+    state: &mut ServerState,
+    params: &Value,
+    outgoing: &mut Vec<Value>,
+) {
+    let Some(uri) = params["textDocument"]["uri"].as_str() else {
+        return;
+    };
+    let Some(text) = params["contentChanges"]
+        .as_array()
+        .and_then(|changes| changes.last())
+        .and_then(|change| change["text"].as_str())
+    else {
+        return;
+    };
+
+    let version = params["textDocument"]["version"].as_i64().unwrap_or(0);
+    let language_id = state
+        .documents
+        .get(uri)
+        .map(|document| document.language_id.clone())
+        .unwrap_or_else(|| "graphql".to_string());
+
+    let document = DocumentState::new(text.to_string(), version, language_id);
+
+    outgoing.push(publish_diagnostics(uri, &document));
+    state.documents.insert(uri.to_string(), document);
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_didClose
+pub fn handle_text_document_did_close(state: &mut ServerState, params: &Value) {
+    if let Some(uri) = params["textDocument"]["uri"].as_str() {
+        state.documents.remove(uri);
+    }
+}
+
+/// Formats the whole document with the pretty printer. Documents that don't
+/// parse can't be printed, so they're answered with an error.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_formatting
+pub fn handle_text_document_formatting(
+    state: &ServerState,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    let document_state = get_document_state(state, params)?;
+    let Some(document) = &document_state.document else {
+        return Err(ResponseError::new(
+            REQUEST_FAILED,
+            "Unable to format a document with syntax errors",
+        ));
+    };
+
+    // the printer works from the AST, which has no comments, so formatting
+    // would delete them
+    let has_comments = lex_with_options(
+        &document_state.text,
+        LexerOptions {
+            preserve_comments: true,
+            ..LexerOptions::default()
+        },
+    )
+    .unwrap_or_default()
+    .iter()
+    .any(|token| matches!(token.token_type, LexicalTokenType::Comment(_)));
+
+    if has_comments {
+        return Err(ResponseError::new(
+            REQUEST_FAILED,
+            "Unable to format a document with comments without losing them",
+        ));
+    }
+
+    let options = &params["options"];
+    let tab_size = options["tabSize"].as_u64().unwrap_or(2) as usize;
+    let options = if options["insertSpaces"].as_bool().unwrap_or(true) {
+        PrintOptions {
+            indent_style: IndentStyle::Spaces,
+            indent_width: tab_size,
+        }
+    } else {
+        PrintOptions {
+            indent_style: IndentStyle::Tabs,
+            indent_width: 1,
+        }
+    };
+
+    let range = Range::new(Position::ZERO, end_of_text(&document_state.text));
+
+    Ok(json!([{
+        "range": range_to_json(&range),
+        "newText": print_with_options(document, &options),
+    }]))
+}
+
+/// Describes the field, type or directive under the cursor using the loaded
+/// schema. Anything else, or anything the schema doesn't know about, has no
+/// hover.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_hover
+pub fn handle_text_document_hover(
+    state: &ServerState,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    let document_state = get_document_state(state, params)?;
+    let (Some(schema), Some(document)) = (&state.schema, &document_state.document) else {
+        return Ok(Value::Null);
+    };
+    let Some(position) = position_from_json(&document_state.text, &params["position"]) else {
+        return Err(ResponseError::new(INVALID_PARAMS, "Invalid position"));
+    };
+
+    let hover = match node_at_position(document, &position) {
+        Some(AstNodeRef::Field(field)) => type_info_at_position(schema, document, &position)
+            .field_def
+            .map(|field_def| {
+                let mut value = format!("**{}**: {}", field_def.name.value, field_def.field_type);
+                push_description(&mut value, field_def.description.as_ref());
+                (value, field.name.position())
+            }),
+        Some(AstNodeRef::NamedType(named_type)) => {
+            schema.get_type(&named_type.name.value).map(|definition| {
+                let mut value = format!("**{}**", definition.name());
+                push_description(&mut value, type_description(definition));
+                (value, named_type.position())
+            })
+        }
+        Some(AstNodeRef::Directive(directive)) => {
+            schema
+                .directives
+                .get(&directive.name.value)
+                .map(|definition| {
+                    let mut value = format!("**@{}**", definition.name.value);
+                    push_description(&mut value, definition.description.as_ref());
+                    (value, directive.position())
+                })
+        }
+        _ => None,
+    };
+
+    Ok(match hover {
+        Some((value, range)) => json!({
+            "contents": { "kind": "markdown", "value": value },
+            "range": range_to_json(range),
+        }),
+        None => Value::Null,
+    })
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#completionItemKind
+const COMPLETION_ITEM_KIND_FUNCTION: u64 = 3;
+const COMPLETION_ITEM_KIND_FIELD: u64 = 5;
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#insertTextFormat
+const INSERT_TEXT_FORMAT_SNIPPET: u64 = 2;
+
+const DIRECTIVE_NAME_PLACEHOLDER: &str = "__completion";
+
+/// Suggests directives right after an `@`, and otherwise the fields of the
+/// type the selection set under the cursor selects from.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_completion
+pub fn handle_text_document_completion(
+    state: &ServerState,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    let document_state = get_document_state(state, params)?;
+    let Some(schema) = &state.schema else {
+        return Ok(json!([]));
+    };
+    let Some(position) = position_from_json(&document_state.text, &params["position"]) else {
+        return Err(ResponseError::new(INVALID_PARAMS, "Invalid position"));
+    };
+
+    if document_state.text[..position.offset].ends_with('@') {
+        return Ok(directive_completion_items(
+            schema,
+            &document_state.text,
+            &position,
+        ));
+    }
+
+    let Some(document) = &document_state.document else {
+        return Ok(json!([]));
+    };
+    let Some(parent_type) = type_info_at_position(schema, document, &position).parent_type else {
+        return Ok(json!([]));
+    };
+
+    let items = parent_type
+        .fields()
+        .iter()
+        .map(field_completion_item)
+        .collect::<Vec<Value>>();
+
+    Ok(Value::Array(items))
+}
+
+/// A bare `@` doesn't parse, so the location of the directive is found in a
+/// copy of the text with a placeholder name after it. Only the directives
+/// allowed at that location are suggested.
+fn directive_completion_items(schema: &Schema, text: &str, position: &Position) -> Value {
+    let mut text = text.to_string();
+    text.insert_str(position.offset, DIRECTIVE_NAME_PLACEHOLDER);
+
+    let Some(location) = parse(&text)
+        .ok()
+        .and_then(|result| directive_location_at(&result.document, position))
+    else {
+        return json!([]);
+    };
+    let location = DirectiveLocation::Executable(location);
+
+    let mut definitions = schema
+        .directives
+        .values()
+        .filter(|definition| definition.locations.contains(&location))
+        .collect::<Vec<&DirectiveDefinition>>();
+    definitions.sort_by(|a, b| a.name.value.cmp(&b.name.value));
+
+    let items = definitions
+        .into_iter()
+        .map(directive_completion_item)
+        .collect::<Vec<Value>>();
+
+    Value::Array(items)
+}
+
+/// Directives with arguments complete with a snippet that has a tab stop for
+/// each of them.
+fn directive_completion_item(definition: &DirectiveDefinition) -> Value {
+    let signature = definition
+        .arguments
+        .iter()
+        .map(|argument| format!("{}: {}", argument.name.value, argument.input_type))
+        .collect::<Vec<String>>();
+
+    let mut item = json!({
+        "label": definition.name.value,
+        "kind": COMPLETION_ITEM_KIND_FUNCTION,
+        "detail": if signature.is_empty() {
+            format!("@{}", definition.name.value)
+        } else {
+            format!("@{}({})", definition.name.value, signature.join(", "))
+        },
+    });
+
+    if let Some(description) = &definition.description {
+        item["documentation"] = json!(description.value);
+    }
+
+    if !definition.arguments.is_empty() {
+        let arguments = definition
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(index, argument)| format!("{}: ${}", argument.name.value, index + 1))
+            .collect::<Vec<String>>();
+
+        item["insertText"] = json!(format!(
+            "{}({})",
+            definition.name.value,
+            arguments.join(", ")
+        ));
+        item["insertTextFormat"] = json!(INSERT_TEXT_FORMAT_SNIPPET);
+    }
+
+    item
+}
+
+/// Fields with required arguments complete with a snippet that has a tab
+/// stop for each of them.
+fn field_completion_item(field_def: &FieldDefinition) -> Value {
+    let mut item = json!({
+        "label": field_def.name.value,
+        "kind": COMPLETION_ITEM_KIND_FIELD,
+        "detail": field_def.field_type.to_string(),
+    });
+
+    if let Some(description) = &field_def.description {
+        item["documentation"] = json!(description.value);
+    }
+
+    let required_arguments = field_def
+        .arguments
+        .iter()
+        .filter(|argument| argument.is_required())
+        .enumerate()
+        .map(|(index, argument)| format!("{}: ${}", argument.name.value, index + 1))
+        .collect::<Vec<String>>();
+
+    if !required_arguments.is_empty() {
+        item["insertText"] = json!(format!(
+            "{}({})",
+            field_def.name.value,
+            required_arguments.join(", ")
+        ));
+        item["insertTextFormat"] = json!(INSERT_TEXT_FORMAT_SNIPPET);
+    }
+
+    item
+}
+
+fn push_description(value: &mut String, description: Option<&StringValue>) {
+    if let Some(description) = description {
+        value.push_str("\n\n");
+        value.push_str(&description.value);
+    }
+}
+
+fn type_description(definition: &TypeDefinition) -> Option<&StringValue> {
+    match definition {
+        TypeDefinition::Scalar(definition) => definition.description.as_ref(),
+        TypeDefinition::Object(definition) => definition.description.as_ref(),
+        TypeDefinition::Interface(definition) => definition.description.as_ref(),
+        TypeDefinition::Union(definition) => definition.description.as_ref(),
+        TypeDefinition::Enum(definition) => definition.description.as_ref(),
+        TypeDefinition::InputObject(definition) => definition.description.as_ref(),
+    }
+}
+
+/// Jumps from a fragment spread to its fragment, from a named type to its
+/// definition in any open document, and from a field to its definition in the
+/// schema file.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_definition
+pub fn handle_text_document_definition(
+    state: &ServerState,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    let document_state = get_document_state(state, params)?;
+    let Some(document) = &document_state.document else {
+        return Ok(Value::Null);
+    };
+    let Some(position) = position_from_json(&document_state.text, &params["position"]) else {
+        return Err(ResponseError::new(INVALID_PARAMS, "Invalid position"));
+    };
+    let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+    let location = match node_at_position(document, &position) {
+        Some(AstNodeRef::FragmentSpread(fragment_spread)) => document
+            .find_fragment(&fragment_spread.name.value)
+            .map(|fragment| location(uri, fragment.position())),
+        Some(AstNodeRef::NamedType(named_type)) => {
+            find_type_definition(state, uri, &named_type.name.value)
+        }
+        Some(AstNodeRef::Field(_)) => state.schema.as_ref().and_then(|schema| {
+            let schema_uri = state.schema_uri.as_ref()?;
+            let field_def = type_info_at_position(schema, document, &position).field_def?;
+            Some(location(schema_uri, field_def.position()))
+        }),
+        _ => None,
+    };
+
+    Ok(location.unwrap_or(Value::Null))
+}
+
+/// Searches the open documents for the definition of a type, starting with
+/// the current one.
+fn find_type_definition(state: &ServerState, uri: &str, name: &str) -> Option<Value> {
+    let mut uris = state.documents.keys().collect::<Vec<&String>>();
+    uris.sort_by_key(|document_uri| (document_uri.as_str() != uri, *document_uri));
+
+    uris.into_iter().find_map(|document_uri| {
+        let document = state.documents[document_uri].document.as_ref()?;
+        document
+            .definitions
+            .iter()
+            .find(|definition| {
+                is_type_definition(definition)
+                    && definition
+                        .name()
+                        .is_some_and(|definition_name| definition_name.value == name)
+            })
+            .map(|definition| location(document_uri, definition.position()))
+    })
+}
+
+fn is_type_definition(definition: &Definition) -> bool {
+    matches!(
+        definition,
+        Definition::ScalarTypeDefinition(_)
+            | Definition::ObjectTypeDefinition(_)
+            | Definition::InterfaceTypeDefinition(_)
+            | Definition::UnionTypeDefinition(_)
+            | Definition::EnumTypeDefinition(_)
+            | Definition::InputObjectTypeDefinition(_)
+    )
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#location
+fn location(uri: &str, range: &Range) -> Value {
+    json!({ "uri": uri, "range": range_to_json(range) })
+}
+
+/// Shows the arguments of the field whose argument list the cursor is in. The
+/// active parameter is the number of commas between the opening parenthesis
+/// and the cursor.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_signatureHelp
+pub fn handle_text_document_signature_help(
+    state: &ServerState,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    let document_state = get_document_state(state, params)?;
+    let (Some(schema), Some(document)) = (&state.schema, &document_state.document) else {
+        return Ok(Value::Null);
+    };
+    let Some(position) = position_from_json(&document_state.text, &params["position"]) else {
+        return Err(ResponseError::new(INVALID_PARAMS, "Invalid position"));
+    };
+
+    let Some(field) = field_at_position(document, &position) else {
+        return Ok(Value::Null);
+    };
+    let Some(field_def) = type_info_at_position(schema, document, &position).field_def else {
+        return Ok(Value::Null);
+    };
+    let Some(active_parameter) = active_argument(
+        &document_state.text,
+        field.name.position.end.offset,
+        position.offset,
+    ) else {
+        return Ok(Value::Null);
+    };
+
+    let parameters = field_def
+        .arguments
+        .iter()
+        .map(|argument| {
+            let mut parameter = json!({
+                "label": format!("{}: {}", argument.name.value, argument.input_type),
+            });
+            if let Some(description) = &argument.description {
+                parameter["documentation"] = json!(description.value);
+            }
+            parameter
+        })
+        .collect::<Vec<Value>>();
+
+    let label = format!(
+        "{}({}): {}",
+        field_def.name.value,
+        parameters
+            .iter()
+            .map(|parameter| parameter["label"].as_str().unwrap_or_default())
+            .collect::<Vec<&str>>()
+            .join(", "),
+        field_def.field_type
+    );
+
+    let mut signature = json!({ "label": label, "parameters": parameters });
+    if let Some(description) = &field_def.description {
+        signature["documentation"] = json!(description.value);
+    }
+
+    Ok(json!({
+        "signatures": [signature],
+        "activeSignature": 0,
+        "activeParameter": active_parameter,
+    }))
+}
+
+/// Counts the commas between the argument list's opening parenthesis and the
+/// cursor, skipping any inside nested values or strings. `None` when the
+/// cursor isn't inside the argument list that follows `name_end`.
+fn active_argument(text: &str, name_end: usize, cursor: usize) -> Option<usize> {
+    let arguments = &text[name_end..];
+    let open = arguments.len() - arguments.trim_start().len();
+    if !arguments[open..].starts_with('(') {
+        return None;
+    }
+
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut commas = 0;
+
+    for (index, c) in arguments
+        .char_indices()
+        .skip_while(|(index, _)| *index < open)
+    {
+        let offset = name_end + index;
+        if offset >= cursor {
+            return (depth > 0).then_some(commas);
+        }
+
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return None;
+                }
+            }
+            ',' if depth == 1 => commas += 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_semanticTokens
+pub fn handle_text_document_semantic_tokens_full(
+    state: &ServerState,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    let document_state = get_document_state(state, params)?;
+    let Some(document) = &document_state.document else {
+        return Ok(Value::Null);
+    };
+
+    Ok(json!({ "data": semantic_tokens(&document_state.text, document) }))
+}
+
+/// Folds every multi-line top-level definition and selection set, and every
+/// run of comments on consecutive lines.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_foldingRange
+pub fn handle_text_document_folding_range(
+    state: &ServerState,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    let document_state = get_document_state(state, params)?;
+    let Some(document) = &document_state.document else {
+        return Ok(Value::Null);
+    };
+
+    let mut collector = FoldingRangeCollector::default();
+    for definition in &document.definitions {
+        collector.push(definition.position(), "region");
+    }
+    walk_document(&mut collector, document);
+
+    let comment_lines = lex_with_options(
+        &document_state.text,
+        LexerOptions {
+            preserve_comments: true,
+            ..LexerOptions::default()
+        },
+    )
+    .unwrap_or_default()
+    .into_iter()
+    .filter(|token| matches!(token.token_type, LexicalTokenType::Comment(_)))
+    .map(|token| token.position.start.line);
+
+    let mut comment_run: Option<(usize, usize)> = None;
+    for line in comment_lines.chain([usize::MAX]) {
+        match comment_run {
+            Some((start, end)) if line == end + 1 => comment_run = Some((start, line)),
+            Some((start, end)) => {
+                collector.push_lines(start, end, "comment");
+                comment_run = Some((line, line));
+            }
+            None => comment_run = Some((line, line)),
+        }
+    }
+
+    let mut ranges = collector.ranges;
+    ranges.sort_by_key(|(start, end, _)| (*start, std::cmp::Reverse(*end)));
+    ranges.dedup();
+
+    let ranges = ranges
+        .into_iter()
+        .map(|(start, end, kind)| json!({ "startLine": start, "endLine": end, "kind": kind }))
+        .collect::<Vec<Value>>();
+
+    Ok(Value::Array(ranges))
+}
+
+#[derive(Default)]
+struct FoldingRangeCollector {
+    ranges: Vec<(usize, usize, &'static str)>,
+}
+
+impl FoldingRangeCollector {
+    fn push(&mut self, range: &Range, kind: &'static str) {
+        self.push_lines(range.start.line, range.end.line, kind);
+    }
+
+    /// Ranges on a single line have nothing to fold.
+    fn push_lines(&mut self, start: usize, end: usize, kind: &'static str) {
+        if start < end {
+            self.ranges.push((start, end, kind));
+        }
+    }
+}
+
+impl Visitor for FoldingRangeCollector {
+    fn enter_selection_set(&mut self, _ctx: &VisitorContext, node: &SelectionSet) -> VisitorAction {
+        self.push(node.position(), "region");
+        VisitorAction::Continue
+    }
+}
+
+/// Renames the fragment or variable whose name is under the cursor. Fragments
+/// are renamed across the open documents, variables within their operation.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_rename
+pub fn handle_text_document_rename(
+    state: &ServerState,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    let document_state = get_document_state(state, params)?;
+    let Some(document) = &document_state.document else {
+        return Ok(Value::Null);
+    };
+    let Some(position) = position_from_json(&document_state.text, &params["position"]) else {
+        return Err(ResponseError::new(INVALID_PARAMS, "Invalid position"));
+    };
+    let new_name = params["newName"].as_str().unwrap_or_default();
+
+    match node_at_position(document, &position) {
+        Some(AstNodeRef::FragmentDefinition(fragment)) => {
+            rename_fragment(state, &fragment.name, &position, new_name)
+        }
+        Some(AstNodeRef::FragmentSpread(fragment_spread)) => {
+            rename_fragment(state, &fragment_spread.name, &position, new_name)
+        }
+        Some(AstNodeRef::VariableDefinition(variable_definition)) => rename_variable(
+            document,
+            &variable_definition.variable,
+            &position,
+            new_name,
+            params,
+        ),
+        Some(AstNodeRef::Argument(argument)) => match variable_at(&argument.value, &position) {
+            Some(variable) => rename_variable(document, variable, &position, new_name, params),
+            None => Ok(Value::Null),
+        },
+        _ => Ok(Value::Null),
+    }
+}
+
+fn rename_fragment(
+    state: &ServerState,
+    fragment_name: &Name,
+    position: &Position,
+    new_name: &str,
+) -> Result<Value, ResponseError> {
+    if !fragment_name.position().contains(position) {
+        return Ok(Value::Null);
+    }
+
+    // https://spec.graphql.org/October2021/#FragmentName
+    if !is_valid_name(new_name) || new_name == "on" {
+        return Err(ResponseError::new(
+            INVALID_PARAMS,
+            format!("Invalid fragment name: {}", new_name),
+        ));
+    }
+
+    let mut changes = serde_json::Map::new();
+    for (uri, document_state) in &state.documents {
+        let Some(document) = &document_state.document else {
+            continue;
+        };
+
+        let mut collector = FragmentNameCollector {
+            name: &fragment_name.value,
+            ranges: Vec::new(),
+        };
+        for fragment in document.fragments() {
+            if fragment.name.value == fragment_name.value {
+                collector.ranges.push(fragment.name.position.clone());
+            }
+        }
+        walk_document(&mut collector, document);
+
+        if !collector.ranges.is_empty() {
+            changes.insert(uri.clone(), text_edits(&collector.ranges, new_name));
+        }
+    }
+
+    Ok(json!({ "changes": changes }))
+}
+
+/// Renames a variable in the operation under the cursor, in its definition
+/// and every use of it, including uses in the fragments the operation spreads
+/// directly or transitively. Variables in fragments outside an operation
+/// can't be renamed, since they belong to whichever operation spreads them.
+fn rename_variable(
+    document: &Document,
+    variable: &Variable,
+    position: &Position,
+    new_name: &str,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    let Some(operation) = document
+        .operations()
+        .find(|operation| operation.position().contains(position))
+    else {
+        return Ok(Value::Null);
+    };
+    if !variable.position().contains(position) {
+        return Ok(Value::Null);
+    }
+
+    // the `$` is part of the variable, not its name
+    let new_name = new_name.strip_prefix('$').unwrap_or(new_name);
+    if !is_valid_name(new_name) {
+        return Err(ResponseError::new(
+            INVALID_PARAMS,
+            format!("Invalid variable name: {}", new_name),
+        ));
+    }
+
+    let name = &variable.name.value;
+    let mut ranges = VariableUses::collect(name, operation, document);
+    for variable_definition in &operation.variable_definitions {
+        if &variable_definition.variable.name.value == name {
+            ranges.push(variable_definition.variable.name.position.clone());
+        }
+    }
+
+    let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+    Ok(json!({ "changes": { uri: text_edits(&ranges, new_name) } }))
+}
+
+/// Finds the variable at the position, which may be nested in a list or
+/// object value.
+fn variable_at<'a>(value: &'a AstValue, position: &Position) -> Option<&'a Variable> {
+    if !value.position().contains(position) {
+        return None;
+    }
+
+    match value {
+        AstValue::Variable(variable) => Some(variable),
+        AstValue::ListValue(list) => list
+            .values
+            .iter()
+            .find_map(|value| variable_at(value, position)),
+        AstValue::ObjectValue(object) => object
+            .fields
+            .iter()
+            .find_map(|field| variable_at(&field.value, position)),
+        _ => None,
+    }
+}
+
+/// Collects the ranges of the names of spreads of one fragment.
+struct FragmentNameCollector<'n> {
+    name: &'n str,
+    ranges: Vec<Range>,
+}
+
+impl Visitor for FragmentNameCollector<'_> {
+    fn enter_fragment_spread(
+        &mut self,
+        _ctx: &VisitorContext,
+        node: &FragmentSpread,
+    ) -> VisitorAction {
+        if node.name.value == self.name {
+            self.ranges.push(node.name.position.clone());
+        }
+        VisitorAction::Continue
+    }
+}
+
+/// Replaces the text of every range, in document order.
+fn text_edits(ranges: &[Range], new_text: &str) -> Value {
+    let mut ranges = ranges.to_vec();
+    ranges.sort();
+
+    let edits = ranges
+        .iter()
+        .map(|range| json!({ "range": range_to_json(range), "newText": new_text }))
+        .collect::<Vec<Value>>();
+
+    Value::Array(edits)
+}
+
+/// Offers fixes for the problems in the requested range: creating fragments
+/// that are spread but not defined in any open document, and adding the
+/// required arguments a field is missing. Each action lists the diagnostics
+/// from the request that it fixes.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_codeAction
+pub fn handle_text_document_code_action(
+    state: &ServerState,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    let document_state = get_document_state(state, params)?;
+    let Some(document) = &document_state.document else {
+        return Ok(json!([]));
+    };
+    let (Some(start), Some(end)) = (
+        position_from_json(&document_state.text, &params["range"]["start"]),
+        position_from_json(&document_state.text, &params["range"]["end"]),
+    ) else {
+        return Err(ResponseError::new(INVALID_PARAMS, "Invalid range"));
+    };
+    let range = Range::new(start, end);
+    let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+    let mut collector = CodeActionTargets {
+        range: &range,
+        fragment_spreads: Vec::new(),
+        fields: Vec::new(),
+    };
+    walk_document(&mut collector, document);
+
+    let mut actions = Vec::new();
+
+    for (name, spread_range) in collector.fragment_spreads {
+        let is_defined = state.documents.values().any(|document_state| {
+            document_state
+                .document
+                .as_ref()
+                .is_some_and(|document| document.find_fragment(&name).is_some())
+        });
+        if is_defined {
+            continue;
+        }
+
+        // the fragment selects from the type it is spread in, when it's known
+        let type_condition = state
+            .schema
+            .as_ref()
+            .and_then(|schema| {
+                type_info_at_position(schema, document, &spread_range.start).parent_type
+            })
+            .map(|parent_type| parent_type.name())
+            .unwrap_or("Query");
+
+        let text = &document_state.text;
+        let separator = if text.is_empty() || text.ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        let new_text = format!(
+            "{}\nfragment {} on {} {{\n  __typename\n}}\n",
+            separator, name, type_condition
+        );
+
+        actions.push(code_action(
+            state,
+            format!("Create fragment `{}`", name),
+            &spread_range,
+            params,
+            CodeActionEdit::new(uri, end_of_text(text), new_text),
+        ));
+    }
+
+    if let Some(schema) = &state.schema {
+        for (name_range, provided, insert_at) in collector.fields {
+            let Some(field_def) =
+                type_info_at_position(schema, document, &name_range.start).field_def
+            else {
+                continue;
+            };
+
+            for argument in field_def
+                .arguments
+                .iter()
+                .filter(|argument| argument.is_required())
+            {
+                if provided.contains(&argument.name.value) {
+                    continue;
+                }
+
+                let (position, new_text) = match &insert_at {
+                    Some(position) => (position.clone(), format!(", {}: ", argument.name.value)),
+                    None => (
+                        name_range.end.clone(),
+                        format!("({}: )", argument.name.value),
+                    ),
+                };
+
+                actions.push(code_action(
+                    state,
+                    format!("Add missing argument `{}`", argument.name.value),
+                    &name_range,
+                    params,
+                    CodeActionEdit::new(uri, position, new_text),
+                ));
+            }
+        }
+    }
+
+    Ok(Value::Array(actions))
+}
+
+/// Fills in the edit of a code action that was sent without one.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#codeAction_resolve
+pub fn handle_code_action_resolve(params: &Value) -> Result<Value, ResponseError> {
+    let Some(edit) = CodeActionEdit::from_json(&params["data"]) else {
+        return Err(ResponseError::new(
+            INVALID_PARAMS,
+            "Code action has no data to resolve",
+        ));
+    };
+
+    let mut action = params.clone();
+    action["edit"] = edit.to_workspace_edit();
+    Ok(action)
+}
+
+/// An insertion a code action makes. It doubles as the action's `data`, so
+/// the edit can be resolved later.
+struct CodeActionEdit {
+    uri: String,
+    position: Position,
+    new_text: String,
+}
+
+impl CodeActionEdit {
+    fn new(uri: &str, position: Position, new_text: String) -> CodeActionEdit {
+        CodeActionEdit {
+            uri: uri.to_string(),
+            position,
+            new_text,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "uri": self.uri,
+            "position": position_to_json(&self.position),
+            "newText": self.new_text,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<CodeActionEdit> {
+        Some(CodeActionEdit {
+            uri: value["uri"].as_str()?.to_string(),
+            position: Position::new(
+                value["position"]["line"].as_u64()? as usize,
+                value["position"]["character"].as_u64()? as usize,
+                0,
+            ),
+            new_text: value["newText"].as_str()?.to_string(),
+        })
+    }
+
+    fn to_workspace_edit(&self) -> Value {
+        let range = Range::new(self.position.clone(), self.position.clone());
+        json!({ "changes": { &self.uri: text_edits(&[range], &self.new_text) } })
+    }
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#codeAction
+fn code_action(
+    state: &ServerState,
+    title: String,
+    target: &Range,
+    params: &Value,
+    edit: CodeActionEdit,
+) -> Value {
+    let diagnostics = params["context"]["diagnostics"]
+        .as_array()
+        .map(|diagnostics| {
+            diagnostics
+                .iter()
+                .filter(|diagnostic| {
+                    let line_and_character = |position: &Value| {
+                        (position["line"].as_u64(), position["character"].as_u64())
+                    };
+                    let start = line_and_character(&diagnostic["range"]["start"]);
+                    let end = line_and_character(&diagnostic["range"]["end"]);
+
+                    start
+                        <= (
+                            Some(target.end.line as u64),
+                            Some(target.end.character as u64),
+                        )
+                        && (
+                            Some(target.start.line as u64),
+                            Some(target.start.character as u64),
+                        ) <= end
+                })
+                .cloned()
+                .collect::<Vec<Value>>()
+        })
+        .unwrap_or_default();
+
+    let mut action = json!({
+        "title": title,
+        "kind": "quickfix",
+        "diagnostics": diagnostics,
+    });
+
+    if state.resolve_code_action_edits {
+        action["data"] = edit.to_json();
+    } else {
+        action["edit"] = edit.to_workspace_edit();
+    }
+
+    action
+}
+
+/// Collects the fragment spreads and fields in a range that code actions may
+/// fix. Fields are kept as the range of their name, the names of the
+/// arguments given, and where another argument would go.
+struct CodeActionTargets<'r> {
+    range: &'r Range,
+    fragment_spreads: Vec<(String, Range)>,
+    fields: Vec<(Range, Vec<String>, Option<Position>)>,
+}
+
+impl CodeActionTargets<'_> {
+    fn touches(&self, range: &Range) -> bool {
+        range.overlaps(self.range) || range.contains(&self.range.start)
+    }
+}
+
+impl Visitor for CodeActionTargets<'_> {
+    fn enter_fragment_spread(
+        &mut self,
+        _ctx: &VisitorContext,
+        node: &FragmentSpread,
+    ) -> VisitorAction {
+        if self.touches(node.position()) {
+            self.fragment_spreads
+                .push((node.name.value.clone(), node.name.position.clone()));
+        }
+        VisitorAction::Continue
+    }
+
+    fn enter_field(&mut self, _ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        if self.touches(node.position()) {
+            self.fields.push((
+                node.name.position.clone(),
+                node.arguments
+                    .iter()
+                    .map(|argument| argument.name.value.clone())
+                    .collect(),
+                node.arguments
+                    .last()
+                    .map(|argument| argument.position.end.clone()),
+            ));
+        }
+        VisitorAction::Continue
+    }
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#symbolKind
+const SYMBOL_KIND_MODULE: u64 = 2;
+const SYMBOL_KIND_CLASS: u64 = 5;
+const SYMBOL_KIND_ENUM: u64 = 10;
+const SYMBOL_KIND_INTERFACE: u64 = 11;
+const SYMBOL_KIND_FUNCTION: u64 = 12;
+const SYMBOL_KIND_STRING: u64 = 15;
+const SYMBOL_KIND_STRUCT: u64 = 23;
+const SYMBOL_KIND_EVENT: u64 = 24;
+
+/// Lists every named top-level definition. Anonymous operations and schema
+/// definitions have no name to show, so they're left out.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_documentSymbol
+pub fn handle_text_document_document_symbol(
+    state: &ServerState,
+    params: &Value,
+) -> Result<Value, ResponseError> {
+    let document_state = get_document_state(state, params)?;
+    let Some(document) = &document_state.document else {
+        return Ok(Value::Null);
+    };
+
+    let symbols = document
+        .definitions
+        .iter()
+        .filter_map(|definition| {
+            let name = definition.name()?;
+            Some(json!({
+                "name": name.value,
+                "kind": symbol_kind(definition),
+                "range": range_to_json(definition.position()),
+                "selectionRange": range_to_json(name.position()),
+            }))
+        })
+        .collect::<Vec<Value>>();
+
+    Ok(Value::Array(symbols))
+}
+
+/// Extensions share the kind of the definition they extend.
+fn symbol_kind(definition: &Definition) -> u64 {
+    match definition {
+        Definition::OperationDefinition(_) => SYMBOL_KIND_FUNCTION,
+        Definition::FragmentDefinition(_) => SYMBOL_KIND_MODULE,
+        Definition::ObjectTypeDefinition(_) | Definition::ObjectTypeExtension(_) => {
+            SYMBOL_KIND_CLASS
+        }
+        Definition::InterfaceTypeDefinition(_) | Definition::InterfaceTypeExtension(_) => {
+            SYMBOL_KIND_INTERFACE
+        }
+        Definition::UnionTypeDefinition(_)
+        | Definition::UnionTypeExtension(_)
+        | Definition::EnumTypeDefinition(_)
+        | Definition::EnumTypeExtension(_) => SYMBOL_KIND_ENUM,
+        Definition::ScalarTypeDefinition(_) | Definition::ScalarTypeExtension(_) => {
+            SYMBOL_KIND_STRING
+        }
+        Definition::InputObjectTypeDefinition(_) | Definition::InputObjectTypeExtension(_) => {
+            SYMBOL_KIND_STRUCT
+        }
+        Definition::DirectiveDefinition(_) => SYMBOL_KIND_EVENT,
+        // never named, so never listed
+        Definition::SchemaDefinition(_) | Definition::SchemaExtension(_) => SYMBOL_KIND_MODULE,
+    }
+}
+
+/// Looks up the document named by `params.textDocument.uri`.
+fn get_document_state<'a>(
+    state: &'a ServerState,
+    params: &Value,
+) -> Result<&'a DocumentState, ResponseError> {
+    let Some(uri) = params["textDocument"]["uri"].as_str() else {
+        return Err(ResponseError::new(
+            INVALID_PARAMS,
+            "Missing textDocument.uri",
+        ));
+    };
+
+    state
+        .documents
+        .get(uri)
+        .ok_or_else(|| ResponseError::new(INVALID_PARAMS, format!("Unknown document: {}", uri)))
+}
+
+/// The position just past the last character of the text.
+fn end_of_text(text: &str) -> Position {
+    let line = text.matches('\n').count();
+    let last_line = text.rsplit('\n').next().unwrap_or_default();
+
+    Position::new(line, last_line.encode_utf16().count(), text.len())
+}
+
+/// Sends every diagnostic for the document. An empty list clears the ones
+/// published for an earlier version.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_publishDiagnostics
+fn publish_diagnostics(uri: &str, document: &DocumentState) -> Value {
+    let diagnostics = document
+        .diagnostics
+        .iter()
+        .map(diagnostic_to_json)
+        .collect::<Vec<Value>>();
+
+    notification(
+        "textDocument/publishDiagnostics",
+        json!({
+            "uri": uri,
+            "version": document.version,
+            "diagnostics": diagnostics,
+        }),
+    )
+}
+
+/// Reads an LSP position, filling in the byte offset from the text so it
+/// compares correctly against node ranges.
+pub fn position_from_json(text: &str, value: &Value) -> Option<Position> {
+    let line = value["line"].as_u64()? as usize;
+    let character = value["character"].as_u64()? as usize;
+
+    let line_start = if line == 0 {
+        0
+    } else {
+        text.match_indices('\n').nth(line - 1)?.0 + 1
+    };
+    let line_text = text[line_start..].split('\n').next().unwrap_or_default();
+
+    // `character` counts UTF-16 code units, so characters outside the basic
+    // multilingual plane take up two
+    let mut units = 0;
+    let column = line_text
+        .char_indices()
+        .find(|(_, c)| {
+            units += c.len_utf16();
+            units > character
+        })
+        .map(|(index, _)| index)
+        .unwrap_or(line_text.len());
+
+    Some(Position::new(line, character, line_start + column))
+}
+
+pub fn position_to_json(position: &Position) -> Value {
+    json!({ "line": position.line, "character": position.character })
+}
+
+pub fn range_to_json(range: &Range) -> Value {
+    json!({
+        "start": position_to_json(&range.start),
+        "end": position_to_json(&range.end),
+    })
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#diagnostic
+pub fn diagnostic_to_json(diagnostic: &Diagnostic) -> Value {
+    let severity = match diagnostic.severity {
+        DiagnosticSeverity::Error => 1,
+        DiagnosticSeverity::Warning => 2,
+        DiagnosticSeverity::Information => 3,
+        DiagnosticSeverity::Hint => 4,
+    };
+
+    json!({
+        "range": range_to_json(&diagnostic.range),
+        "severity": severity,
+        "source": env!("CARGO_PKG_NAME"),
+        "message": diagnostic.message,
+    })
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#responseMessage
+fn response(id: &Value, result: Result<Value, ResponseError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error.to_json() }),
+    }
+}
+
+/// Converts a `file://` URI to a path, decoding any percent-encoded bytes.
+/// Other schemes have no path.
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let decoded = if byte == b'%' {
+            std::str::from_utf8(tail.get(..2)?)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        } else {
+            None
+        };
+
+        match decoded {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+/// Converts a path to a `file://` URI, percent-encoding the bytes that can't
+/// appear in one.
+pub fn path_to_uri(path: &Path) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'.' | b'_' | b'~' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+/// A request sent to the client. Its response comes back through
+/// `handle_response`, matched to it by id.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#requestMessage
+fn request(id: i64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#progress
+fn progress(token: &str, value: Value) -> Value {
+    notification("$/progress", json!({ "token": token, "value": value }))
+}
+
+/// A message sent to the client without expecting a response.
+pub fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use crate::lexer::lex_with_options;
+use crate::lexer::types::{LexerOptions, LexicalTokenType};
+use crate::parser::types::{
+    Argument, Document, Field, FragmentDefinition, InlineFragment, Name, Value, Variable,
+    VariableDefinition,
+};
+use crate::visitor::{walk_document, Visitor, VisitorAction, VisitorContext};
+
+/// The token types the server reports, in the order the client indexes them.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#semanticTokenTypes
+pub const SEMANTIC_TOKEN_TYPES: [&str; 8] = [
+    "keyword",
+    "type",
+    "property",
+    "variable",
+    "enumMember",
+    "string",
+    "number",
+    "operator",
+];
+
+const KEYWORD: u32 = 0;
+const TYPE: u32 = 1;
+const PROPERTY: u32 = 2;
+const VARIABLE: u32 = 3;
+const ENUM_MEMBER: u32 = 4;
+const STRING: u32 = 5;
+const NUMBER: u32 = 6;
+const OPERATOR: u32 = 7;
+
+const KEYWORDS: [&str; 5] = ["query", "mutation", "subscription", "fragment", "on"];
+
+/// Encodes the tokens of the document as the relative-position integer array
+/// of `SemanticTokens`. Punctuators, strings and numbers are taken from the
+/// lexer, while names are classified by where they appear in the AST.
+/// https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_semanticTokens
+pub fn semantic_tokens(text: &str, document: &Document) -> Vec<u32> {
+    let Ok(lexical_tokens) = lex_with_options(text, LexerOptions::default()) else {
+        return Vec::new();
+    };
+
+    let mut classifier = NameClassifier::default();
+    walk_document(&mut classifier, document);
+
+    let mut data = Vec::new();
+    let mut previous = (0, 0);
+
+    for lexical_token in lexical_tokens {
+        let position = &lexical_token.position;
+        let token_type = match &lexical_token.token_type {
+            LexicalTokenType::Punctuator(_) => OPERATOR,
+            LexicalTokenType::StringValue(_) | LexicalTokenType::BlockStringValue(_) => STRING,
+            LexicalTokenType::IntValue(_) | LexicalTokenType::FloatValue(_) => NUMBER,
+            LexicalTokenType::Name(name) => match classifier.names.get(&position.end.offset) {
+                Some(token_type) => *token_type,
+                None if KEYWORDS.contains(&name.as_str()) => KEYWORD,
+                None => continue,
+            },
+            LexicalTokenType::Comment(_) | LexicalTokenType::EOF => continue,
+        };
+
+        // tokens can't span lines, so block strings are split up
+        let source = &text[position.start.offset..position.end.offset];
+        for (index, line) in source.split('\n').enumerate() {
+            let line = line.trim_end_matches('\r');
+            let length = line.encode_utf16().count() as u32;
+            if length == 0 {
+                continue;
+            }
+
+            let line_number = (position.start.line + index) as u32;
+            let character = if index == 0 {
+                position.start.character as u32
+            } else {
+                0
+            };
+
+            let delta_line = line_number - previous.0;
+            let delta_start = if delta_line == 0 {
+                character - previous.1
+            } else {
+                character
+            };
+            data.extend([delta_line, delta_start, length, token_type, 0]);
+            previous = (line_number, character);
+        }
+    }
+
+    data
+}
+
+/// Collects the token type of every name the AST can classify, keyed by the
+/// offset the name ends at, which is the same for the AST node and the token.
+#[derive(Default)]
+struct NameClassifier {
+    names: HashMap<usize, u32>,
+}
+
+impl NameClassifier {
+    fn insert(&mut self, name: &Name, token_type: u32) {
+        self.names.insert(name.position.end.offset, token_type);
+    }
+
+    fn insert_value(&mut self, value: &Value) {
+        match value {
+            Value::EnumValue(enum_value) => {
+                self.names
+                    .insert(enum_value.position.end.offset, ENUM_MEMBER);
+            }
+            Value::ListValue(list) => {
+                for value in &list.values {
+                    self.insert_value(value);
+                }
+            }
+            Value::ObjectValue(object) => {
+                for field in &object.fields {
+                    self.insert_value(&field.value);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for NameClassifier {
+    fn enter_fragment_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        node: &FragmentDefinition,
+    ) -> VisitorAction {
+        self.insert(&node.type_condition.name, TYPE);
+        VisitorAction::Continue
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        node: &VariableDefinition,
+    ) -> VisitorAction {
+        self.insert(&node.variable.name, VARIABLE);
+        self.insert(&node.variable_type.named_type().name, TYPE);
+        if let Some(default_value) = &node.default_value {
+            self.insert_value(default_value);
+        }
+        VisitorAction::Continue
+    }
+
+    fn enter_field(&mut self, _ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        self.insert(&node.name, PROPERTY);
+        VisitorAction::Continue
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        _ctx: &VisitorContext,
+        node: &InlineFragment,
+    ) -> VisitorAction {
+        if let Some(type_condition) = &node.type_condition {
+            self.insert(&type_condition.name, TYPE);
+        }
+        VisitorAction::Continue
+    }
+
+    fn enter_argument(&mut self, _ctx: &VisitorContext, node: &Argument) -> VisitorAction {
+        self.insert_value(&node.value);
+        VisitorAction::Continue
+    }
+
+    fn enter_variable(&mut self, _ctx: &VisitorContext, node: &Variable) -> VisitorAction {
+        self.insert(&node.name, VARIABLE);
+        VisitorAction::Continue
+    }
+}
@@ -0,0 +1,611 @@
+#![cfg(test)]
+
+use super::*;
+use crate::parser::parse_strict;
+
+const URI: &str = "file:///query.graphql";
+
+const SCHEMA: &str = r#"
+    "The root of every query"
+    type Query {
+        "Looks up a user by id"
+        user(id: ID!): User
+        users: [User!]!
+    }
+
+    "Someone with an account"
+    type User {
+        id: ID!
+        "The name to display"
+        name: String
+        friends(first: Int!, after: String, order: Order! = ASC): [User!]!
+    }
+
+    enum Order {
+        ASC
+        DESC
+    }
+
+    "Caches the field for the given number of seconds"
+    directive @cached(ttl: Int) on FIELD
+
+    directive @live on QUERY | SUBSCRIPTION
+"#;
+
+fn state_with_document(text: &str) -> ServerState {
+    let schema = Schema::from_document(&parse_strict(SCHEMA.to_string()).unwrap());
+    let mut state = ServerState {
+        schema: Some(schema),
+        initialized: true,
+        ..ServerState::default()
+    };
+    state.documents.insert(
+        URI.to_string(),
+        DocumentState::new(text.to_string(), 1, "graphql".to_string()),
+    );
+    state
+}
+
+fn text_document_position(line: usize, character: usize) -> Value {
+    json!({
+        "textDocument": { "uri": URI },
+        "position": { "line": line, "character": character },
+    })
+}
+
+#[test]
+fn it_hovers_a_field() {
+    let state = state_with_document("query {\n  user(id: 1) { name }\n}");
+
+    let hover = handle_text_document_hover(&state, &text_document_position(1, 3)).unwrap();
+    assert_eq!(hover["contents"]["kind"], "markdown");
+    assert_eq!(
+        hover["contents"]["value"],
+        "**user**: User\n\nLooks up a user by id"
+    );
+    assert_eq!(
+        hover["range"],
+        json!({ "start": { "line": 1, "character": 2 }, "end": { "line": 1, "character": 6 } })
+    );
+
+    let hover = handle_text_document_hover(&state, &text_document_position(1, 18)).unwrap();
+    assert_eq!(
+        hover["contents"]["value"],
+        "**name**: String\n\nThe name to display"
+    );
+}
+
+#[test]
+fn it_hovers_a_named_type() {
+    let state = state_with_document("fragment UserFields on User { id }");
+
+    let hover = handle_text_document_hover(&state, &text_document_position(0, 24)).unwrap();
+    assert_eq!(
+        hover["contents"]["value"],
+        "**User**\n\nSomeone with an account"
+    );
+}
+
+#[test]
+fn it_hovers_a_directive() {
+    let state = state_with_document("{ users @cached(ttl: 60) { id } }");
+
+    let hover = handle_text_document_hover(&state, &text_document_position(0, 10)).unwrap();
+    assert_eq!(
+        hover["contents"]["value"],
+        "**@cached**\n\nCaches the field for the given number of seconds"
+    );
+}
+
+#[test]
+fn it_has_no_hover_without_schema_info() {
+    let mut state = state_with_document("{ unknown users { id } }");
+
+    // a field the schema doesn't define
+    let hover = handle_text_document_hover(&state, &text_document_position(0, 3)).unwrap();
+    assert!(hover.is_null());
+
+    // punctuation
+    let hover = handle_text_document_hover(&state, &text_document_position(0, 0)).unwrap();
+    assert!(hover.is_null());
+
+    state.schema = None;
+    let hover = handle_text_document_hover(&state, &text_document_position(0, 12)).unwrap();
+    assert!(hover.is_null());
+}
+
+#[test]
+fn it_reads_positions_with_byte_offsets() {
+    let text = "{\n  é { id }\n}";
+
+    let position = position_from_json(text, &json!({ "line": 1, "character": 4 })).unwrap();
+    assert_eq!(position, Position::new(1, 4, 7));
+
+    // past the end of the line
+    let position = position_from_json(text, &json!({ "line": 2, "character": 5 })).unwrap();
+    assert_eq!(position, Position::new(2, 5, text.len()));
+
+    assert!(position_from_json(text, &json!({ "line": 3, "character": 0 })).is_none());
+}
+
+#[test]
+fn it_reads_positions_in_utf16_code_units() {
+    let text = "{ a(x: \"😀\") b }";
+
+    // the emoji takes up two UTF-16 code units and four bytes
+    let position = position_from_json(text, &json!({ "line": 0, "character": 11 })).unwrap();
+    assert_eq!(position, Position::new(0, 11, 13));
+    assert_eq!(&text[position.offset..], ") b }");
+
+    // halfway through the emoji is the start of it
+    let position = position_from_json(text, &json!({ "line": 0, "character": 9 })).unwrap();
+    assert_eq!(position.offset, 8);
+}
+
+#[test]
+fn it_completes_the_fields_of_the_selection_set_type() {
+    let state = state_with_document("{\n  user(id: 1) {\n    \n  }\n}");
+
+    let items = handle_text_document_completion(&state, &text_document_position(2, 4)).unwrap();
+    assert_eq!(
+        items,
+        json!([
+            { "label": "id", "kind": 5, "detail": "ID!" },
+            {
+                "label": "name",
+                "kind": 5,
+                "detail": "String",
+                "documentation": "The name to display",
+            },
+            {
+                "label": "friends",
+                "kind": 5,
+                "detail": "[User!]!",
+                "insertText": "friends(first: $1)",
+                "insertTextFormat": 2,
+            },
+        ])
+    );
+
+    // the root selection set selects from `Query`
+    let items = handle_text_document_completion(&state, &text_document_position(0, 1)).unwrap();
+    assert_eq!(labels(&items), vec!["user", "users"]);
+}
+
+#[test]
+fn it_has_no_completions_outside_a_selection_set() {
+    let state = state_with_document("query Users { users { id } }");
+
+    let items = handle_text_document_completion(&state, &text_document_position(0, 2)).unwrap();
+    assert_eq!(items, json!([]));
+}
+
+fn labels(items: &Value) -> Vec<&str> {
+    items
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn it_completes_directives_valid_at_the_location() {
+    let state = state_with_document("query Users @ {\n  users { id }\n}");
+
+    let items = handle_text_document_completion(&state, &text_document_position(0, 13)).unwrap();
+    assert_eq!(
+        items,
+        json!([{ "label": "live", "kind": 3, "detail": "@live" }])
+    );
+
+    let state = state_with_document("query Users {\n  users @ { id }\n}");
+    let items = handle_text_document_completion(&state, &text_document_position(1, 9)).unwrap();
+    assert_eq!(labels(&items), vec!["cached", "include", "skip"]);
+    assert_eq!(
+        items[1],
+        json!({
+            "label": "include",
+            "kind": 3,
+            "detail": "@include(if: Boolean!)",
+            "documentation": concat!(
+                "Directs the executor to include this field or fragment only when the `if` ",
+                "argument is true."
+            ),
+            "insertText": "include(if: $1)",
+            "insertTextFormat": 2,
+        })
+    );
+}
+
+#[test]
+fn it_has_no_directive_completions_without_a_schema() {
+    let mut state = state_with_document("{ users @ { id } }");
+    state.schema = None;
+
+    let items = handle_text_document_completion(&state, &text_document_position(0, 9)).unwrap();
+    assert_eq!(items, json!([]));
+}
+
+fn definition_at(state: &ServerState, line: usize, character: usize) -> Value {
+    handle_text_document_definition(state, &text_document_position(line, character)).unwrap()
+}
+
+#[test]
+fn it_finds_the_definition_of_a_fragment_spread() {
+    let state =
+        state_with_document("{ users { ...UserFields } }\n\nfragment UserFields on User { id }");
+
+    assert_eq!(
+        definition_at(&state, 0, 14),
+        json!({
+            "uri": URI,
+            "range": {
+                "start": { "line": 2, "character": 0 },
+                "end": { "line": 2, "character": 34 },
+            },
+        })
+    );
+
+    // an unknown fragment
+    let state = state_with_document("{ users { ...Missing } }");
+    assert!(definition_at(&state, 0, 14).is_null());
+}
+
+#[test]
+fn it_finds_the_definition_of_a_named_type_in_open_documents() {
+    let mut state = state_with_document("fragment UserFields on User { id }");
+    state.documents.insert(
+        "file:///schema.graphql".to_string(),
+        DocumentState::new(
+            "type Query { users: [User] }\n\ntype User { id: ID }".to_string(),
+            1,
+            "graphql".to_string(),
+        ),
+    );
+
+    assert_eq!(
+        definition_at(&state, 0, 24),
+        json!({
+            "uri": "file:///schema.graphql",
+            "range": {
+                "start": { "line": 2, "character": 0 },
+                "end": { "line": 2, "character": 20 },
+            },
+        })
+    );
+}
+
+#[test]
+fn it_finds_the_definition_of_a_field_in_the_schema_file() {
+    let mut state = state_with_document("{ users { id } }");
+    assert!(definition_at(&state, 0, 3).is_null());
+
+    state.schema_uri = Some("file:///schema.graphql".to_string());
+    let users = state
+        .schema
+        .as_ref()
+        .unwrap()
+        .get_type("Query")
+        .unwrap()
+        .field("users")
+        .unwrap();
+
+    assert_eq!(
+        definition_at(&state, 0, 3),
+        json!({ "uri": "file:///schema.graphql", "range": range_to_json(&users.position) })
+    );
+}
+
+#[test]
+fn it_lists_document_symbols() {
+    let state = state_with_document(concat!(
+        "query Users { users { id } }\n",
+        "fragment UserFields on User { id }\n",
+        "schema { query: Query }\n",
+        "type Post { id: ID }\n",
+        "extend type Post { title: String }\n",
+        "interface Node { id: ID! }\n",
+        "union Result = Post | User\n",
+        "enum Role { ADMIN }\n",
+        "scalar Date\n",
+        "input PostInput { title: String }\n",
+        "directive @cached on FIELD\n",
+    ));
+
+    let symbols =
+        handle_text_document_document_symbol(&state, &json!({ "textDocument": { "uri": URI } }))
+            .unwrap();
+    let symbols = symbols.as_array().unwrap();
+
+    let summary = symbols
+        .iter()
+        .map(|symbol| {
+            (
+                symbol["name"].as_str().unwrap(),
+                symbol["kind"].as_u64().unwrap(),
+            )
+        })
+        .collect::<Vec<(&str, u64)>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("Users", 12),
+            ("UserFields", 2),
+            ("Post", 5),
+            ("Post", 5),
+            ("Node", 11),
+            ("Result", 10),
+            ("Role", 10),
+            ("Date", 15),
+            ("PostInput", 23),
+            ("cached", 24),
+        ]
+    );
+
+    assert_eq!(
+        symbols[1]["range"],
+        json!({ "start": { "line": 1, "character": 0 }, "end": { "line": 1, "character": 34 } })
+    );
+    assert_eq!(
+        symbols[1]["selectionRange"],
+        json!({ "start": { "line": 1, "character": 9 }, "end": { "line": 1, "character": 19 } })
+    );
+
+    // anonymous operations have no name to list
+    let state = state_with_document("{ users { id } }");
+    let symbols =
+        handle_text_document_document_symbol(&state, &json!({ "textDocument": { "uri": URI } }))
+            .unwrap();
+    assert_eq!(symbols, json!([]));
+}
+
+fn signature_help_at(state: &ServerState, line: usize, character: usize) -> Value {
+    handle_text_document_signature_help(state, &text_document_position(line, character)).unwrap()
+}
+
+#[test]
+fn it_shows_the_signature_of_a_field() {
+    let state = state_with_document("{ user(id: 1) { id } }");
+
+    assert_eq!(
+        signature_help_at(&state, 0, 7),
+        json!({
+            "signatures": [{
+                "label": "user(id: ID!): User",
+                "documentation": "Looks up a user by id",
+                "parameters": [{ "label": "id: ID!" }],
+            }],
+            "activeSignature": 0,
+            "activeParameter": 0,
+        })
+    );
+}
+
+#[test]
+fn it_counts_commas_to_find_the_active_parameter() {
+    let state = state_with_document(
+        "{ users { friends(first: 10, after: \"a,b\", order: [ASC, DESC]) { id } } }",
+    );
+
+    let help = signature_help_at(&state, 0, 20);
+    assert_eq!(
+        help["signatures"][0]["label"],
+        "friends(first: Int!, after: String, order: Order!): [User!]!"
+    );
+    assert_eq!(help["activeParameter"], 0);
+
+    // the comma inside the string doesn't count
+    assert_eq!(signature_help_at(&state, 0, 39)["activeParameter"], 1);
+
+    // nor does the one inside the list
+    assert_eq!(signature_help_at(&state, 0, 58)["activeParameter"], 2);
+}
+
+#[test]
+fn it_has_no_signature_outside_an_argument_list() {
+    let state = state_with_document("{ user(id: 1) { id } users { id } }");
+
+    // on the field name
+    assert!(signature_help_at(&state, 0, 3).is_null());
+    // after the closing parenthesis
+    assert!(signature_help_at(&state, 0, 13).is_null());
+    // a field without arguments
+    assert!(signature_help_at(&state, 0, 23).is_null());
+}
+
+#[test]
+fn it_encodes_semantic_tokens() {
+    let state = state_with_document(concat!(
+        "query GetUser($id: ID!) {\n",
+        "  user(id: $id) { name friends(first: 2, order: ASC) { id } }\n",
+        "}",
+    ));
+
+    let tokens = handle_text_document_semantic_tokens_full(
+        &state,
+        &json!({ "textDocument": { "uri": URI } }),
+    )
+    .unwrap();
+    let data = tokens["data"].as_array().unwrap();
+
+    // the operation and argument names aren't classified
+    assert_eq!(data.len(), 29 * 5);
+    assert_eq!(
+        data[..25],
+        json!([
+            0, 0, 5, 0, 0, // query
+            0, 13, 1, 7, 0, // (
+            0, 1, 1, 7, 0, // $
+            0, 1, 2, 3, 0, // id
+            0, 2, 1, 7, 0, // :
+        ])
+        .as_array()
+        .unwrap()[..]
+    );
+
+    // `ASC` on the second line
+    assert_eq!(
+        data[22 * 5..23 * 5],
+        json!([0, 2, 3, 4, 0]).as_array().unwrap()[..]
+    );
+}
+
+#[test]
+fn it_splits_block_strings_into_a_token_per_line() {
+    let state = state_with_document("{ user(id: \"\"\"\n  one\n  two\n\"\"\") { id } }");
+
+    let tokens = handle_text_document_semantic_tokens_full(
+        &state,
+        &json!({ "textDocument": { "uri": URI } }),
+    )
+    .unwrap();
+    let data = tokens["data"].as_array().unwrap();
+
+    // { user ( : then the four lines of the string
+    assert_eq!(
+        data[20..40],
+        json!([0, 2, 3, 5, 0, 1, 0, 5, 5, 0, 1, 0, 5, 5, 0, 1, 0, 3, 5, 0])
+            .as_array()
+            .unwrap()[..]
+    );
+}
+
+#[test]
+fn it_measures_semantic_tokens_in_utf16_code_units() {
+    let state = state_with_document("query Q { a(x: \"😀\") b }");
+
+    let tokens = handle_text_document_semantic_tokens_full(
+        &state,
+        &json!({ "textDocument": { "uri": URI } }),
+    )
+    .unwrap();
+
+    // the tokens with their deltas resolved to columns, all on the one line
+    let mut column = 0;
+    let tokens = tokens["data"]
+        .as_array()
+        .unwrap()
+        .chunks(5)
+        .map(|token| {
+            column += token[1].as_u64().unwrap();
+            (
+                column,
+                token[2].as_u64().unwrap(),
+                token[3].as_u64().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // the emoji is a surrogate pair, so the string is four code units long
+    assert!(tokens.contains(&(15, 4, 5)));
+    // and the `)` after it is at 19
+    assert!(tokens.contains(&(19, 1, 7)));
+}
+
+fn code_actions(state: &ServerState, line: usize, character: usize, diagnostics: Value) -> Value {
+    let position = json!({ "line": line, "character": character });
+    handle_text_document_code_action(
+        state,
+        &json!({
+            "textDocument": { "uri": URI },
+            "range": { "start": position, "end": position },
+            "context": { "diagnostics": diagnostics },
+        }),
+    )
+    .unwrap()
+}
+
+fn insertion(line: usize, character: usize, new_text: &str) -> Value {
+    let position = json!({ "line": line, "character": character });
+    json!({
+        "changes": {
+            URI: [{ "range": { "start": position, "end": position }, "newText": new_text }]
+        }
+    })
+}
+
+#[test]
+fn it_offers_to_add_missing_required_arguments() {
+    let state = state_with_document("{ user { id } users { friends(after: \"a\") { id } } }");
+
+    let diagnostic = json!({
+        "range": {
+            "start": { "line": 0, "character": 2 },
+            "end": { "line": 0, "character": 6 },
+        },
+        "message": "Field 'user' argument 'id' of type 'ID!' is required, but it was not provided.",
+    });
+    let elsewhere = json!({
+        "range": {
+            "start": { "line": 0, "character": 22 },
+            "end": { "line": 0, "character": 29 },
+        },
+        "message": "Something else",
+    });
+
+    let actions = code_actions(&state, 0, 3, json!([diagnostic, elsewhere]));
+    assert_eq!(
+        actions,
+        json!([{
+            "title": "Add missing argument `id`",
+            "kind": "quickfix",
+            "diagnostics": [diagnostic],
+            "edit": insertion(0, 6, "(id: )"),
+        }])
+    );
+
+    let actions = code_actions(&state, 0, 24, json!([]));
+    assert_eq!(actions[0]["title"], "Add missing argument `first`");
+    assert_eq!(actions[0]["edit"], insertion(0, 40, ", first: "));
+
+    // nothing is missing from `users`
+    let actions = code_actions(&state, 0, 16, json!([]));
+    assert_eq!(actions, json!([]));
+}
+
+#[test]
+fn it_offers_to_create_an_unknown_fragment() {
+    let state = state_with_document(
+        "{ users { ...UserFields ...Missing } }\n\nfragment UserFields on User { id }",
+    );
+
+    let actions = code_actions(&state, 0, 29, json!([]));
+    assert_eq!(
+        actions,
+        json!([{
+            "title": "Create fragment `Missing`",
+            "kind": "quickfix",
+            "diagnostics": [],
+            "edit": insertion(2, 34, "\n\nfragment Missing on User {\n  __typename\n}\n"),
+        }])
+    );
+
+    let actions = code_actions(&state, 0, 14, json!([]));
+    assert_eq!(actions, json!([]));
+}
+
+#[test]
+fn it_resolves_code_action_edits_later_when_the_client_can() {
+    let mut state = state_with_document("{ user { id } }");
+    state.resolve_code_action_edits = true;
+
+    let actions = code_actions(&state, 0, 3, json!([]));
+    let action = &actions[0];
+    assert!(action["edit"].is_null());
+    assert!(action["data"].is_object());
+
+    let resolved = handle_code_action_resolve(action).unwrap();
+    assert_eq!(resolved["title"], "Add missing argument `id`");
+    assert_eq!(resolved["edit"], insertion(0, 6, "(id: )"));
+}
+
+#[test]
+fn it_converts_between_file_uris_and_paths() {
+    let path = PathBuf::from("/home/me/my schema.graphql");
+    let uri = path_to_uri(&path);
+
+    assert_eq!(uri, "file:///home/me/my%20schema.graphql");
+    assert_eq!(uri_to_path(&uri), Some(path));
+    assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+}
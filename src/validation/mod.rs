@@ -0,0 +1,63 @@
+use crate::lsp::types::{Diagnostic, DiagnosticSeverity};
+use crate::parser::types::{Document, OperationType, SchemaDefinition};
+use crate::schema::Schema;
+
+pub mod rules;
+
+mod tests;
+
+/// A validation rule, which adds a diagnostic for every violation it finds in
+/// the document.
+pub type Rule = fn(&Document, &Schema, &mut Vec<Diagnostic>);
+
+/// Every rule `validate` runs, in the order their diagnostics are reported.
+pub const RULES: &[Rule] = &[
+    rules::executable_definitions,
+    rules::scalar_leafs,
+    rules::fields_on_correct_type,
+    rules::known_argument_names,
+    rules::provided_required_arguments,
+];
+
+/// Validates an executable document against the schema, returning the
+/// diagnostics of every rule. The document is expected to have parsed
+/// without errors.
+/// https://spec.graphql.org/October2021/#sec-Validation
+pub fn validate(document: &Document, schema: &Schema) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for rule in RULES {
+        rule(document, schema, &mut diagnostics);
+    }
+
+    diagnostics
+}
+
+/// https://spec.graphql.org/October2021/#sec-Schema
+pub fn validate_schema_definition(definition: &SchemaDefinition) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut seen: Vec<&OperationType> = Vec::new();
+
+    for operation_type_definition in &definition.operation_types {
+        let operation_type = &operation_type_definition.operation_type;
+
+        if seen.contains(&operation_type) {
+            let name = match operation_type {
+                OperationType::Query => "query",
+                OperationType::Mutation => "mutation",
+                OperationType::Subscription => "subscription",
+            };
+
+            diagnostics.push(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                format!("There can be only one {} type in schema.", name),
+                operation_type_definition.position.clone(),
+            ));
+            continue;
+        }
+
+        seen.push(operation_type);
+    }
+
+    diagnostics
+}
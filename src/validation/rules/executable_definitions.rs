@@ -0,0 +1,30 @@
+use crate::lsp::types::{Diagnostic, DiagnosticSeverity};
+use crate::parser::types::{Definition, Document, Node};
+use crate::schema::Schema;
+
+/// A document sent for execution may only contain operations and fragments.
+/// https://spec.graphql.org/October2021/#sec-Executable-Definitions
+pub fn executable_definitions(
+    document: &Document,
+    _schema: &Schema,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for definition in &document.definitions {
+        if let Definition::OperationDefinition(_) | Definition::FragmentDefinition(_) = definition {
+            continue;
+        }
+
+        let (message, range) = match definition.name() {
+            Some(name) => (
+                format!("The '{}' definition is not executable.", name.value),
+                name.position.clone(),
+            ),
+            None => (
+                String::from("The schema definition is not executable."),
+                definition.position().clone(),
+            ),
+        };
+
+        diagnostics.push(Diagnostic::new(DiagnosticSeverity::Error, message, range));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::helpers::closest_name;
+use crate::lsp::types::{Diagnostic, DiagnosticSeverity};
+use crate::parser::types::Document;
+use crate::schema::Schema;
+
+use super::walk_fields;
+
+/// Fields may only be selected on types that define them. `__typename` can be
+/// selected on any type, and the root query type also has `__schema` and
+/// `__type`.
+/// https://spec.graphql.org/October2021/#sec-Field-Selections
+pub fn fields_on_correct_type(
+    document: &Document,
+    schema: &Schema,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    walk_fields(document, schema, |field, info| {
+        let Some(parent_type) = info.parent_type else {
+            return;
+        };
+
+        // selections on leaf types are reported by `scalar_leafs`
+        if !parent_type.is_composite() || info.definition.is_some() {
+            return;
+        }
+
+        let name = field.name.value.as_str();
+        let is_query_type = schema.query_type.as_deref() == Some(parent_type.name());
+        if name == "__typename" || (is_query_type && matches!(name, "__schema" | "__type")) {
+            return;
+        }
+
+        let mut message = format!(
+            "Field '{}' does not exist on type '{}'.",
+            name,
+            parent_type.name()
+        );
+        let field_names = parent_type
+            .fields()
+            .iter()
+            .map(|field_def| field_def.name.value.as_str());
+        if let Some(suggestion) = closest_name(name, field_names) {
+            message.push_str(&format!(" Did you mean '{}'?", suggestion));
+        }
+
+        diagnostics.push(Diagnostic::new(
+            DiagnosticSeverity::Error,
+            message,
+            field.name.position.clone(),
+        ));
+    });
+}
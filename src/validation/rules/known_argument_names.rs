@@ -0,0 +1,58 @@
+use crate::lsp::types::{Diagnostic, DiagnosticSeverity};
+use crate::parser::types::Document;
+use crate::schema::Schema;
+
+use super::{walk_directives, walk_fields};
+
+/// Every argument passed to a field or directive must be one it defines.
+/// Fields and directives the schema doesn't know are left to other rules.
+/// https://spec.graphql.org/October2021/#sec-Argument-Names
+pub fn known_argument_names(
+    document: &Document,
+    schema: &Schema,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    walk_fields(document, schema, |field, info| {
+        let (Some(parent_type), Some(definition)) = (info.parent_type, info.definition) else {
+            return;
+        };
+
+        for argument in &field.arguments {
+            if definition
+                .arguments
+                .iter()
+                .all(|input_value| input_value.name != argument.name)
+            {
+                diagnostics.push(Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    format!(
+                        "Unknown argument '{}' on field '{}' of type '{}'.",
+                        argument.name.value,
+                        field.name.value,
+                        parent_type.name()
+                    ),
+                    argument.name.position.clone(),
+                ));
+            }
+        }
+    });
+
+    walk_directives(document, schema, |directive, definition| {
+        for argument in &directive.arguments {
+            if definition
+                .arguments
+                .iter()
+                .all(|input_value| input_value.name != argument.name)
+            {
+                diagnostics.push(Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    format!(
+                        "Unknown argument '{}' on directive '@{}'.",
+                        argument.name.value, directive.name.value
+                    ),
+                    argument.name.position.clone(),
+                ));
+            }
+        }
+    });
+}
@@ -0,0 +1,148 @@
+// The validation rules for executable documents, one per file. Each rule is a
+// function that appends its diagnostics, and is run by `validate` once it is
+// listed in `RULES`.
+
+use crate::parser::types::{
+    Directive, DirectiveDefinition, Document, Field, FieldDefinition, FragmentDefinition,
+    InlineFragment, OperationDefinition,
+};
+use crate::schema::{Schema, TypeDefinition};
+use crate::visitor::{walk_document, TypeInfoVisitor, Visitor, VisitorAction, VisitorContext};
+
+mod executable_definitions;
+mod fields_on_correct_type;
+mod known_argument_names;
+mod provided_required_arguments;
+mod scalar_leafs;
+
+mod tests;
+
+pub use self::executable_definitions::executable_definitions;
+pub use self::fields_on_correct_type::fields_on_correct_type;
+pub use self::known_argument_names::known_argument_names;
+pub use self::provided_required_arguments::provided_required_arguments;
+pub use self::scalar_leafs::scalar_leafs;
+
+/// What the schema says about a selected field. Each is `None` when the
+/// schema doesn't define it.
+struct FieldInfo<'s> {
+    /// The type the field is selected on.
+    parent_type: Option<&'s TypeDefinition>,
+    definition: Option<&'s FieldDefinition>,
+    /// The named type the field returns, with list and non-null wrappers
+    /// removed.
+    field_type: Option<&'s TypeDefinition>,
+}
+
+/// Calls `check` for every field of the document, in document order, along
+/// with what the schema says about it.
+fn walk_fields<'s>(
+    document: &Document,
+    schema: &'s Schema,
+    check: impl FnMut(&Field, FieldInfo<'s>),
+) {
+    let mut visitor = FieldWalker {
+        type_info: TypeInfoVisitor::new(schema),
+        check,
+    };
+    walk_document(&mut visitor, document);
+}
+
+struct FieldWalker<'s, F> {
+    type_info: TypeInfoVisitor<'s>,
+    check: F,
+}
+
+impl<'s, F: FnMut(&Field, FieldInfo<'s>)> Visitor for FieldWalker<'s, F> {
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &OperationDefinition,
+    ) -> VisitorAction {
+        self.type_info.enter_operation_definition(ctx, node)
+    }
+
+    fn leave_operation_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &OperationDefinition,
+    ) -> VisitorAction {
+        self.type_info.leave_operation_definition(ctx, node)
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &FragmentDefinition,
+    ) -> VisitorAction {
+        self.type_info.enter_fragment_definition(ctx, node)
+    }
+
+    fn leave_fragment_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &FragmentDefinition,
+    ) -> VisitorAction {
+        self.type_info.leave_fragment_definition(ctx, node)
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &InlineFragment,
+    ) -> VisitorAction {
+        self.type_info.enter_inline_fragment(ctx, node)
+    }
+
+    fn leave_inline_fragment(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &InlineFragment,
+    ) -> VisitorAction {
+        self.type_info.leave_inline_fragment(ctx, node)
+    }
+
+    fn enter_field(&mut self, ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        let parent_type = self.type_info.current_type();
+        self.type_info.enter_field(ctx, node);
+
+        let info = FieldInfo {
+            parent_type,
+            definition: self.type_info.current_field_def(),
+            field_type: self.type_info.current_type(),
+        };
+        (self.check)(node, info);
+
+        VisitorAction::Continue
+    }
+
+    fn leave_field(&mut self, ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        self.type_info.leave_field(ctx, node)
+    }
+}
+
+/// Calls `check` for every directive of the document that the schema
+/// defines, in document order, along with its definition.
+fn walk_directives<'s>(
+    document: &Document,
+    schema: &'s Schema,
+    check: impl FnMut(&Directive, &'s DirectiveDefinition),
+) {
+    let mut visitor = DirectiveWalker { schema, check };
+    walk_document(&mut visitor, document);
+}
+
+struct DirectiveWalker<'s, F> {
+    schema: &'s Schema,
+    check: F,
+}
+
+impl<'s, F: FnMut(&Directive, &'s DirectiveDefinition)> Visitor for DirectiveWalker<'s, F> {
+    fn enter_directive(&mut self, _ctx: &VisitorContext, node: &Directive) -> VisitorAction {
+        if let Some(definition) = self.schema.directives.get(&node.name.value) {
+            (self.check)(node, definition);
+        }
+
+        VisitorAction::Continue
+    }
+}
@@ -0,0 +1,62 @@
+use crate::lsp::types::{Diagnostic, DiagnosticSeverity};
+use crate::parser::types::{Argument, Document, InputValueDefinition};
+use crate::schema::Schema;
+
+use super::{walk_directives, walk_fields};
+
+/// Arguments of a non-null type without a default value must be passed to
+/// every field and directive that defines them.
+/// https://spec.graphql.org/October2021/#sec-Required-Arguments
+pub fn provided_required_arguments(
+    document: &Document,
+    schema: &Schema,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    walk_fields(document, schema, |field, info| {
+        let Some(definition) = info.definition else {
+            return;
+        };
+
+        for input_value in missing_arguments(&definition.arguments, &field.arguments) {
+            diagnostics.push(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                format!(
+                    "Field '{}' argument '{}' of type '{}' is required, but it was not provided.",
+                    field.name.value, input_value.name.value, input_value.input_type
+                ),
+                field.name.position.clone(),
+            ));
+        }
+    });
+
+    walk_directives(document, schema, |directive, definition| {
+        for input_value in missing_arguments(&definition.arguments, &directive.arguments) {
+            diagnostics.push(Diagnostic::new(
+                DiagnosticSeverity::Error,
+                format!(
+                    concat!(
+                        "Directive '@{}' argument '{}' of type '{}' is required, ",
+                        "but it was not provided."
+                    ),
+                    directive.name.value, input_value.name.value, input_value.input_type
+                ),
+                directive.name.position.clone(),
+            ));
+        }
+    });
+}
+
+/// The required arguments of a definition that weren't passed.
+fn missing_arguments<'d>(
+    definitions: &'d [InputValueDefinition],
+    arguments: &[Argument],
+) -> impl Iterator<Item = &'d InputValueDefinition> {
+    let passed = arguments
+        .iter()
+        .map(|argument| argument.name.value.clone())
+        .collect::<Vec<String>>();
+
+    definitions.iter().filter(move |input_value| {
+        input_value.is_required() && !passed.contains(&input_value.name.value)
+    })
+}
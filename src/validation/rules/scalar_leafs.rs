@@ -0,0 +1,49 @@
+use crate::lsp::types::{Diagnostic, DiagnosticSeverity};
+use crate::parser::types::Document;
+use crate::schema::Schema;
+
+use super::walk_fields;
+
+/// Fields that return a scalar or enum are leaves of the response, so there
+/// is nothing to select from them, while fields that return an object,
+/// interface or union must say which of its fields to return.
+/// https://spec.graphql.org/October2021/#sec-Leaf-Field-Selections
+pub fn scalar_leafs(document: &Document, schema: &Schema, diagnostics: &mut Vec<Diagnostic>) {
+    walk_fields(document, schema, |field, info| {
+        let Some(field_type) = info.field_type else {
+            return;
+        };
+
+        match &field.selection_set {
+            Some(selection_set) if field_type.is_leaf() => {
+                diagnostics.push(Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    format!(
+                        "Field '{}' must not have a selection set; it is a scalar/enum.",
+                        field.name.value
+                    ),
+                    selection_set.position.clone(),
+                ));
+            }
+            None if field_type.is_composite() => {
+                let type_name = match info.definition {
+                    Some(definition) => definition.field_type.to_string(),
+                    None => field_type.name().to_string(),
+                };
+
+                diagnostics.push(Diagnostic::new(
+                    DiagnosticSeverity::Error,
+                    format!(
+                        concat!(
+                            "Field '{}' of type '{}' must have a selection set ",
+                            "because it is a composite type."
+                        ),
+                        field.name.value, type_name
+                    ),
+                    field.name.position.clone(),
+                ));
+            }
+            _ => {}
+        }
+    });
+}
@@ -0,0 +1,218 @@
+#![cfg(test)]
+
+use super::*;
+use crate::lsp::types::Diagnostic;
+use crate::parser::parse_strict;
+use crate::parser::types::Document;
+use crate::schema::Schema;
+use crate::validation::Rule;
+
+const SCHEMA: &str = r#"
+    type Query {
+        user(id: ID!): User
+        users(first: Int! = 10, after: String): [User!]!
+    }
+
+    type User {
+        id: ID!
+        name: String
+        friends: [User!]!
+    }
+"#;
+
+/// Runs the rule alone and returns the messages of its diagnostics.
+fn messages(rule: Rule, source: &str) -> Vec<String> {
+    let schema = Schema::from_document(&parse_strict(SCHEMA.to_string()).unwrap());
+    let document: Document = parse_strict(source.to_string()).unwrap();
+
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    rule(&document, &schema, &mut diagnostics);
+    diagnostics
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect()
+}
+
+#[test]
+fn it_accepts_operations_and_fragments() {
+    let messages = messages(
+        executable_definitions,
+        "query GetUser { user(id: 1) { ...UserFields } }\nfragment UserFields on User { id }",
+    );
+
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn it_errs_for_type_system_definitions() {
+    let messages = messages(
+        executable_definitions,
+        "{ user(id: 1) { id } }\ntype Admin { id: ID }\nextend schema { mutation: Admin }",
+    );
+
+    assert_eq!(
+        messages,
+        [
+            "The 'Admin' definition is not executable.",
+            "The schema definition is not executable.",
+        ]
+    );
+}
+
+#[test]
+fn it_accepts_leaf_fields_without_selection_sets() {
+    let messages = messages(scalar_leafs, "{ user(id: 1) { id name __typename } }");
+
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn it_errs_for_leaf_fields_with_selection_sets() {
+    let messages = messages(
+        scalar_leafs,
+        "{ user(id: 1) { name { length } __typename { name } } }",
+    );
+
+    assert_eq!(
+        messages,
+        [
+            "Field 'name' must not have a selection set; it is a scalar/enum.",
+            "Field '__typename' must not have a selection set; it is a scalar/enum.",
+        ]
+    );
+}
+
+#[test]
+fn it_accepts_composite_fields_with_selection_sets() {
+    let messages = messages(scalar_leafs, "{ user(id: 1) { id } }");
+
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn it_errs_for_composite_fields_without_selection_sets() {
+    let messages = messages(
+        scalar_leafs,
+        "query { user(id: 1) }\nfragment UserFields on User { friends }",
+    );
+
+    assert_eq!(
+        messages,
+        [
+            concat!(
+                "Field 'user' of type 'User' must have a selection set ",
+                "because it is a composite type."
+            ),
+            concat!(
+                "Field 'friends' of type '[User!]!' must have a selection set ",
+                "because it is a composite type."
+            ),
+        ]
+    );
+}
+
+#[test]
+fn it_accepts_fields_defined_on_the_type() {
+    let messages = messages(
+        fields_on_correct_type,
+        concat!(
+            "{ __schema { types { name } } user(id: 1) { id __typename } }\n",
+            "fragment UserFields on User { friends { name } }",
+        ),
+    );
+
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn it_errs_for_fields_the_type_does_not_define() {
+    let messages = messages(
+        fields_on_correct_type,
+        "{ user(id: 1) { foobar nam friends { __schema } } }",
+    );
+
+    assert_eq!(
+        messages,
+        [
+            "Field 'foobar' does not exist on type 'User'.",
+            "Field 'nam' does not exist on type 'User'. Did you mean 'name'?",
+            "Field '__schema' does not exist on type 'User'.",
+        ]
+    );
+}
+
+#[test]
+fn it_ignores_fields_on_unknown_types() {
+    let messages = messages(
+        fields_on_correct_type,
+        "fragment AdminFields on Admin { foobar }",
+    );
+
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn it_accepts_known_arguments() {
+    let messages = messages(
+        known_argument_names,
+        "query ($skip: Boolean!) { user(id: 1) @include(if: $skip) { id } }",
+    );
+
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn it_errs_for_unknown_arguments() {
+    let messages = messages(
+        known_argument_names,
+        r#"{ user(id: 1, fakeArg: "x") @skip(if: true, when: false) { name(short: true) } }"#,
+    );
+
+    assert_eq!(
+        messages,
+        [
+            "Unknown argument 'fakeArg' on field 'user' of type 'Query'.",
+            "Unknown argument 'short' on field 'name' of type 'User'.",
+            "Unknown argument 'when' on directive '@skip'.",
+        ]
+    );
+}
+
+#[test]
+fn it_ignores_arguments_of_unknown_fields_and_directives() {
+    let messages = messages(
+        known_argument_names,
+        "{ admin(id: 1) { id } user(id: 1) @unknown(if: true) { id } }",
+    );
+
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn it_accepts_fields_and_directives_with_their_required_arguments() {
+    let messages = messages(
+        provided_required_arguments,
+        "{ user(id: 1) @include(if: true) { id } users { id } }",
+    );
+
+    assert!(messages.is_empty());
+}
+
+#[test]
+fn it_errs_for_missing_required_arguments() {
+    let messages = messages(
+        provided_required_arguments,
+        "{ user { ...on User @skip { id } } }",
+    );
+
+    assert_eq!(
+        messages,
+        [
+            "Field 'user' argument 'id' of type 'ID!' is required, but it was not provided.",
+            concat!(
+                "Directive '@skip' argument 'if' of type 'Boolean!' is required, ",
+                "but it was not provided."
+            ),
+        ]
+    );
+}
@@ -0,0 +1,150 @@
+#![cfg(test)]
+
+use super::*;
+use crate::parser::parse_strict;
+use crate::parser::types::Definition;
+
+fn parse_schema_definition(source: &str) -> SchemaDefinition {
+    let document = parse_strict(source.to_string()).unwrap();
+
+    match document.definitions.first() {
+        Some(Definition::SchemaDefinition(schema_definition)) => schema_definition.clone(),
+        _ => panic!("Expected SchemaDefinition"),
+    }
+}
+
+#[test]
+fn it_accepts_unique_operation_types() {
+    let schema_definition = parse_schema_definition(
+        r#"
+            schema {
+                query: Query
+                mutation: Mutation
+            }
+        "#,
+    );
+
+    assert!(validate_schema_definition(&schema_definition).is_empty());
+}
+
+#[test]
+fn it_errs_for_duplicate_operation_types() {
+    let schema_definition = parse_schema_definition(
+        r#"
+            schema {
+                query: Query
+                query: Query
+            }
+        "#,
+    );
+
+    let diagnostics = validate_schema_definition(&schema_definition);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "There can be only one query type in schema."
+    );
+}
+
+const SCHEMA: &str = r#"
+    type Query {
+        user(id: ID!): User
+    }
+
+    type User {
+        id: ID!
+        name: String
+    }
+"#;
+
+fn validate_source(source: &str) -> Vec<Diagnostic> {
+    let schema = Schema::from_document(&parse_strict(SCHEMA.to_string()).unwrap());
+    let document = parse_strict(source.to_string()).unwrap();
+    validate(&document, &schema)
+}
+
+#[test]
+fn it_validates_fields_on_the_correct_type() {
+    let messages = validate_source("{ user(id: 1) { foobar } }")
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect::<Vec<String>>();
+
+    assert!(messages.contains(&String::from(
+        "Field 'foobar' does not exist on type 'User'."
+    )));
+}
+
+#[test]
+fn it_validates_known_argument_names() {
+    let messages = validate_source(r#"{ user(id: 1, fakeArg: "x") { id } }"#)
+        .into_iter()
+        .map(|diagnostic| diagnostic.message)
+        .collect::<Vec<String>>();
+
+    assert!(messages.contains(&String::from(
+        "Unknown argument 'fakeArg' on field 'user' of type 'Query'."
+    )));
+}
+
+#[test]
+fn it_validates_a_valid_document_without_diagnostics() {
+    let diagnostics = validate_source(
+        r#"
+            query GetUser($id: ID!) {
+                user(id: $id) {
+                    ...UserFields
+                }
+            }
+
+            fragment UserFields on User {
+                id
+                name
+            }
+        "#,
+    );
+
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn it_collects_the_diagnostics_of_every_rule() {
+    let diagnostics = validate_source(
+        r#"
+            { user(id: 1) { id } }
+
+            type Admin { id: ID }
+        "#,
+    );
+
+    assert!(!diagnostics.is_empty());
+    assert!(matches!(diagnostics[0].severity, DiagnosticSeverity::Error));
+    assert_eq!(diagnostics[0].range.start.line, 3);
+}
+
+#[test]
+fn it_runs_every_rule() {
+    let diagnostics = validate_source(
+        r#"
+            { user(fake: 1) { foobar name { length } } }
+
+            type Admin { id: ID }
+        "#,
+    );
+
+    let messages = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        messages,
+        [
+            "The 'Admin' definition is not executable.",
+            "Field 'name' must not have a selection set; it is a scalar/enum.",
+            "Field 'foobar' does not exist on type 'User'.",
+            "Unknown argument 'fake' on field 'user' of type 'Query'.",
+            "Field 'user' argument 'id' of type 'ID!' is required, but it was not provided.",
+        ]
+    );
+}
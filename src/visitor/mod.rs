@@ -1,15 +1,1495 @@
-use crate::parser::types::{Document, Node};
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
 
-pub fn visit(document: Document) {
-    let visitor = Visitor::new(document);
+use crate::lsp::types::{Position, Range};
+use crate::parser::types::{
+    Argument, Definition, Directive, Document, ExecutableDirectiveLocation, Field, FieldDefinition,
+    FragmentDefinition, FragmentSpread, InlineFragment, Name, NamedType, Node, OperationDefinition,
+    OperationType, Selection, SelectionSet, Value, Variable, VariableDefinition,
+};
+use crate::schema::{Schema, TypeDefinition};
+
+mod tests;
+
+/// A node whose children are being walked.
+#[derive(Debug, Clone, Copy)]
+pub enum AncestorRef<'a> {
+    Document(&'a Document),
+    OperationDefinition(&'a OperationDefinition),
+    FragmentDefinition(&'a FragmentDefinition),
+    VariableDefinition(&'a VariableDefinition),
+    SelectionSet(&'a SelectionSet),
+    Field(&'a Field),
+    FragmentSpread(&'a FragmentSpread),
+    InlineFragment(&'a InlineFragment),
+    Directive(&'a Directive),
+    Argument(&'a Argument),
+}
+
+/// What the walker knows about where the current node sits in the document.
+#[derive(Debug, Default)]
+pub struct VisitorContext<'a> {
+    /// The ancestors of the current node, starting with the document and
+    /// ending with its parent. The current node itself is not included.
+    pub ancestors: Vec<AncestorRef<'a>>,
+}
+
+impl<'a> VisitorContext<'a> {
+    pub fn parent(&self) -> Option<&AncestorRef<'a>> {
+        self.ancestors.last()
+    }
+
+    /// The operation the current node is in, if it isn't in a fragment.
+    pub fn operation(&self) -> Option<&'a OperationDefinition> {
+        self.ancestors.iter().find_map(|ancestor| match ancestor {
+            AncestorRef::OperationDefinition(operation) => Some(*operation),
+            _ => None,
+        })
+    }
+}
+
+/// Tells the walker how to carry on after a hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisitorAction {
+    Continue,
+    /// Skip the children of the node just entered, moving on to its siblings.
+    SkipChildren,
+    /// Stop the walk entirely.
+    Stop,
+}
+
+/// Hooks called while walking the executable parts of a document. Every hook
+/// does nothing by default, so a visitor only implements the ones it needs.
+/// The `VisitorAction` a hook returns decides how the walk carries on.
+pub trait Visitor {
+    fn enter_document(&mut self, _ctx: &VisitorContext, _node: &Document) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_document(&mut self, _ctx: &VisitorContext, _node: &Document) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &OperationDefinition,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_operation_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &OperationDefinition,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &FragmentDefinition,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_fragment_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &FragmentDefinition,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &VariableDefinition,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_variable_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &VariableDefinition,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_selection_set(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &SelectionSet,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_selection_set(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &SelectionSet,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_field(&mut self, _ctx: &VisitorContext, _node: &Field) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_field(&mut self, _ctx: &VisitorContext, _node: &Field) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &FragmentSpread,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_fragment_spread(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &FragmentSpread,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &InlineFragment,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_inline_fragment(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &InlineFragment,
+    ) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_directive(&mut self, _ctx: &VisitorContext, _node: &Directive) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_directive(&mut self, _ctx: &VisitorContext, _node: &Directive) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_argument(&mut self, _ctx: &VisitorContext, _node: &Argument) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_argument(&mut self, _ctx: &VisitorContext, _node: &Argument) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    /// Called for variables used in argument values, not for the variable
+    /// a variable definition defines.
+    fn enter_variable(&mut self, _ctx: &VisitorContext, _node: &Variable) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_variable(&mut self, _ctx: &VisitorContext, _node: &Variable) -> VisitorAction {
+        VisitorAction::Continue
+    }
+}
+
+/// Walks the operations and fragments of the document depth first, calling
+/// the visitor's `enter_*` hook before a node's children and its `leave_*`
+/// hook after them. Type system definitions are skipped.
+pub fn walk_document(visitor: &mut impl Visitor, document: &Document) {
+    let _ = walk_document_inner(visitor, &mut VisitorContext::default(), document);
+}
+
+fn walk_document_inner<'a, V: Visitor>(
+    visitor: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    node: &'a Document,
+) -> Flow {
+    if enter(visitor.enter_document(ctx, node))? {
+        ctx.ancestors.push(AncestorRef::Document(node));
+
+        for definition in &node.definitions {
+            match definition {
+                Definition::OperationDefinition(node) => {
+                    walk_operation_definition(visitor, ctx, node)?
+                }
+                Definition::FragmentDefinition(node) => {
+                    walk_fragment_definition(visitor, ctx, node)?
+                }
+                _ => {}
+            }
+        }
+
+        ctx.ancestors.pop();
+    }
+
+    leave(visitor.leave_document(ctx, node))
+}
+
+fn walk_operation_definition<'a, V: Visitor>(
+    visitor: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    node: &'a OperationDefinition,
+) -> Flow {
+    if enter(visitor.enter_operation_definition(ctx, node))? {
+        ctx.ancestors.push(AncestorRef::OperationDefinition(node));
+
+        for variable_definition in &node.variable_definitions {
+            walk_variable_definition(visitor, ctx, variable_definition)?;
+        }
+        walk_directives(visitor, ctx, &node.directives)?;
+        walk_selection_set(visitor, ctx, &node.selection_set)?;
+
+        ctx.ancestors.pop();
+    }
+
+    leave(visitor.leave_operation_definition(ctx, node))
+}
+
+fn walk_fragment_definition<'a, V: Visitor>(
+    visitor: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    node: &'a FragmentDefinition,
+) -> Flow {
+    if enter(visitor.enter_fragment_definition(ctx, node))? {
+        ctx.ancestors.push(AncestorRef::FragmentDefinition(node));
+
+        walk_directives(visitor, ctx, &node.directives)?;
+        walk_selection_set(visitor, ctx, &node.selection_set)?;
+
+        ctx.ancestors.pop();
+    }
+
+    leave(visitor.leave_fragment_definition(ctx, node))
+}
+
+fn walk_variable_definition<'a, V: Visitor>(
+    visitor: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    node: &'a VariableDefinition,
+) -> Flow {
+    if enter(visitor.enter_variable_definition(ctx, node))? {
+        ctx.ancestors.push(AncestorRef::VariableDefinition(node));
+        walk_directives(visitor, ctx, &node.directives)?;
+        ctx.ancestors.pop();
+    }
+
+    leave(visitor.leave_variable_definition(ctx, node))
+}
+
+fn walk_selection_set<'a, V: Visitor>(
+    visitor: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    node: &'a SelectionSet,
+) -> Flow {
+    if enter(visitor.enter_selection_set(ctx, node))? {
+        ctx.ancestors.push(AncestorRef::SelectionSet(node));
+
+        for selection in &node.selections {
+            match selection {
+                Selection::Field(field) => walk_field(visitor, ctx, field)?,
+                Selection::FragmentSpread(fragment_spread) => {
+                    walk_fragment_spread(visitor, ctx, fragment_spread)?
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    walk_inline_fragment(visitor, ctx, inline_fragment)?
+                }
+            }
+        }
+
+        ctx.ancestors.pop();
+    }
+
+    leave(visitor.leave_selection_set(ctx, node))
+}
+
+fn walk_field<'a, V: Visitor>(
+    visitor: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    node: &'a Field,
+) -> Flow {
+    if enter(visitor.enter_field(ctx, node))? {
+        ctx.ancestors.push(AncestorRef::Field(node));
+
+        walk_arguments(visitor, ctx, &node.arguments)?;
+        walk_directives(visitor, ctx, &node.directives)?;
+        if let Some(selection_set) = &node.selection_set {
+            walk_selection_set(visitor, ctx, selection_set)?;
+        }
+
+        ctx.ancestors.pop();
+    }
+
+    leave(visitor.leave_field(ctx, node))
+}
+
+fn walk_fragment_spread<'a, V: Visitor>(
+    visitor: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    node: &'a FragmentSpread,
+) -> Flow {
+    if enter(visitor.enter_fragment_spread(ctx, node))? {
+        ctx.ancestors.push(AncestorRef::FragmentSpread(node));
+        walk_directives(visitor, ctx, &node.directives)?;
+        ctx.ancestors.pop();
+    }
+
+    leave(visitor.leave_fragment_spread(ctx, node))
+}
+
+fn walk_inline_fragment<'a, V: Visitor>(
+    visitor: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    node: &'a InlineFragment,
+) -> Flow {
+    if enter(visitor.enter_inline_fragment(ctx, node))? {
+        ctx.ancestors.push(AncestorRef::InlineFragment(node));
+
+        walk_directives(visitor, ctx, &node.directives)?;
+        walk_selection_set(visitor, ctx, &node.selection_set)?;
+
+        ctx.ancestors.pop();
+    }
+
+    leave(visitor.leave_inline_fragment(ctx, node))
+}
+
+fn walk_directives<'a, V: Visitor>(
+    visitor: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    directives: &'a [Directive],
+) -> Flow {
+    for directive in directives {
+        if enter(visitor.enter_directive(ctx, directive))? {
+            ctx.ancestors.push(AncestorRef::Directive(directive));
+            walk_arguments(visitor, ctx, &directive.arguments)?;
+            ctx.ancestors.pop();
+        }
+        leave(visitor.leave_directive(ctx, directive))?;
+    }
+
+    ControlFlow::Continue(())
+}
+
+fn walk_arguments<'a, V: Visitor>(
+    visitor: &mut V,
+    ctx: &mut VisitorContext<'a>,
+    arguments: &'a [Argument],
+) -> Flow {
+    for argument in arguments {
+        if enter(visitor.enter_argument(ctx, argument))? {
+            ctx.ancestors.push(AncestorRef::Argument(argument));
+            walk_value(visitor, ctx, &argument.value)?;
+            ctx.ancestors.pop();
+        }
+        leave(visitor.leave_argument(ctx, argument))?;
+    }
+
+    ControlFlow::Continue(())
+}
+
+fn walk_value<V: Visitor>(visitor: &mut V, ctx: &VisitorContext, node: &Value) -> Flow {
+    match node {
+        Value::Variable(variable) => {
+            enter(visitor.enter_variable(ctx, variable))?;
+            leave(visitor.leave_variable(ctx, variable))
+        }
+        Value::ListValue(list) => {
+            for value in &list.values {
+                walk_value(visitor, ctx, value)?;
+            }
+            ControlFlow::Continue(())
+        }
+        Value::ObjectValue(object) => {
+            for field in &object.fields {
+                walk_value(visitor, ctx, &field.value)?;
+            }
+            ControlFlow::Continue(())
+        }
+        _ => ControlFlow::Continue(()),
+    }
+}
+
+/// Like `Visitor`, but the hooks can modify the nodes they are given. There
+/// is no `VisitorContext`, as ancestors can't be borrowed while their children
+/// are being modified.
+pub trait VisitorMut {
+    fn enter_document(&mut self, _node: &mut Document) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_document(&mut self, _node: &mut Document) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_operation_definition(&mut self, _node: &mut OperationDefinition) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_operation_definition(&mut self, _node: &mut OperationDefinition) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_fragment_definition(&mut self, _node: &mut FragmentDefinition) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_fragment_definition(&mut self, _node: &mut FragmentDefinition) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_variable_definition(&mut self, _node: &mut VariableDefinition) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_variable_definition(&mut self, _node: &mut VariableDefinition) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_selection_set(&mut self, _node: &mut SelectionSet) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_selection_set(&mut self, _node: &mut SelectionSet) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_field(&mut self, _node: &mut Field) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_field(&mut self, _node: &mut Field) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_fragment_spread(&mut self, _node: &mut FragmentSpread) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_fragment_spread(&mut self, _node: &mut FragmentSpread) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_inline_fragment(&mut self, _node: &mut InlineFragment) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_inline_fragment(&mut self, _node: &mut InlineFragment) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_directive(&mut self, _node: &mut Directive) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_directive(&mut self, _node: &mut Directive) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_argument(&mut self, _node: &mut Argument) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_argument(&mut self, _node: &mut Argument) -> VisitorAction {
+        VisitorAction::Continue
+    }
+
+    fn enter_variable(&mut self, _node: &mut Variable) -> VisitorAction {
+        VisitorAction::Continue
+    }
+    fn leave_variable(&mut self, _node: &mut Variable) -> VisitorAction {
+        VisitorAction::Continue
+    }
+}
+
+/// Walks the document like `walk_document`, handing each hook a mutable
+/// reference to the node and following the `VisitorAction` it returns.
+pub fn walk_document_mut(visitor: &mut impl VisitorMut, document: &mut Document) {
+    let _ = walk_document_mut_inner(visitor, document);
+}
+
+type Flow = ControlFlow<()>;
+
+/// Whether to walk the children of a node that was just entered.
+fn enter(action: VisitorAction) -> ControlFlow<(), bool> {
+    match action {
+        VisitorAction::Continue => ControlFlow::Continue(true),
+        VisitorAction::SkipChildren => ControlFlow::Continue(false),
+        VisitorAction::Stop => ControlFlow::Break(()),
+    }
+}
+
+fn leave(action: VisitorAction) -> Flow {
+    match action {
+        VisitorAction::Stop => ControlFlow::Break(()),
+        _ => ControlFlow::Continue(()),
+    }
+}
+
+fn walk_document_mut_inner<V: VisitorMut>(visitor: &mut V, node: &mut Document) -> Flow {
+    if enter(visitor.enter_document(node))? {
+        for definition in &mut node.definitions {
+            match definition {
+                Definition::OperationDefinition(node) => {
+                    walk_operation_definition_mut(visitor, node)?
+                }
+                Definition::FragmentDefinition(node) => {
+                    walk_fragment_definition_mut(visitor, node)?
+                }
+                _ => {}
+            }
+        }
+    }
+
+    leave(visitor.leave_document(node))
+}
+
+fn walk_operation_definition_mut<V: VisitorMut>(
+    visitor: &mut V,
+    node: &mut OperationDefinition,
+) -> Flow {
+    if enter(visitor.enter_operation_definition(node))? {
+        for variable_definition in &mut node.variable_definitions {
+            walk_variable_definition_mut(visitor, variable_definition)?;
+        }
+        walk_directives_mut(visitor, &mut node.directives)?;
+        walk_selection_set_mut(visitor, &mut node.selection_set)?;
+    }
+
+    leave(visitor.leave_operation_definition(node))
+}
+
+fn walk_fragment_definition_mut<V: VisitorMut>(
+    visitor: &mut V,
+    node: &mut FragmentDefinition,
+) -> Flow {
+    if enter(visitor.enter_fragment_definition(node))? {
+        walk_directives_mut(visitor, &mut node.directives)?;
+        walk_selection_set_mut(visitor, &mut node.selection_set)?;
+    }
+
+    leave(visitor.leave_fragment_definition(node))
+}
+
+fn walk_variable_definition_mut<V: VisitorMut>(
+    visitor: &mut V,
+    node: &mut VariableDefinition,
+) -> Flow {
+    if enter(visitor.enter_variable_definition(node))? {
+        walk_directives_mut(visitor, &mut node.directives)?;
+    }
+
+    leave(visitor.leave_variable_definition(node))
+}
+
+fn walk_selection_set_mut<V: VisitorMut>(visitor: &mut V, node: &mut SelectionSet) -> Flow {
+    if enter(visitor.enter_selection_set(node))? {
+        for selection in &mut node.selections {
+            match selection {
+                Selection::Field(field) => walk_field_mut(visitor, field)?,
+                Selection::FragmentSpread(fragment_spread) => {
+                    walk_fragment_spread_mut(visitor, fragment_spread)?
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    walk_inline_fragment_mut(visitor, inline_fragment)?
+                }
+            }
+        }
+    }
+
+    leave(visitor.leave_selection_set(node))
+}
+
+fn walk_field_mut<V: VisitorMut>(visitor: &mut V, node: &mut Field) -> Flow {
+    if enter(visitor.enter_field(node))? {
+        walk_arguments_mut(visitor, &mut node.arguments)?;
+        walk_directives_mut(visitor, &mut node.directives)?;
+        if let Some(selection_set) = &mut node.selection_set {
+            walk_selection_set_mut(visitor, selection_set)?;
+        }
+    }
+
+    leave(visitor.leave_field(node))
+}
+
+fn walk_fragment_spread_mut<V: VisitorMut>(visitor: &mut V, node: &mut FragmentSpread) -> Flow {
+    if enter(visitor.enter_fragment_spread(node))? {
+        walk_directives_mut(visitor, &mut node.directives)?;
+    }
+
+    leave(visitor.leave_fragment_spread(node))
+}
+
+fn walk_inline_fragment_mut<V: VisitorMut>(visitor: &mut V, node: &mut InlineFragment) -> Flow {
+    if enter(visitor.enter_inline_fragment(node))? {
+        walk_directives_mut(visitor, &mut node.directives)?;
+        walk_selection_set_mut(visitor, &mut node.selection_set)?;
+    }
+
+    leave(visitor.leave_inline_fragment(node))
+}
+
+fn walk_directives_mut<V: VisitorMut>(visitor: &mut V, directives: &mut [Directive]) -> Flow {
+    for directive in directives {
+        if enter(visitor.enter_directive(directive))? {
+            walk_arguments_mut(visitor, &mut directive.arguments)?;
+        }
+        leave(visitor.leave_directive(directive))?;
+    }
+
+    ControlFlow::Continue(())
+}
+
+fn walk_arguments_mut<V: VisitorMut>(visitor: &mut V, arguments: &mut [Argument]) -> Flow {
+    for argument in arguments {
+        if enter(visitor.enter_argument(argument))? {
+            walk_value_mut(visitor, &mut argument.value)?;
+        }
+        leave(visitor.leave_argument(argument))?;
+    }
+
+    ControlFlow::Continue(())
+}
+
+fn walk_value_mut<V: VisitorMut>(visitor: &mut V, node: &mut Value) -> Flow {
+    match node {
+        Value::Variable(variable) => {
+            enter(visitor.enter_variable(variable))?;
+            leave(visitor.leave_variable(variable))
+        }
+        Value::ListValue(list) => {
+            for value in &mut list.values {
+                walk_value_mut(visitor, value)?;
+            }
+            ControlFlow::Continue(())
+        }
+        Value::ObjectValue(object) => {
+            for field in &mut object.fields {
+                walk_value_mut(visitor, &mut field.value)?;
+            }
+            ControlFlow::Continue(())
+        }
+        _ => ControlFlow::Continue(()),
+    }
+}
+
+/// Adds a `__typename` field to every selection set on a composite type, so
+/// clients can tell which concrete type each object in the response is. The
+/// root selection set of an operation is left alone.
+pub struct AddTypenameVisitor;
+
+impl AddTypenameVisitor {
+    fn add_typename(selection_set: &mut SelectionSet) {
+        let has_typename = selection_set
+            .selections
+            .iter()
+            .any(|selection| match selection {
+                Selection::Field(field) => field.response_name() == "__typename",
+                _ => false,
+            });
+
+        if has_typename {
+            return;
+        }
+
+        // the field was not written in the source, so it has no range
+        selection_set.selections.push(Selection::Field(Field {
+            alias: None,
+            name: Name {
+                value: String::from("__typename"),
+                position: Range::ZERO,
+            },
+            arguments: Vec::new(),
+            directives: Vec::new(),
+            selection_set: None,
+            position: Range::ZERO,
+        }));
+    }
 }
 
-pub struct Visitor {
-    document: Document,
+impl VisitorMut for AddTypenameVisitor {
+    fn enter_field(&mut self, node: &mut Field) -> VisitorAction {
+        if let Some(selection_set) = &mut node.selection_set {
+            AddTypenameVisitor::add_typename(selection_set);
+        }
+
+        VisitorAction::Continue
+    }
+
+    fn enter_fragment_definition(&mut self, node: &mut FragmentDefinition) -> VisitorAction {
+        AddTypenameVisitor::add_typename(&mut node.selection_set);
+
+        VisitorAction::Continue
+    }
+}
+
+/// Tracks the schema type and field definition of the node being visited, for
+/// visitors that need to check a document against a schema. A stack entry is
+/// `None` when the document refers to a type or field the schema doesn't
+/// define.
+pub struct TypeInfoVisitor<'s> {
+    schema: &'s Schema,
+    type_stack: Vec<Option<&'s TypeDefinition>>,
+    field_stack: Vec<Option<&'s FieldDefinition>>,
+}
+
+impl<'s> TypeInfoVisitor<'s> {
+    pub fn new(schema: &'s Schema) -> TypeInfoVisitor<'s> {
+        TypeInfoVisitor {
+            schema,
+            type_stack: Vec::new(),
+            field_stack: Vec::new(),
+        }
+    }
+
+    /// The type whose fields the current selection set selects from, or the
+    /// type of the field just entered.
+    pub fn current_type(&self) -> Option<&'s TypeDefinition> {
+        self.type_stack.last().copied().flatten()
+    }
+
+    /// The definition of the field just entered.
+    pub fn current_field_def(&self) -> Option<&'s FieldDefinition> {
+        self.field_stack.last().copied().flatten()
+    }
+
+    fn push_named_type(&mut self, name: &str) {
+        self.type_stack.push(self.schema.get_type(name));
+    }
+}
+
+impl Visitor for TypeInfoVisitor<'_> {
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        node: &OperationDefinition,
+    ) -> VisitorAction {
+        self.type_stack.push(self.schema.root_type(&node.operation));
+        VisitorAction::Continue
+    }
+
+    fn leave_operation_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &OperationDefinition,
+    ) -> VisitorAction {
+        self.type_stack.pop();
+        VisitorAction::Continue
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        node: &FragmentDefinition,
+    ) -> VisitorAction {
+        self.push_named_type(&node.type_condition.name.value);
+        VisitorAction::Continue
+    }
+
+    fn leave_fragment_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &FragmentDefinition,
+    ) -> VisitorAction {
+        self.type_stack.pop();
+        VisitorAction::Continue
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        _ctx: &VisitorContext,
+        node: &InlineFragment,
+    ) -> VisitorAction {
+        match &node.type_condition {
+            Some(type_condition) => self.push_named_type(&type_condition.name.value),
+            // without a type condition the fragment selects from the
+            // enclosing type
+            None => self
+                .type_stack
+                .push(self.type_stack.last().copied().flatten()),
+        }
+
+        VisitorAction::Continue
+    }
+
+    fn leave_inline_fragment(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &InlineFragment,
+    ) -> VisitorAction {
+        self.type_stack.pop();
+        VisitorAction::Continue
+    }
+
+    fn enter_field(&mut self, _ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        // https://spec.graphql.org/October2021/#sec-Type-Name-Introspection
+        if node.name.value == "__typename" {
+            self.field_stack.push(None);
+            self.push_named_type("String");
+            return VisitorAction::Continue;
+        }
+
+        let parent_type = self.type_stack.last().copied().flatten();
+        let field_def = parent_type.and_then(|parent_type| parent_type.field(&node.name.value));
+
+        self.field_stack.push(field_def);
+        match field_def {
+            Some(field_def) => self.push_named_type(field_def.field_type.named_type_name()),
+            None => self.type_stack.push(None),
+        }
+
+        VisitorAction::Continue
+    }
+
+    fn leave_field(&mut self, _ctx: &VisitorContext, _node: &Field) -> VisitorAction {
+        self.type_stack.pop();
+        self.field_stack.pop();
+
+        VisitorAction::Continue
+    }
+}
+
+/// What the schema says about the innermost selection set and field that
+/// contain a position.
+#[derive(Debug, Default)]
+pub struct TypeInfoAtPosition<'s> {
+    /// The type the innermost selection set containing the position selects
+    /// from.
+    pub parent_type: Option<&'s TypeDefinition>,
+    /// The type the innermost field containing the position is selected on.
+    pub field_parent_type: Option<&'s TypeDefinition>,
+    /// The definition of the innermost field containing the position.
+    pub field_def: Option<&'s FieldDefinition>,
+}
+
+/// Resolves the types around a position, for features like hover and
+/// completion that need to know what the schema allows under the cursor.
+pub fn type_info_at_position<'s>(
+    schema: &'s Schema,
+    doc: &Document,
+    pos: &Position,
+) -> TypeInfoAtPosition<'s> {
+    let mut visitor = TypeInfoAtPositionVisitor {
+        type_info: TypeInfoVisitor::new(schema),
+        pos,
+        result: TypeInfoAtPosition::default(),
+    };
+
+    walk_document(&mut visitor, doc);
+    visitor.result
+}
+
+struct TypeInfoAtPositionVisitor<'s, 'p> {
+    type_info: TypeInfoVisitor<'s>,
+    pos: &'p Position,
+    result: TypeInfoAtPosition<'s>,
+}
+
+impl Visitor for TypeInfoAtPositionVisitor<'_, '_> {
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &OperationDefinition,
+    ) -> VisitorAction {
+        if !node.position().contains(self.pos) {
+            return VisitorAction::SkipChildren;
+        }
+
+        self.type_info.enter_operation_definition(ctx, node)
+    }
+
+    fn leave_operation_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &OperationDefinition,
+    ) -> VisitorAction {
+        if !node.position().contains(self.pos) {
+            return VisitorAction::Continue;
+        }
+
+        self.type_info.leave_operation_definition(ctx, node)
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &FragmentDefinition,
+    ) -> VisitorAction {
+        if !node.position().contains(self.pos) {
+            return VisitorAction::SkipChildren;
+        }
+
+        self.type_info.enter_fragment_definition(ctx, node)
+    }
+
+    fn leave_fragment_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &FragmentDefinition,
+    ) -> VisitorAction {
+        if !node.position().contains(self.pos) {
+            return VisitorAction::Continue;
+        }
+
+        self.type_info.leave_fragment_definition(ctx, node)
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &InlineFragment,
+    ) -> VisitorAction {
+        self.type_info.enter_inline_fragment(ctx, node)
+    }
+
+    fn leave_inline_fragment(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &InlineFragment,
+    ) -> VisitorAction {
+        self.type_info.leave_inline_fragment(ctx, node)
+    }
+
+    fn enter_selection_set(&mut self, _ctx: &VisitorContext, node: &SelectionSet) -> VisitorAction {
+        if node.position().contains(self.pos) {
+            self.result.parent_type = self.type_info.type_stack.last().copied().flatten();
+        }
+
+        VisitorAction::Continue
+    }
+
+    fn enter_field(&mut self, ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        let parent_type = self.type_info.type_stack.last().copied().flatten();
+        self.type_info.enter_field(ctx, node);
+
+        if node.position().contains(self.pos) {
+            self.result.field_parent_type = parent_type;
+            self.result.field_def = self.type_info.field_stack.last().copied().flatten();
+        }
+
+        VisitorAction::Continue
+    }
+
+    fn leave_field(&mut self, ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        self.type_info.leave_field(ctx, node)
+    }
+}
+
+/// Finds fragments that spread themselves, directly or through other
+/// fragments.
+/// https://spec.graphql.org/October2021/#sec-Fragment-spreads-must-not-form-cycles
+pub struct CycleDetector<'a> {
+    doc: &'a Document,
+    /// The fragments each fragment spreads directly, in document order.
+    visited: HashMap<String, Vec<String>>,
+    cycles: Vec<Vec<String>>,
+    current_fragment: Option<String>,
+}
+
+impl<'a> CycleDetector<'a> {
+    /// Returns every cycle found as the path of fragment names that forms
+    /// it, starting and ending with the same fragment.
+    pub fn detect(doc: &'a Document) -> Vec<Vec<String>> {
+        let mut detector = CycleDetector {
+            doc,
+            visited: HashMap::new(),
+            cycles: Vec::new(),
+            current_fragment: None,
+        };
+
+        walk_document(&mut detector, doc);
+
+        let mut done = HashSet::new();
+        for fragment in detector.doc.fragments() {
+            let mut path = Vec::new();
+            detector.search(&fragment.name.value, &mut path, &mut done);
+        }
+
+        detector.cycles
+    }
+
+    fn search(&mut self, name: &str, path: &mut Vec<String>, done: &mut HashSet<String>) {
+        if let Some(index) = path.iter().position(|fragment| fragment == name) {
+            let mut cycle = path[index..].to_vec();
+            cycle.push(name.to_string());
+            self.cycles.push(cycle);
+            return;
+        }
+
+        if done.contains(name) {
+            return;
+        }
+
+        path.push(name.to_string());
+        let spreads = self.visited.get(name).cloned().unwrap_or_default();
+        for spread in spreads {
+            self.search(&spread, path, done);
+        }
+        path.pop();
+
+        done.insert(name.to_string());
+    }
+}
+
+impl Visitor for CycleDetector<'_> {
+    fn enter_fragment_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        node: &FragmentDefinition,
+    ) -> VisitorAction {
+        self.current_fragment = Some(node.name.value.clone());
+        VisitorAction::Continue
+    }
+
+    fn leave_fragment_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &FragmentDefinition,
+    ) -> VisitorAction {
+        self.current_fragment = None;
+        VisitorAction::Continue
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        _ctx: &VisitorContext,
+        node: &FragmentSpread,
+    ) -> VisitorAction {
+        // spreads in operations can't be part of a cycle, as nothing can
+        // spread an operation
+        if let Some(fragment) = &self.current_fragment {
+            self.visited
+                .entry(fragment.clone())
+                .or_default()
+                .push(node.name.value.clone());
+        }
+
+        VisitorAction::Continue
+    }
+}
+
+/// Collects the names of the variables used in argument values.
+#[derive(Debug, Default)]
+pub struct UsedVariables {
+    pub names: HashSet<String>,
+}
+
+impl UsedVariables {
+    /// The variables the operation uses, including those used by the
+    /// fragments it spreads.
+    /// https://spec.graphql.org/October2021/#sec-All-Variable-Uses-Defined
+    pub fn collect(op: &OperationDefinition, doc: &Document) -> HashSet<String> {
+        let mut visitor = UsedVariables::default();
+        let _ = walk_operation_definition(&mut visitor, &mut VisitorContext::default(), op);
+
+        for name in UsedFragments::collect_transitive(op, doc) {
+            if let Some(fragment) = doc.find_fragment(&name) {
+                let _ = walk_fragment_definition(
+                    &mut visitor,
+                    &mut VisitorContext::default(),
+                    fragment,
+                );
+            }
+        }
+
+        visitor.names
+    }
+}
+
+impl Visitor for UsedVariables {
+    fn enter_variable(&mut self, _ctx: &VisitorContext, node: &Variable) -> VisitorAction {
+        self.names.insert(node.name.value.clone());
+        VisitorAction::Continue
+    }
+}
+
+/// Collects the ranges of the names of every use of one variable.
+#[derive(Debug)]
+pub struct VariableUses<'n> {
+    name: &'n str,
+    pub ranges: Vec<Range>,
 }
 
-impl Visitor {
-    pub fn new(document: Document) -> Self {
-        Self { document }
+impl<'n> VariableUses<'n> {
+    /// The uses in the operation, including those in the fragments it
+    /// spreads. The definition of the variable isn't a use.
+    pub fn collect(name: &'n str, op: &OperationDefinition, doc: &Document) -> Vec<Range> {
+        let mut visitor = VariableUses {
+            name,
+            ranges: Vec::new(),
+        };
+        let _ = walk_operation_definition(&mut visitor, &mut VisitorContext::default(), op);
+
+        for fragment_name in UsedFragments::collect_transitive(op, doc) {
+            if let Some(fragment) = doc.find_fragment(&fragment_name) {
+                let _ = walk_fragment_definition(
+                    &mut visitor,
+                    &mut VisitorContext::default(),
+                    fragment,
+                );
+            }
+        }
+
+        visitor.ranges
+    }
+}
+
+impl Visitor for VariableUses<'_> {
+    fn enter_variable(&mut self, _ctx: &VisitorContext, node: &Variable) -> VisitorAction {
+        if node.name.value == self.name {
+            self.ranges.push(node.name.position.clone());
+        }
+        VisitorAction::Continue
+    }
+}
+
+/// Collects the names of the fragments spread.
+#[derive(Debug, Default)]
+pub struct UsedFragments {
+    pub names: HashSet<String>,
+}
+
+impl UsedFragments {
+    /// The fragments the operation spreads, directly or through other
+    /// fragments. Spreads of fragments the document doesn't define are
+    /// included, but can't be followed.
+    pub fn collect_transitive(op: &OperationDefinition, doc: &Document) -> HashSet<String> {
+        let mut visitor = UsedFragments::default();
+        let _ = walk_operation_definition(&mut visitor, &mut VisitorContext::default(), op);
+
+        let mut pending: Vec<String> = visitor.names.iter().cloned().collect();
+        let mut followed = HashSet::new();
+
+        while let Some(name) = pending.pop() {
+            if !followed.insert(name.clone()) {
+                continue;
+            }
+
+            if let Some(fragment) = doc.find_fragment(&name) {
+                let mut fragment_visitor = UsedFragments::default();
+                let _ = walk_fragment_definition(
+                    &mut fragment_visitor,
+                    &mut VisitorContext::default(),
+                    fragment,
+                );
+                pending.extend(fragment_visitor.names.iter().cloned());
+                visitor.names.extend(fragment_visitor.names);
+            }
+        }
+
+        visitor.names
+    }
+}
+
+impl Visitor for UsedFragments {
+    fn enter_fragment_spread(
+        &mut self,
+        _ctx: &VisitorContext,
+        node: &FragmentSpread,
+    ) -> VisitorAction {
+        self.names.insert(node.name.value.clone());
+        VisitorAction::Continue
+    }
+}
+
+/// Measures how deeply the operations of a document nest their selection
+/// sets. Fragment spreads are followed into their definitions, and fragments
+/// count towards the depth of the selection set they are spread in.
+pub struct MaxDepthVisitor<'a> {
+    pub max_depth: usize,
+    current_depth: usize,
+    doc: &'a Document,
+    /// The fragments currently being followed, so cycles aren't walked
+    /// forever.
+    fragment_path: Vec<String>,
+}
+
+impl<'a> MaxDepthVisitor<'a> {
+    /// The depth of the most deeply nested operation. A query selecting only
+    /// scalar fields has a depth of 1.
+    pub fn measure(doc: &'a Document) -> usize {
+        let mut visitor = MaxDepthVisitor {
+            max_depth: 0,
+            current_depth: 0,
+            doc,
+            fragment_path: Vec::new(),
+        };
+
+        for operation in doc.operations() {
+            let _ =
+                walk_operation_definition(&mut visitor, &mut VisitorContext::default(), operation);
+        }
+
+        visitor.max_depth
+    }
+
+    /// Whether the selection set adds a level of nesting, rather than being
+    /// merged into the selection set around it.
+    fn is_nested(ctx: &VisitorContext) -> bool {
+        !matches!(
+            ctx.parent(),
+            Some(AncestorRef::InlineFragment(_)) | Some(AncestorRef::FragmentSpread(_))
+        )
+    }
+}
+
+impl Visitor for MaxDepthVisitor<'_> {
+    fn enter_selection_set(&mut self, ctx: &VisitorContext, _node: &SelectionSet) -> VisitorAction {
+        if MaxDepthVisitor::is_nested(ctx) {
+            self.current_depth += 1;
+            self.max_depth = self.max_depth.max(self.current_depth);
+        }
+
+        VisitorAction::Continue
+    }
+
+    fn leave_selection_set(&mut self, ctx: &VisitorContext, _node: &SelectionSet) -> VisitorAction {
+        if MaxDepthVisitor::is_nested(ctx) {
+            self.current_depth -= 1;
+        }
+
+        VisitorAction::Continue
+    }
+
+    fn enter_fragment_spread(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &FragmentSpread,
+    ) -> VisitorAction {
+        let name = &node.name.value;
+        if self.fragment_path.contains(name) {
+            return VisitorAction::Continue;
+        }
+
+        let doc = self.doc;
+        if let Some(fragment) = doc.find_fragment(name) {
+            let mut fragment_ctx = VisitorContext {
+                ancestors: ctx.ancestors.clone(),
+            };
+            fragment_ctx
+                .ancestors
+                .push(AncestorRef::FragmentSpread(node));
+
+            self.fragment_path.push(name.clone());
+            let _ = walk_selection_set(self, &mut fragment_ctx, &fragment.selection_set);
+            self.fragment_path.pop();
+        }
+
+        VisitorAction::Continue
+    }
+}
+
+/// A reference to a node of an executable document.
+#[derive(Debug, Clone, Copy)]
+pub enum AstNodeRef<'a> {
+    OperationDefinition(&'a OperationDefinition),
+    FragmentDefinition(&'a FragmentDefinition),
+    VariableDefinition(&'a VariableDefinition),
+    Field(&'a Field),
+    FragmentSpread(&'a FragmentSpread),
+    InlineFragment(&'a InlineFragment),
+    Directive(&'a Directive),
+    Argument(&'a Argument),
+    NamedType(&'a NamedType),
+}
+
+/// Finds the innermost node of an operation or fragment that contains the
+/// position, for features like hover and completion that act on whatever is
+/// under the cursor.
+pub fn node_at_position<'a>(doc: &'a Document, pos: &Position) -> Option<AstNodeRef<'a>> {
+    doc.definitions
+        .iter()
+        .find_map(|definition| match definition {
+            Definition::OperationDefinition(node) if node.position().contains(pos) => {
+                Some(operation_definition_at(node, pos))
+            }
+            Definition::FragmentDefinition(node) if node.position().contains(pos) => {
+                Some(fragment_definition_at(node, pos))
+            }
+            _ => None,
+        })
+}
+
+fn find_containing<'a, T: Node>(nodes: &'a [T], pos: &Position) -> Option<&'a T> {
+    nodes.iter().find(|node| node.position().contains(pos))
+}
+
+fn operation_definition_at<'a>(node: &'a OperationDefinition, pos: &Position) -> AstNodeRef<'a> {
+    find_containing(&node.variable_definitions, pos)
+        .map(|variable_definition| variable_definition_at(variable_definition, pos))
+        .or_else(|| directives_at(&node.directives, pos))
+        .or_else(|| selection_set_at(&node.selection_set, pos))
+        .unwrap_or(AstNodeRef::OperationDefinition(node))
+}
+
+fn fragment_definition_at<'a>(node: &'a FragmentDefinition, pos: &Position) -> AstNodeRef<'a> {
+    named_type_at(&node.type_condition, pos)
+        .or_else(|| directives_at(&node.directives, pos))
+        .or_else(|| selection_set_at(&node.selection_set, pos))
+        .unwrap_or(AstNodeRef::FragmentDefinition(node))
+}
+
+fn variable_definition_at<'a>(node: &'a VariableDefinition, pos: &Position) -> AstNodeRef<'a> {
+    named_type_at(node.variable_type.named_type(), pos)
+        .or_else(|| directives_at(&node.directives, pos))
+        .unwrap_or(AstNodeRef::VariableDefinition(node))
+}
+
+fn named_type_at<'a>(node: &'a NamedType, pos: &Position) -> Option<AstNodeRef<'a>> {
+    if node.position().contains(pos) {
+        Some(AstNodeRef::NamedType(node))
+    } else {
+        None
+    }
+}
+
+fn selection_set_at<'a>(node: &'a SelectionSet, pos: &Position) -> Option<AstNodeRef<'a>> {
+    if !node.position().contains(pos) {
+        return None;
+    }
+
+    find_containing(&node.selections, pos).map(|selection| match selection {
+        Selection::Field(field) => field_at(field, pos),
+        Selection::FragmentSpread(fragment_spread) => {
+            directives_at(&fragment_spread.directives, pos)
+                .unwrap_or(AstNodeRef::FragmentSpread(fragment_spread))
+        }
+        Selection::InlineFragment(inline_fragment) => inline_fragment_at(inline_fragment, pos),
+    })
+}
+
+fn field_at<'a>(node: &'a Field, pos: &Position) -> AstNodeRef<'a> {
+    find_containing(&node.arguments, pos)
+        .map(AstNodeRef::Argument)
+        .or_else(|| directives_at(&node.directives, pos))
+        .or_else(|| {
+            node.selection_set
+                .as_ref()
+                .and_then(|selection_set| selection_set_at(selection_set, pos))
+        })
+        .unwrap_or(AstNodeRef::Field(node))
+}
+
+fn inline_fragment_at<'a>(node: &'a InlineFragment, pos: &Position) -> AstNodeRef<'a> {
+    node.type_condition
+        .as_ref()
+        .and_then(|type_condition| named_type_at(type_condition, pos))
+        .or_else(|| directives_at(&node.directives, pos))
+        .or_else(|| selection_set_at(&node.selection_set, pos))
+        .unwrap_or(AstNodeRef::InlineFragment(node))
+}
+
+fn directives_at<'a>(directives: &'a [Directive], pos: &Position) -> Option<AstNodeRef<'a>> {
+    find_containing(directives, pos).map(|directive| {
+        find_containing(&directive.arguments, pos)
+            .map(AstNodeRef::Argument)
+            .unwrap_or(AstNodeRef::Directive(directive))
+    })
+}
+
+/// Finds the innermost field that contains the position, even when the
+/// position is on one of its arguments or directives.
+pub fn field_at_position<'a>(doc: &'a Document, pos: &Position) -> Option<&'a Field> {
+    doc.definitions
+        .iter()
+        .find_map(|definition| match definition {
+            Definition::OperationDefinition(node) => {
+                field_in_selection_set_at(&node.selection_set, pos)
+            }
+            Definition::FragmentDefinition(node) => {
+                field_in_selection_set_at(&node.selection_set, pos)
+            }
+            _ => None,
+        })
+}
+
+fn field_in_selection_set_at<'a>(node: &'a SelectionSet, pos: &Position) -> Option<&'a Field> {
+    if !node.position().contains(pos) {
+        return None;
+    }
+
+    find_containing(&node.selections, pos).and_then(|selection| match selection {
+        Selection::Field(field) => field
+            .selection_set
+            .as_ref()
+            .and_then(|selection_set| field_in_selection_set_at(selection_set, pos))
+            .or(Some(field)),
+        Selection::FragmentSpread(_) => None,
+        Selection::InlineFragment(inline_fragment) => {
+            field_in_selection_set_at(&inline_fragment.selection_set, pos)
+        }
+    })
+}
+
+/// The location of the directive that contains the position, which decides
+/// the directives that may be used there.
+/// https://spec.graphql.org/October2021/#sec-Directives-Are-In-Valid-Locations
+pub fn directive_location_at(
+    doc: &Document,
+    pos: &Position,
+) -> Option<ExecutableDirectiveLocation> {
+    let mut finder = DirectiveLocationFinder {
+        pos,
+        location: None,
+    };
+
+    walk_document(&mut finder, doc);
+    finder.location
+}
+
+struct DirectiveLocationFinder<'p> {
+    pos: &'p Position,
+    location: Option<ExecutableDirectiveLocation>,
+}
+
+impl Visitor for DirectiveLocationFinder<'_> {
+    fn enter_directive(&mut self, ctx: &VisitorContext, node: &Directive) -> VisitorAction {
+        if !node.position().contains(self.pos) {
+            return VisitorAction::SkipChildren;
+        }
+
+        self.location = match ctx.parent() {
+            Some(AncestorRef::OperationDefinition(operation)) => match operation.operation {
+                OperationType::Query => Some(ExecutableDirectiveLocation::Query),
+                OperationType::Mutation => Some(ExecutableDirectiveLocation::Mutation),
+                OperationType::Subscription => Some(ExecutableDirectiveLocation::Subscription),
+            },
+            Some(AncestorRef::FragmentDefinition(_)) => {
+                Some(ExecutableDirectiveLocation::FragmentDefinition)
+            }
+            Some(AncestorRef::VariableDefinition(_)) => {
+                Some(ExecutableDirectiveLocation::VariableDefinition)
+            }
+            Some(AncestorRef::Field(_)) => Some(ExecutableDirectiveLocation::Field),
+            Some(AncestorRef::FragmentSpread(_)) => {
+                Some(ExecutableDirectiveLocation::FragmentSpread)
+            }
+            Some(AncestorRef::InlineFragment(_)) => {
+                Some(ExecutableDirectiveLocation::InlineFragment)
+            }
+            _ => None,
+        };
+
+        VisitorAction::Stop
+    }
+}
+
+/// Finds the first field, in walk order, that matches the predicate. The walk
+/// stops as soon as it is found.
+pub struct FindFirst<F> {
+    predicate: F,
+    pub found: Option<Field>,
+}
+
+impl<F: FnMut(&Field) -> bool> FindFirst<F> {
+    pub fn new(predicate: F) -> FindFirst<F> {
+        FindFirst {
+            predicate,
+            found: None,
+        }
+    }
+
+    pub fn find(document: &Document, predicate: F) -> Option<Field> {
+        let mut visitor = FindFirst::new(predicate);
+        walk_document(&mut visitor, document);
+        visitor.found
+    }
+}
+
+impl<F: FnMut(&Field) -> bool> Visitor for FindFirst<F> {
+    fn enter_field(&mut self, _ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        if (self.predicate)(node) {
+            self.found = Some(node.clone());
+            return VisitorAction::Stop;
+        }
+
+        VisitorAction::Continue
     }
 }
@@ -0,0 +1,743 @@
+#![cfg(test)]
+
+use super::*;
+use crate::lsp::types::Position;
+use crate::parser::parse_strict;
+use crate::parser::types::OperationType;
+
+#[derive(Default)]
+struct FieldCounter {
+    fields: usize,
+}
+
+impl Visitor for FieldCounter {
+    fn enter_field(&mut self, _ctx: &VisitorContext, _node: &Field) -> VisitorAction {
+        self.fields += 1;
+        VisitorAction::Continue
+    }
+}
+
+#[test]
+fn it_visits_every_field() {
+    let source = r#"
+        query GetUser($id: ID!) {
+            user(id: $id) {
+                name
+                friends @include(if: true) {
+                    name
+                }
+                ... on Admin {
+                    level
+                }
+                ...UserFields
+            }
+        }
+
+        fragment UserFields on User {
+            email
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    let mut counter = FieldCounter::default();
+    walk_document(&mut counter, &document);
+
+    assert_eq!(counter.fields, 6);
+}
+
+#[derive(Default)]
+struct EventRecorder {
+    events: Vec<String>,
+}
+
+impl Visitor for EventRecorder {
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &OperationDefinition,
+    ) -> VisitorAction {
+        self.events.push(String::from("enter operation"));
+        VisitorAction::Continue
+    }
+
+    fn leave_operation_definition(
+        &mut self,
+        _ctx: &VisitorContext,
+        _node: &OperationDefinition,
+    ) -> VisitorAction {
+        self.events.push(String::from("leave operation"));
+        VisitorAction::Continue
+    }
+
+    fn enter_field(&mut self, _ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        self.events.push(format!("enter {}", node.name.value));
+        VisitorAction::Continue
+    }
+
+    fn leave_field(&mut self, _ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        self.events.push(format!("leave {}", node.name.value));
+        VisitorAction::Continue
+    }
+
+    fn enter_argument(&mut self, _ctx: &VisitorContext, node: &Argument) -> VisitorAction {
+        self.events.push(format!("argument {}", node.name.value));
+        VisitorAction::Continue
+    }
+}
+
+#[test]
+fn it_calls_enter_and_leave_hooks_in_order() {
+    let document = parse_strict("{ user(id: 1) { name } }".to_string()).unwrap();
+
+    let mut recorder = EventRecorder::default();
+    walk_document(&mut recorder, &document);
+
+    assert_eq!(
+        recorder.events,
+        vec![
+            "enter operation",
+            "enter user",
+            "argument id",
+            "enter name",
+            "leave name",
+            "leave user",
+            "leave operation",
+        ]
+    );
+}
+
+fn selection_names(selection_set: &SelectionSet) -> Vec<&str> {
+    selection_set
+        .selections
+        .iter()
+        .map(|selection| match selection {
+            Selection::Field(field) => field.name.value.as_str(),
+            _ => "...",
+        })
+        .collect()
+}
+
+#[test]
+fn it_adds_typename_to_composite_selection_sets() {
+    let mut document =
+        parse_strict("{ user { name friends { __typename name } } }".to_string()).unwrap();
+
+    walk_document_mut(&mut AddTypenameVisitor, &mut document);
+
+    let operation = document.operations().next().unwrap();
+    assert_eq!(selection_names(&operation.selection_set), vec!["user"]);
+
+    let user = match &operation.selection_set.selections[0] {
+        Selection::Field(field) => field,
+        _ => panic!("Expected Field"),
+    };
+    let user_selection_set = user.selection_set.as_ref().unwrap();
+    assert_eq!(
+        selection_names(user_selection_set),
+        vec!["name", "friends", "__typename"]
+    );
+
+    let friends = match &user_selection_set.selections[1] {
+        Selection::Field(field) => field,
+        _ => panic!("Expected Field"),
+    };
+    assert_eq!(
+        selection_names(friends.selection_set.as_ref().unwrap()),
+        vec!["__typename", "name"]
+    );
+}
+
+struct RenameFirstField;
+
+impl VisitorMut for RenameFirstField {
+    fn enter_field(&mut self, node: &mut Field) -> VisitorAction {
+        node.name.value = String::from("renamed");
+        VisitorAction::Stop
+    }
+}
+
+struct SkipFieldChildren {
+    fields: usize,
+}
+
+impl VisitorMut for SkipFieldChildren {
+    fn enter_field(&mut self, _node: &mut Field) -> VisitorAction {
+        self.fields += 1;
+        VisitorAction::SkipChildren
+    }
+}
+
+#[test]
+fn it_follows_visitor_actions_when_walking_mutably() {
+    let source = "{ first { nested } second }";
+
+    let mut document = parse_strict(source.to_string()).unwrap();
+    walk_document_mut(&mut RenameFirstField, &mut document);
+    let operation = document.operations().next().unwrap();
+    assert_eq!(
+        selection_names(&operation.selection_set),
+        vec!["renamed", "second"]
+    );
+
+    let mut document = parse_strict(source.to_string()).unwrap();
+    let mut visitor = SkipFieldChildren { fields: 0 };
+    walk_document_mut(&mut visitor, &mut document);
+    assert_eq!(visitor.fields, 2);
+}
+
+struct TypeRecorder<'s> {
+    type_info: TypeInfoVisitor<'s>,
+    fields: Vec<String>,
+}
+
+impl Visitor for TypeRecorder<'_> {
+    fn enter_operation_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &OperationDefinition,
+    ) -> VisitorAction {
+        self.type_info.enter_operation_definition(ctx, node)
+    }
+
+    fn leave_operation_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &OperationDefinition,
+    ) -> VisitorAction {
+        self.type_info.leave_operation_definition(ctx, node)
+    }
+
+    fn enter_fragment_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &FragmentDefinition,
+    ) -> VisitorAction {
+        self.type_info.enter_fragment_definition(ctx, node)
+    }
+
+    fn leave_fragment_definition(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &FragmentDefinition,
+    ) -> VisitorAction {
+        self.type_info.leave_fragment_definition(ctx, node)
+    }
+
+    fn enter_inline_fragment(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &InlineFragment,
+    ) -> VisitorAction {
+        self.type_info.enter_inline_fragment(ctx, node)
+    }
+
+    fn leave_inline_fragment(
+        &mut self,
+        ctx: &VisitorContext,
+        node: &InlineFragment,
+    ) -> VisitorAction {
+        self.type_info.leave_inline_fragment(ctx, node)
+    }
+
+    fn enter_field(&mut self, ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        let parent_type = self.type_info.current_type().map(|t| t.name().to_string());
+        self.type_info.enter_field(ctx, node);
+
+        let field_type = match self.type_info.current_field_def() {
+            Some(field_def) => field_def.field_type.to_string(),
+            None => String::from("?"),
+        };
+        self.fields.push(format!(
+            "{}.{}: {}",
+            parent_type.unwrap_or_else(|| String::from("?")),
+            node.name.value,
+            field_type
+        ));
+
+        VisitorAction::Continue
+    }
+
+    fn leave_field(&mut self, ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        self.type_info.leave_field(ctx, node)
+    }
+}
+
+#[test]
+fn it_tracks_the_current_type_and_field() {
+    let schema_source = r#"
+        type Query {
+            user(id: ID!): User
+        }
+
+        interface Node {
+            id: ID!
+        }
+
+        type User implements Node {
+            id: ID!
+            name: String
+            friends: [User!]!
+        }
+
+        type Admin {
+            level: Int
+        }
+    "#;
+    let schema = Schema::from_document(&parse_strict(schema_source.to_string()).unwrap());
+
+    let source = r#"
+        {
+            user(id: 1) {
+                name
+                friends { id }
+                ... on Admin { level }
+                ... { missing }
+                __typename
+            }
+        }
+
+        fragment NodeFields on Node {
+            id
+        }
+    "#;
+    let document = parse_strict(source.to_string()).unwrap();
+
+    let mut recorder = TypeRecorder {
+        type_info: TypeInfoVisitor::new(&schema),
+        fields: Vec::new(),
+    };
+    walk_document(&mut recorder, &document);
+
+    assert_eq!(
+        recorder.fields,
+        vec![
+            "Query.user: User",
+            "User.name: String",
+            "User.friends: [User!]!",
+            "User.id: ID!",
+            "Admin.level: Int",
+            "User.missing: ?",
+            "User.__typename: ?",
+            "Node.id: ID!",
+        ]
+    );
+    assert!(recorder.type_info.current_type().is_none());
+}
+
+#[test]
+fn it_detects_a_self_referential_fragment() {
+    let source = r#"
+        { user { ...UserFields } }
+
+        fragment UserFields on User {
+            name
+            friends { ...UserFields }
+        }
+    "#;
+    let document = parse_strict(source.to_string()).unwrap();
+
+    assert_eq!(
+        CycleDetector::detect(&document),
+        vec![vec!["UserFields", "UserFields"]]
+    );
+}
+
+#[test]
+fn it_detects_a_cycle_between_two_fragments() {
+    let source = r#"
+        fragment A on User { ...B }
+        fragment B on User { ...A }
+    "#;
+    let document = parse_strict(source.to_string()).unwrap();
+
+    assert_eq!(CycleDetector::detect(&document), vec![vec!["A", "B", "A"]]);
+}
+
+#[test]
+fn it_does_not_report_a_chain_of_fragments_as_a_cycle() {
+    let source = r#"
+        fragment A on User { ...B ...C }
+        fragment B on User { ...C }
+        fragment C on User { name }
+    "#;
+    let document = parse_strict(source.to_string()).unwrap();
+
+    assert!(CycleDetector::detect(&document).is_empty());
+}
+
+#[test]
+fn it_collects_used_variables_and_fragments_through_nested_spreads() {
+    let source = r#"
+        query GetUser($id: ID!, $first: Int, $unused: Boolean, $filter: String) {
+            user(id: $id) {
+                ...UserFields
+            }
+        }
+
+        fragment UserFields on User {
+            name
+            ...FriendFields
+        }
+
+        fragment FriendFields on User {
+            friends(first: $first, where: { name: [$filter] }) {
+                ...UserFields
+                ...Unknown
+            }
+        }
+
+        fragment Unused on User {
+            email(format: $unused)
+        }
+    "#;
+    let document = parse_strict(source.to_string()).unwrap();
+    let operation = document.operations().next().unwrap();
+
+    let variables = UsedVariables::collect(operation, &document);
+    let mut variables: Vec<_> = variables.into_iter().collect();
+    variables.sort();
+    assert_eq!(variables, vec!["filter", "first", "id"]);
+
+    let fragments = UsedFragments::collect_transitive(operation, &document);
+    let mut fragments: Vec<_> = fragments.into_iter().collect();
+    fragments.sort();
+    assert_eq!(fragments, vec!["FriendFields", "Unknown", "UserFields"]);
+}
+
+#[derive(Default)]
+struct MutationFieldFinder {
+    fields: Vec<String>,
+}
+
+impl Visitor for MutationFieldFinder {
+    fn enter_field(&mut self, ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        let in_mutation = ctx.ancestors.iter().any(|ancestor| {
+            matches!(
+                ancestor,
+                AncestorRef::OperationDefinition(operation)
+                    if operation.operation == OperationType::Mutation
+            )
+        });
+
+        if in_mutation {
+            self.fields.push(node.name.value.clone());
+        }
+
+        VisitorAction::Continue
+    }
+}
+
+#[test]
+fn it_exposes_the_ancestors_of_each_node() {
+    let source = r#"
+        query GetUser { user { name } }
+        mutation SaveUser { saveUser { id } }
+    "#;
+    let document = parse_strict(source.to_string()).unwrap();
+
+    let mut finder = MutationFieldFinder::default();
+    walk_document(&mut finder, &document);
+
+    assert_eq!(finder.fields, vec!["saveUser", "id"]);
+}
+
+#[derive(Default)]
+struct AncestorRecorder {
+    ancestors: Vec<usize>,
+}
+
+impl Visitor for AncestorRecorder {
+    fn enter_field(&mut self, ctx: &VisitorContext, _node: &Field) -> VisitorAction {
+        assert!(matches!(ctx.parent(), Some(AncestorRef::SelectionSet(_))));
+        assert!(ctx.operation().is_some());
+        self.ancestors.push(ctx.ancestors.len());
+
+        VisitorAction::Continue
+    }
+
+    fn enter_variable(&mut self, ctx: &VisitorContext, _node: &Variable) -> VisitorAction {
+        assert!(matches!(ctx.parent(), Some(AncestorRef::Argument(_))));
+        self.ancestors.push(ctx.ancestors.len());
+
+        VisitorAction::Continue
+    }
+}
+
+#[test]
+fn it_pushes_and_pops_ancestors_around_children() {
+    let document = parse_strict("query ($id: ID) { user(id: $id) { name } }".to_string()).unwrap();
+
+    let mut recorder = AncestorRecorder::default();
+    walk_document(&mut recorder, &document);
+
+    // document, operation, selection set, then the field, argument and
+    // nested selection set
+    assert_eq!(recorder.ancestors, vec![3, 5, 5]);
+}
+
+#[test]
+fn it_measures_the_depth_of_a_flat_query() {
+    let document = parse_strict("{ name email }".to_string()).unwrap();
+
+    assert_eq!(MaxDepthVisitor::measure(&document), 1);
+}
+
+#[test]
+fn it_measures_the_depth_of_a_nested_query() {
+    let document = parse_strict("{ user { friends { name } email } version }".to_string()).unwrap();
+
+    assert_eq!(MaxDepthVisitor::measure(&document), 3);
+}
+
+#[test]
+fn it_measures_depth_through_inline_fragments_and_fragment_spreads() {
+    let source = r#"
+        {
+            node {
+                ... on User {
+                    ...UserFields
+                }
+            }
+        }
+
+        fragment UserFields on User {
+            friends {
+                ...FriendFields
+            }
+        }
+
+        fragment FriendFields on User {
+            name
+            ...UserFields
+        }
+
+        fragment Unused on User {
+            a { b { c { d } } }
+        }
+    "#;
+    let document = parse_strict(source.to_string()).unwrap();
+
+    assert_eq!(MaxDepthVisitor::measure(&document), 3);
+}
+
+/// The node at the first occurrence of `needle` in a single line document.
+fn node_at<'a>(document: &'a Document, source: &str, needle: &str) -> Option<AstNodeRef<'a>> {
+    let offset = source.find(needle).unwrap();
+    node_at_position(document, &Position::new(0, offset, offset))
+}
+
+#[test]
+fn it_finds_the_innermost_node_at_a_position() {
+    let source = concat!(
+        "query Q($id: ID @a) { user(id: $id) @skip(if: false) { ...F ... on User { name } } } ",
+        "fragment F on User { email }"
+    );
+    let document = parse_strict(source.to_string()).unwrap();
+
+    assert!(matches!(
+        node_at(&document, source, "query"),
+        Some(AstNodeRef::OperationDefinition(_))
+    ));
+    assert!(matches!(
+        node_at(&document, source, "$id:"),
+        Some(AstNodeRef::VariableDefinition(_))
+    ));
+    assert!(matches!(
+        node_at(&document, source, "ID"),
+        Some(AstNodeRef::NamedType(named_type)) if named_type.name.value == "ID"
+    ));
+    assert!(matches!(
+        node_at(&document, source, "@a"),
+        Some(AstNodeRef::Directive(directive)) if directive.name.value == "a"
+    ));
+    assert!(matches!(
+        node_at(&document, source, "user"),
+        Some(AstNodeRef::Field(field)) if field.name.value == "user"
+    ));
+    assert!(matches!(
+        node_at(&document, source, "id: $id"),
+        Some(AstNodeRef::Argument(argument)) if argument.name.value == "id"
+    ));
+    assert!(matches!(
+        node_at(&document, source, "skip"),
+        Some(AstNodeRef::Directive(directive)) if directive.name.value == "skip"
+    ));
+    assert!(matches!(
+        node_at(&document, source, "if:"),
+        Some(AstNodeRef::Argument(argument)) if argument.name.value == "if"
+    ));
+    assert!(matches!(
+        node_at(&document, source, "...F"),
+        Some(AstNodeRef::FragmentSpread(_))
+    ));
+    assert!(matches!(
+        node_at(&document, source, "... on"),
+        Some(AstNodeRef::InlineFragment(_))
+    ));
+    assert!(matches!(
+        node_at(&document, source, "User {"),
+        Some(AstNodeRef::NamedType(named_type)) if named_type.name.value == "User"
+    ));
+    assert!(matches!(
+        node_at(&document, source, "name"),
+        Some(AstNodeRef::Field(field)) if field.name.value == "name"
+    ));
+    assert!(matches!(
+        node_at(&document, source, "fragment"),
+        Some(AstNodeRef::FragmentDefinition(_))
+    ));
+    assert!(matches!(
+        node_at(&document, source, "email"),
+        Some(AstNodeRef::Field(field)) if field.name.value == "email"
+    ));
+}
+
+#[test]
+fn it_finds_no_node_outside_of_definitions() {
+    let source = "{ name }   ";
+    let document = parse_strict(source.to_string()).unwrap();
+
+    assert!(node_at_position(&document, &Position::new(0, 10, 10)).is_none());
+    assert!(node_at_position(&document, &Position::new(3, 0, 40)).is_none());
+}
+
+#[test]
+fn it_stops_walking_after_the_first_match() {
+    let source = "{ first { nested } second { target } third { target } }";
+    let document = parse_strict(source.to_string()).unwrap();
+
+    let mut visited = Vec::new();
+    let found = FindFirst::find(&document, |field| {
+        visited.push(field.name.value.clone());
+        field.name.value == "target"
+    });
+
+    assert_eq!(found.unwrap().name.value, "target");
+    assert_eq!(visited, vec!["first", "nested", "second", "target"]);
+}
+
+#[derive(Default)]
+struct SkipFieldChildrenCounter {
+    fields: Vec<String>,
+}
+
+impl Visitor for SkipFieldChildrenCounter {
+    fn enter_field(&mut self, _ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        self.fields.push(node.name.value.clone());
+
+        if node.name.value == "skipped" {
+            VisitorAction::SkipChildren
+        } else {
+            VisitorAction::Continue
+        }
+    }
+
+    fn leave_field(&mut self, _ctx: &VisitorContext, node: &Field) -> VisitorAction {
+        self.fields.push(format!("/{}", node.name.value));
+        VisitorAction::Continue
+    }
+}
+
+#[test]
+fn it_skips_the_children_of_a_node() {
+    let document = parse_strict("{ skipped { a } kept { b } }".to_string()).unwrap();
+
+    let mut visitor = SkipFieldChildrenCounter::default();
+    walk_document(&mut visitor, &document);
+
+    assert_eq!(
+        visitor.fields,
+        vec!["skipped", "/skipped", "kept", "b", "/b", "/kept"]
+    );
+}
+
+#[test]
+fn it_resolves_type_info_at_a_position() {
+    let schema_source = r#"
+        type Query { user: User }
+        type Post { title: String }
+        type User { id: ID, posts: [Post!] }
+    "#;
+    let schema = Schema::from_document(&parse_strict(schema_source.to_string()).unwrap());
+    let document = parse_strict("{ user { posts {  } } }".to_string()).unwrap();
+
+    // on the `posts` name
+    let info = type_info_at_position(&schema, &document, &Position::new(0, 10, 10));
+    assert_eq!(info.parent_type.unwrap().name(), "User");
+    assert_eq!(info.field_parent_type.unwrap().name(), "User");
+    assert_eq!(info.field_def.unwrap().name.value, "posts");
+
+    // inside the empty selection set of `posts`
+    let info = type_info_at_position(&schema, &document, &Position::new(0, 16, 16));
+    assert_eq!(info.parent_type.unwrap().name(), "Post");
+    assert_eq!(info.field_def.unwrap().name.value, "posts");
+
+    // outside every definition
+    let info = type_info_at_position(&schema, &document, &Position::new(1, 0, 23));
+    assert!(info.parent_type.is_none());
+    assert!(info.field_def.is_none());
+}
+
+#[test]
+fn it_finds_the_location_of_a_directive() {
+    let source = r#"
+        query Users($first: Int @a) @b {
+            users @c {
+                ...UserFields @d
+                ... on User @e { id }
+            }
+        }
+
+        fragment UserFields on User @f { id }
+    "#;
+    let document = parse_strict(source.to_string()).unwrap();
+
+    let location_of = |name: &str| {
+        let offset = source.find(&format!("@{}", name)).unwrap();
+        let line = source[..offset].matches('\n').count();
+        let character = offset - source[..offset].rfind('\n').unwrap() - 1;
+        directive_location_at(&document, &Position::new(line, character, offset))
+    };
+
+    assert_eq!(
+        location_of("a"),
+        Some(ExecutableDirectiveLocation::VariableDefinition)
+    );
+    assert_eq!(location_of("b"), Some(ExecutableDirectiveLocation::Query));
+    assert_eq!(location_of("c"), Some(ExecutableDirectiveLocation::Field));
+    assert_eq!(
+        location_of("d"),
+        Some(ExecutableDirectiveLocation::FragmentSpread)
+    );
+    assert_eq!(
+        location_of("e"),
+        Some(ExecutableDirectiveLocation::InlineFragment)
+    );
+    assert_eq!(
+        location_of("f"),
+        Some(ExecutableDirectiveLocation::FragmentDefinition)
+    );
+
+    assert_eq!(directive_location_at(&document, &Position::ZERO), None);
+}
+
+#[test]
+fn it_finds_the_innermost_field_at_a_position() {
+    let document =
+        parse_strict("{ user(id: 1) { ... on User { name @skip(if: true) } } }".to_string())
+            .unwrap();
+
+    let field_at = |character: usize| {
+        field_at_position(&document, &Position::new(0, character, character))
+            .map(|field| field.name.value.as_str())
+    };
+
+    // on an argument
+    assert_eq!(field_at(8), Some("user"));
+    // on a directive of a field inside an inline fragment
+    assert_eq!(field_at(37), Some("name"));
+    // on the inline fragment itself
+    assert_eq!(field_at(17), Some("user"));
+    assert_eq!(field_at(0), None);
+}
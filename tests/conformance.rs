@@ -0,0 +1,46 @@
+use std::fs;
+use std::path::Path;
+
+use gql_lsp::parser::parse;
+
+fn read_fixtures(directory: &str) -> Vec<(String, String)> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(directory);
+
+    let mut fixtures = fs::read_dir(&path)
+        .unwrap_or_else(|_| panic!("Unable to read fixture directory {:?}", path))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "graphql"))
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            let source = fs::read_to_string(&path).expect("Unable to read fixture");
+            (name, source)
+        })
+        .collect::<Vec<(String, String)>>();
+
+    fixtures.sort();
+    fixtures
+}
+
+#[test]
+fn it_parses_all_valid_fixtures() {
+    let fixtures = read_fixtures("valid");
+    assert!(!fixtures.is_empty());
+
+    for (name, source) in fixtures {
+        if let Err(diagnostics) = parse(&source) {
+            panic!("Expected {} to parse, got {:?}", name, diagnostics);
+        }
+    }
+}
+
+#[test]
+fn it_errs_for_all_invalid_fixtures() {
+    let fixtures = read_fixtures("invalid");
+    assert!(!fixtures.is_empty());
+
+    for (name, source) in fixtures {
+        assert!(parse(&source).is_err(), "Expected {} to fail parsing", name);
+    }
+}
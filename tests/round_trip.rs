@@ -0,0 +1,155 @@
+use gql_lsp::parser::parse;
+use gql_lsp::parser::types::AstEq;
+use gql_lsp::print::pretty_print::print;
+
+const SOURCE: &str = r#"
+query GetUser($id: ID!, $withFriends: Boolean = false, $first: Int = 10 @deprecated) @live {
+  user(id: $id) {
+    id
+    displayName: name
+    avatar(size: 64, format: PNG, options: { rounded: true, border: null, tags: ["a", "b"] })
+    ...UserFields
+    ... on Admin {
+      level
+    }
+    ... @include(if: $withFriends) {
+      friends(first: $first) {
+        ...UserFields @skip(if: false)
+      }
+    }
+  }
+}
+
+mutation SaveUser($input: UserInput!) {
+  saveUser(input: $input, note: "Saved \"quickly\"", weight: 1.5) {
+    id
+  }
+}
+
+subscription OnUserSaved {
+  userSaved {
+    id
+  }
+}
+
+fragment UserFields on User @cached(ttl: 60) {
+  name
+  email
+}
+
+schema @key {
+  query: Query
+  mutation: Mutation
+  subscription: Subscription
+}
+
+"""
+The root query type.
+
+Everything starts here.
+"""
+type Query implements Node & Entity @key(fields: "id") {
+  "Looks a user up by id"
+  user(id: ID!, limit: Int = 10 @deprecated): User
+  users(
+    "How many users to return"
+    first: Int = 10
+    after: String
+  ): [User!]!
+}
+
+type Mutation {
+  saveUser(input: UserInput!, note: String, weight: Float): User
+}
+
+type Subscription {
+  userSaved: User
+}
+
+interface Node {
+  id: ID!
+}
+
+interface Entity implements Node {
+  id: ID!
+}
+
+type User implements Node {
+  id: ID!
+  name: String
+  email: String
+  friends(first: Int): [User]
+}
+
+type Admin {
+  level: Int
+}
+
+union SearchResult @key = User | Admin
+
+enum Role {
+  ADMIN @deprecated(reason: "Use OWNER")
+  "Owns everything"
+  OWNER
+  GUEST
+}
+
+input UserInput {
+  name: String = "Ada"
+  roles: [Role!] = [GUEST]
+  settings: Settings = { theme: "dark", compact: true }
+}
+
+input Settings {
+  theme: String
+  compact: Boolean
+}
+
+"An ISO-8601 timestamp"
+scalar DateTime @specifiedBy(url: "https://example.com/datetime")
+
+"Caches the field for the given number of seconds"
+directive @cached(ttl: Int = 60) repeatable on FIELD_DEFINITION | OBJECT | FRAGMENT_DEFINITION
+
+extend schema @live {
+  subscription: Subscription
+}
+
+extend scalar DateTime @key
+
+extend type User implements Entity @key(fields: "id") {
+  role: Role
+}
+
+extend interface Node @key
+
+extend union SearchResult = Query
+
+extend enum Role {
+  OWNER_ADMIN
+}
+
+extend input Settings @deprecated {
+  language: String = "en"
+}
+"#;
+
+#[test]
+fn round_trip() {
+    let document = parse(SOURCE).unwrap().document;
+    let output = print(&document);
+
+    let reparsed = match parse(&output) {
+        Ok(result) => result.document,
+        Err(diagnostics) => panic!(
+            "Printed output failed to parse: {:?}\n{}",
+            diagnostics, output
+        ),
+    };
+
+    assert!(
+        document.ast_eq(&reparsed),
+        "Printed output does not match the source:\n{}",
+        output
+    );
+}
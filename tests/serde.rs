@@ -0,0 +1,48 @@
+#![cfg(feature = "serde")]
+
+use gql_lsp::parser::parse_strict;
+use gql_lsp::parser::types::{AstEq, Document};
+
+#[test]
+fn it_round_trips_documents_through_json() {
+    let source = r#"
+        "The root query"
+        type Query {
+            user(id: ID!, filter: UserFilter = { active: true, tags: ["a"] }): User
+        }
+
+        query GetUser($id: ID! = "1") @cached(ttl: 1.5) {
+            user(id: $id) {
+                name
+                ... on Admin { level }
+                ...UserFields
+            }
+        }
+
+        fragment UserFields on User {
+            email
+        }
+    "#;
+
+    let document = parse_strict(source.to_string()).unwrap();
+
+    let json = serde_json::to_string(&document).unwrap();
+    let deserialized: Document = serde_json::from_str(&json).unwrap();
+
+    assert!(document.ast_eq(&deserialized));
+}
+
+#[test]
+fn it_serializes_ranges_as_lsp_positions() {
+    let document = parse_strict("{ a }".to_string()).unwrap();
+
+    let json = serde_json::to_value(&document.position).unwrap();
+
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "start": { "line": 0, "character": 0 },
+            "end": { "line": 0, "character": 5 }
+        })
+    );
+}
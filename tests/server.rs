@@ -0,0 +1,790 @@
+use std::io::{self, BufRead, Cursor, Read};
+
+use gql_lsp::lsp::transport::read_lsp_message;
+use gql_lsp::parser::parse;
+use gql_lsp::parser::types::AstEq;
+use gql_lsp::serve;
+use serde_json::{json, Value};
+
+fn frame(message: &Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+/// Feeds the messages to the server and collects everything it writes back.
+fn run(messages: &[Value]) -> Vec<Value> {
+    let input = messages.iter().map(frame).collect::<String>();
+    let mut output = Vec::new();
+    serve(Cursor::new(input), &mut output).unwrap();
+    read_responses(output)
+}
+
+/// Like `run`, but calls `between` once the server has handled the `before`
+/// messages and before it reads the `after` ones, all in the one session.
+fn run_session(before: &[Value], between: impl FnOnce(), after: &[Value]) -> Vec<Value> {
+    let input = SessionInput {
+        before: Cursor::new(before.iter().map(frame).collect()),
+        between: Some(between),
+        after: Cursor::new(after.iter().map(frame).collect()),
+    };
+    let mut output = Vec::new();
+    serve(input, &mut output).unwrap();
+    read_responses(output)
+}
+
+struct SessionInput<F> {
+    before: Cursor<String>,
+    between: Option<F>,
+    after: Cursor<String>,
+}
+
+impl<F: FnOnce()> SessionInput<F> {
+    fn current(&mut self) -> &mut Cursor<String> {
+        if (self.before.position() as usize) < self.before.get_ref().len() {
+            return &mut self.before;
+        }
+
+        // the server only asks for more once it's done with what it has
+        if let Some(between) = self.between.take() {
+            between();
+        }
+        &mut self.after
+    }
+}
+
+impl<F: FnOnce()> Read for SessionInput<F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.current().read(buf)
+    }
+}
+
+impl<F: FnOnce()> BufRead for SessionInput<F> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.current().fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.current().consume(amount)
+    }
+}
+
+fn read_responses(output: Vec<u8>) -> Vec<Value> {
+    let mut reader = Cursor::new(output);
+    let mut responses = Vec::new();
+    while (reader.position() as usize) < reader.get_ref().len() {
+        responses.push(read_lsp_message(&mut reader).unwrap());
+    }
+    responses
+}
+
+fn request(id: i64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+#[test]
+fn it_runs_the_lifecycle() {
+    let responses = run(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        notification("initialized", json!({})),
+        request(2, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+        // never read, the server stops at `exit`
+        request(3, "shutdown", Value::Null),
+    ]);
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0]["id"], 1);
+    assert!(responses[0]["result"]["capabilities"].is_object());
+    assert_eq!(responses[0]["result"]["serverInfo"]["name"], "gql_lsp");
+    assert_eq!(
+        responses[1],
+        json!({ "jsonrpc": "2.0", "id": 2, "result": null })
+    );
+}
+
+#[test]
+fn it_rejects_requests_before_initialize() {
+    let responses = run(&[request(1, "shutdown", Value::Null)]);
+
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0]["error"]["code"], -32002);
+}
+
+#[test]
+fn it_rejects_requests_after_shutdown() {
+    let responses = run(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        request(2, "shutdown", Value::Null),
+        request(3, "textDocument/hover", json!({})),
+    ]);
+
+    assert_eq!(responses[2]["error"]["code"], -32600);
+}
+
+#[test]
+fn it_answers_unknown_methods_with_an_error() {
+    let responses = run(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        request(2, "workspace/unknown", json!({})),
+        notification("$/unknown", json!({})),
+    ]);
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[1]["id"], 2);
+    assert_eq!(responses[1]["error"]["code"], -32601);
+}
+
+fn initialize() -> Value {
+    request(1, "initialize", json!({ "capabilities": {} }))
+}
+
+fn did_open(uri: &str, text: &str) -> Value {
+    notification(
+        "textDocument/didOpen",
+        json!({
+            "textDocument": { "uri": uri, "languageId": "graphql", "version": 1, "text": text }
+        }),
+    )
+}
+
+#[test]
+fn it_publishes_diagnostics_on_open() {
+    let responses = run(&[
+        initialize(),
+        did_open("file:///query.graphql", "query { user(id: ) }"),
+    ]);
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[1]["method"], "textDocument/publishDiagnostics");
+
+    let params = &responses[1]["params"];
+    assert_eq!(params["uri"], "file:///query.graphql");
+    assert_eq!(params["version"], 1);
+
+    let diagnostics = params["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["severity"], 1);
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({ "line": 0, "character": 17 })
+    );
+    assert!(diagnostics[0]["message"].is_string());
+}
+
+#[test]
+fn it_publishes_no_diagnostics_for_a_valid_document() {
+    let responses = run(&[
+        initialize(),
+        did_open("file:///query.graphql", "query { user { id } }"),
+    ]);
+
+    assert_eq!(responses[1]["params"]["diagnostics"], json!([]));
+}
+
+fn did_change(uri: &str, version: i64, text: &str) -> Value {
+    notification(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": uri, "version": version },
+            "contentChanges": [{ "text": text }],
+        }),
+    )
+}
+
+#[test]
+fn it_republishes_diagnostics_on_change() {
+    let uri = "file:///query.graphql";
+    let responses = run(&[
+        initialize(),
+        did_open(uri, "query { user { id } }"),
+        did_change(uri, 2, "query { user { id }"),
+        did_change(uri, 3, "query { user { id } }"),
+    ]);
+
+    assert_eq!(
+        responses[0]["result"]["capabilities"]["textDocumentSync"],
+        1
+    );
+    assert_eq!(responses.len(), 4);
+
+    assert_eq!(responses[2]["params"]["version"], 2);
+    assert_eq!(
+        responses[2]["params"]["diagnostics"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    assert_eq!(responses[3]["params"]["version"], 3);
+    assert_eq!(responses[3]["params"]["diagnostics"], json!([]));
+}
+
+fn formatting(id: i64, uri: &str, options: Value) -> Value {
+    request(
+        id,
+        "textDocument/formatting",
+        json!({ "textDocument": { "uri": uri }, "options": options }),
+    )
+}
+
+#[test]
+fn it_formats_a_document() {
+    let uri = "file:///query.graphql";
+    let source = "query   GetUser($id:ID!){user(id:$id){\n id,\n\n    name }}\n";
+    let responses = run(&[
+        initialize(),
+        did_open(uri, source),
+        formatting(2, uri, json!({ "tabSize": 4, "insertSpaces": true })),
+    ]);
+
+    let edits = responses[2]["result"].as_array().unwrap();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(
+        edits[0]["range"],
+        json!({ "start": { "line": 0, "character": 0 }, "end": { "line": 4, "character": 0 } })
+    );
+
+    let formatted = edits[0]["newText"].as_str().unwrap();
+    assert!(formatted.contains("\n    user(id: $id) {\n        id\n"));
+
+    let original = parse(source).unwrap().document;
+    let reparsed = parse(formatted).unwrap().document;
+    assert!(original.ast_eq(&reparsed));
+}
+
+#[test]
+fn it_formats_with_tabs() {
+    let uri = "file:///query.graphql";
+    let responses = run(&[
+        initialize(),
+        did_open(uri, "{ user { id } }"),
+        formatting(2, uri, json!({ "tabSize": 4, "insertSpaces": false })),
+    ]);
+
+    let formatted = responses[2]["result"][0]["newText"].as_str().unwrap();
+    assert!(formatted.contains("\n\tuser {\n\t\tid\n"));
+}
+
+#[test]
+fn it_refuses_to_format_a_document_with_errors() {
+    let uri = "file:///query.graphql";
+    let responses = run(&[
+        initialize(),
+        did_open(uri, "{ user { id }"),
+        formatting(2, uri, json!({ "tabSize": 2, "insertSpaces": true })),
+    ]);
+
+    assert!(responses[2]["result"].is_null());
+    assert_eq!(responses[2]["error"]["code"], -32803);
+}
+
+#[test]
+fn it_formats_extensions_and_refuses_to_drop_comments() {
+    let uri = "file:///schema.graphql";
+    let source = "type Query { a: Int }\nextend type Query { b: Int }\n";
+    let responses = run(&[
+        initialize(),
+        did_open(uri, source),
+        formatting(2, uri, json!({ "tabSize": 2, "insertSpaces": true })),
+        did_change(uri, 2, &format!("# keep me\n{}", source)),
+        formatting(3, uri, json!({ "tabSize": 2, "insertSpaces": true })),
+    ]);
+
+    let formatted = responses[2]["result"][0]["newText"].as_str().unwrap();
+    assert_eq!(
+        formatted,
+        "type Query {\n  a: Int\n}\nextend type Query {\n  b: Int\n}\n"
+    );
+
+    assert!(responses[4]["result"].is_null());
+    assert_eq!(responses[4]["error"]["code"], -32803);
+}
+
+#[test]
+fn it_folds_definitions_selection_sets_and_comments() {
+    let uri = "file:///query.graphql";
+    let source = concat!(
+        "# Loads a user\n",
+        "# with their friends\n",
+        "query GetUser {\n",
+        "  user(id: 1) {\n",
+        "    friends {\n",
+        "      name\n",
+        "    }\n",
+        "    id\n",
+        "  }\n",
+        "}\n",
+        "\n",
+        "fragment UserFields on User { id }\n",
+    );
+    let responses = run(&[
+        initialize(),
+        did_open(uri, source),
+        request(
+            2,
+            "textDocument/foldingRange",
+            json!({ "textDocument": { "uri": uri } }),
+        ),
+    ]);
+
+    assert_eq!(
+        responses[0]["result"]["capabilities"]["foldingRangeProvider"],
+        true
+    );
+    assert_eq!(
+        responses[2]["result"],
+        json!([
+            { "startLine": 0, "endLine": 1, "kind": "comment" },
+            { "startLine": 2, "endLine": 9, "kind": "region" },
+            { "startLine": 3, "endLine": 8, "kind": "region" },
+            { "startLine": 4, "endLine": 6, "kind": "region" },
+        ])
+    );
+}
+
+fn rename(id: i64, uri: &str, line: usize, character: usize, new_name: &str) -> Value {
+    request(
+        id,
+        "textDocument/rename",
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": line, "character": character },
+            "newName": new_name,
+        }),
+    )
+}
+
+fn edit(start: (usize, usize), end: (usize, usize), new_text: &str) -> Value {
+    json!({
+        "range": {
+            "start": { "line": start.0, "character": start.1 },
+            "end": { "line": end.0, "character": end.1 },
+        },
+        "newText": new_text,
+    })
+}
+
+#[test]
+fn it_renames_a_fragment_across_documents() {
+    let fragments = "file:///fragments.graphql";
+    let query = "file:///query.graphql";
+    let responses = run(&[
+        initialize(),
+        did_open(fragments, "fragment UserFields on User {\n  id\n}\n"),
+        did_open(
+            query,
+            "{\n  user { ...UserFields }\n  me { ...UserFields }\n}\n",
+        ),
+        rename(2, query, 1, 14, "PersonFields"),
+        rename(3, fragments, 0, 12, "on"),
+        rename(4, fragments, 0, 24, "Person"),
+    ]);
+
+    assert_eq!(
+        responses[3]["result"],
+        json!({
+            "changes": {
+                fragments: [edit((0, 9), (0, 19), "PersonFields")],
+                query: [
+                    edit((1, 12), (1, 22), "PersonFields"),
+                    edit((2, 10), (2, 20), "PersonFields"),
+                ],
+            }
+        })
+    );
+
+    // `on` can't be a fragment name
+    assert_eq!(responses[4]["error"]["code"], -32602);
+
+    // the type condition isn't a fragment name
+    assert!(responses[5]["result"].is_null());
+}
+
+#[test]
+fn it_renames_a_variable_within_its_operation() {
+    let uri = "file:///query.graphql";
+    let source = concat!(
+        "query GetUser($id: ID!) {\n",
+        "  user(id: $id) { ...UserFields }\n",
+        "}\n",
+        "query GetOther($id: ID!) {\n",
+        "  user(id: $id) { id }\n",
+        "}\n",
+        "fragment UserFields on User {\n",
+        "  friends(ids: [$id]) { ...FriendFields }\n",
+        "}\n",
+        "fragment FriendFields on User {\n",
+        "  avatar(owner: { id: $id })\n",
+        "}\n",
+    );
+    let responses = run(&[
+        initialize(),
+        did_open(uri, source),
+        // on the definition
+        rename(2, uri, 0, 15, "$userId"),
+        // on a use, without the `$`
+        rename(3, uri, 1, 13, "userId"),
+        // on a use in a fragment
+        rename(4, uri, 7, 17, "userId"),
+    ]);
+
+    let expected = json!({
+        "changes": {
+            uri: [
+                edit((0, 15), (0, 17), "userId"),
+                edit((1, 12), (1, 14), "userId"),
+                edit((7, 17), (7, 19), "userId"),
+                edit((10, 23), (10, 25), "userId"),
+            ]
+        }
+    });
+    assert_eq!(responses[2]["result"], expected);
+    assert_eq!(responses[3]["result"], expected);
+
+    assert!(responses[4]["result"].is_null());
+}
+
+/// Writes the schema to a fresh directory, returning the directory's URI.
+fn schema_root(name: &str, schema: &str) -> (std::path::PathBuf, String) {
+    schema_root_with_file(name, "schema.graphql", schema)
+}
+
+fn schema_root_with_file(
+    name: &str,
+    file_name: &str,
+    schema: &str,
+) -> (std::path::PathBuf, String) {
+    let root = std::env::temp_dir().join(format!("gql_lsp_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join(file_name), schema).unwrap();
+    let root_uri = format!("file://{}", root.display());
+    (root, root_uri)
+}
+
+/// The schema is loaded once the client confirms it is initialized.
+fn initialized() -> Value {
+    notification("initialized", json!({}))
+}
+
+fn initialize_with_schema(root_uri: &str) -> Value {
+    request(
+        1,
+        "initialize",
+        json!({
+            "capabilities": {},
+            "rootUri": root_uri,
+            "initializationOptions": { "schemaPath": "schema.graphql" },
+        }),
+    )
+}
+
+fn text_document_position(
+    id: i64,
+    method: &str,
+    uri: &str,
+    line: usize,
+    character: usize,
+) -> Value {
+    request(
+        id,
+        method,
+        json!({
+            "textDocument": { "uri": uri },
+            "position": { "line": line, "character": character },
+        }),
+    )
+}
+
+#[test]
+fn it_loads_the_schema_from_the_schema_path() {
+    let (root, root_uri) = schema_root(
+        "schema_path",
+        "type Query { user: User }\ntype User { id: ID!\n \"The name\" name: String }\n",
+    );
+    let uri = "file:///query.graphql";
+    let responses = run(&[
+        initialize_with_schema(&root_uri),
+        initialized(),
+        did_open(uri, "{ user { name } }"),
+        text_document_position(2, "textDocument/hover", uri, 0, 10),
+        text_document_position(3, "textDocument/completion", uri, 0, 9),
+        text_document_position(4, "textDocument/definition", uri, 0, 10),
+    ]);
+    std::fs::remove_dir_all(root).unwrap();
+
+    assert_eq!(responses.len(), 5);
+
+    let hover = responses[2]["result"]["contents"]["value"]
+        .as_str()
+        .unwrap();
+    assert!(hover.contains("**name**: String"));
+    assert!(hover.contains("The name"));
+
+    let labels = responses[3]["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(labels, ["id", "name"]);
+
+    let definition_uri = responses[4]["result"]["uri"].as_str().unwrap();
+    assert!(definition_uri.starts_with("file:///"));
+    assert!(definition_uri.ends_with("/schema.graphql"));
+}
+
+#[test]
+fn it_reloads_the_schema_when_it_changes() {
+    let (root, root_uri) = schema_root("reload", "type Query { user: ID }\n");
+    let schema_path = root.join("schema.graphql");
+    let schema_uri = format!("{}/schema.graphql", root_uri);
+    let uri = "file:///query.graphql";
+
+    let before = [
+        initialize_with_schema(&root_uri),
+        initialized(),
+        did_open(uri, "{ user }"),
+        text_document_position(2, "textDocument/hover", uri, 0, 3),
+    ];
+    let did_change_watched_files = notification(
+        "workspace/didChangeWatchedFiles",
+        json!({ "changes": [{ "uri": schema_uri, "type": 2 }] }),
+    );
+    let hover_again = text_document_position(3, "textDocument/hover", uri, 0, 3);
+    let rewrite = |schema: &'static str| {
+        let schema_path = schema_path.clone();
+        move || std::fs::write(schema_path, schema).unwrap()
+    };
+
+    let reloaded = run_session(
+        &before,
+        rewrite("type Query { user: String }\n"),
+        &[did_change_watched_files, hover_again.clone()],
+    );
+    // without the notification the schema that was loaded is kept
+    std::fs::write(&schema_path, "type Query { user: ID }\n").unwrap();
+    let unchanged = run_session(
+        &before,
+        rewrite("type Query { user: Int }\n"),
+        &[hover_again],
+    );
+    std::fs::remove_dir_all(root).unwrap();
+
+    let hover = |responses: &[Value], id: i64| {
+        let response = responses.iter().find(|message| message["id"] == id);
+        response.unwrap()["result"]["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+    assert!(hover(&reloaded, 2).contains("**user**: ID"));
+    assert!(hover(&reloaded, 3).contains("**user**: String"));
+    assert!(hover(&unchanged, 2).contains("**user**: ID"));
+    assert!(hover(&unchanged, 3).contains("**user**: ID"));
+}
+
+#[test]
+fn it_registers_a_watcher_for_the_schema_file() {
+    let (root, root_uri) = schema_root("watcher", "type Query { user: ID }\n");
+    let initialize = |capabilities: Value| {
+        request(
+            1,
+            "initialize",
+            json!({
+                "capabilities": capabilities,
+                "rootUri": root_uri,
+                "initializationOptions": { "schemaPath": "schema.graphql" },
+            }),
+        )
+    };
+    let dynamic = run(&[
+        initialize(json!({
+            "workspace": { "didChangeWatchedFiles": { "dynamicRegistration": true } }
+        })),
+        initialized(),
+    ]);
+    let registration = dynamic
+        .iter()
+        .find(|message| message["method"] == "client/registerCapability")
+        .unwrap();
+    assert!(registration["id"].is_number());
+
+    let registrations = &registration["params"]["registrations"];
+    assert_eq!(
+        registrations[0]["method"],
+        "workspace/didChangeWatchedFiles"
+    );
+    let glob_pattern = registrations[0]["registerOptions"]["watchers"][0]["globPattern"]
+        .as_str()
+        .unwrap();
+    assert_eq!(glob_pattern, root.join("schema.graphql").to_string_lossy());
+
+    // clients without dynamic registration aren't sent the request
+    let fixed = run(&[initialize(json!({})), initialized()]);
+    std::fs::remove_dir_all(root).unwrap();
+
+    assert!(fixed
+        .iter()
+        .all(|message| message["method"] != "client/registerCapability"));
+}
+
+#[test]
+fn it_shows_a_message_when_the_schema_fails_to_load() {
+    let (root, root_uri) = schema_root("invalid", "type Query {\n");
+    let invalid = run(&[initialize_with_schema(&root_uri), initialized()]);
+    std::fs::remove_dir_all(root).unwrap();
+    let missing = run(&[
+        initialize_with_schema("file:///does/not/exist"),
+        initialized(),
+    ]);
+
+    for responses in [invalid, missing] {
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[1]["method"], "window/showMessage");
+        assert_eq!(responses[1]["params"]["type"], 1);
+        let message = responses[1]["params"]["message"].as_str().unwrap();
+        assert!(message.starts_with("Failed to load schema: "));
+        assert!(message.contains("schema.graphql"));
+    }
+}
+
+#[test]
+fn it_loads_the_schema_from_an_introspection_result() {
+    let (root, root_uri) = schema_root_with_file(
+        "introspection",
+        "schema.json",
+        include_str!("fixtures/introspection.json"),
+    );
+    let uri = "file:///query.graphql";
+    let responses = run(&[
+        request(
+            1,
+            "initialize",
+            json!({
+                "capabilities": {},
+                "rootUri": root_uri,
+                "initializationOptions": { "schemaIntrospectionPath": "schema.json" },
+            }),
+        ),
+        initialized(),
+        did_open(uri, "{ user(id: 1) { id } }"),
+        text_document_position(2, "textDocument/hover", uri, 0, 3),
+        text_document_position(3, "textDocument/definition", uri, 0, 3),
+    ]);
+    std::fs::remove_dir_all(root).unwrap();
+
+    assert_eq!(responses.len(), 4);
+
+    let hover = responses[2]["result"]["contents"]["value"]
+        .as_str()
+        .unwrap();
+    assert!(hover.contains("**user**: User"));
+    assert!(hover.contains("Looks up a user by id"));
+
+    // there's no SDL to point into
+    assert!(responses[3]["result"].is_null());
+}
+
+fn initialize_with_progress(root_uri: &str) -> Value {
+    request(
+        1,
+        "initialize",
+        json!({
+            "capabilities": { "window": { "workDoneProgress": true } },
+            "rootUri": root_uri,
+            "initializationOptions": { "schemaPath": "schema.graphql" },
+        }),
+    )
+}
+
+#[test]
+fn it_reports_progress_while_loading_the_schema() {
+    let (root, root_uri) = schema_root("progress", "type Query { user: ID }\n");
+    let responses = run(&[
+        initialize_with_progress(&root_uri),
+        initialized(),
+        json!({ "jsonrpc": "2.0", "id": 1, "result": null }),
+    ]);
+    std::fs::remove_dir_all(root).unwrap();
+
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(
+        responses[0]["result"]["capabilities"]["workDoneProgress"],
+        true
+    );
+
+    assert_eq!(responses[1]["id"], 1);
+    assert_eq!(responses[1]["method"], "window/workDoneProgress/create");
+    let token = &responses[1]["params"]["token"];
+    assert!(token.is_string());
+
+    // a load this small is over before any report is due
+    assert_eq!(responses.len(), 4);
+    assert_eq!(
+        responses[2],
+        json!({
+            "jsonrpc": "2.0",
+            "method": "$/progress",
+            "params": {
+                "token": token,
+                "value": {
+                    "kind": "begin",
+                    "title": "Loading GraphQL schema",
+                    "message": "schema.graphql",
+                },
+            },
+        })
+    );
+    assert_eq!(responses[3]["params"]["token"], *token);
+    assert_eq!(responses[3]["params"]["value"], json!({ "kind": "end" }));
+}
+
+#[test]
+fn it_waits_for_the_progress_token_before_loading() {
+    let (root, root_uri) = schema_root("progress_pending", "type Query { user: ID }\n");
+    let uri = "file:///query.graphql";
+    let hover = text_document_position(2, "textDocument/hover", uri, 0, 3);
+
+    let unanswered = run(&[
+        initialize_with_progress(&root_uri),
+        initialized(),
+        did_open(uri, "{ user }"),
+        hover.clone(),
+    ]);
+
+    let refused = run(&[
+        initialize_with_progress(&root_uri),
+        initialized(),
+        json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32603, "message": "no" } }),
+        did_open(uri, "{ user }"),
+        hover,
+    ]);
+    std::fs::remove_dir_all(root).unwrap();
+
+    // nothing is loaded until the client answers
+    assert_eq!(unanswered.len(), 4);
+    assert!(unanswered[3]["result"].is_null());
+
+    // a token that wasn't created is never used, but the schema still loads
+    assert_eq!(refused.len(), 4);
+    assert!(refused
+        .iter()
+        .all(|message| message["method"] != "$/progress"));
+    let contents = refused[3]["result"]["contents"]["value"].as_str().unwrap();
+    assert!(contents.contains("**user**: ID"));
+}
+
+#[test]
+fn it_only_reports_progress_to_clients_that_support_it() {
+    let (root, root_uri) = schema_root("no_progress", "type Query { user: ID }\n");
+    let responses = run(&[initialize_with_schema(&root_uri), initialized()]);
+    std::fs::remove_dir_all(root).unwrap();
+
+    assert_eq!(responses.len(), 1);
+}